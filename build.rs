@@ -0,0 +1,18 @@
+use std::env;
+
+// Writes the C header for the functions in src/ffi.rs into OUT_DIR, leaving the source tree alone.
+// tests/ffi_tests.rs checks it against the copy in include/
+fn main() {
+  println!("cargo:rerun-if-changed=src/ffi.rs");
+  println!("cargo:rerun-if-changed=cbindgen.toml");
+
+  let crate_directory = env::var("CARGO_MANIFEST_DIR").unwrap();
+  let out_directory = env::var("OUT_DIR").unwrap();
+
+  match cbindgen::generate(&crate_directory) {
+    Ok(bindings) => {
+      bindings.write_to_file(format!("{out_directory}/tictactoe_with_ai.h"));
+    }
+    Err(error) => println!("cargo:warning=Failed to generate the C header: '{error}'"),
+  }
+}
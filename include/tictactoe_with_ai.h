@@ -0,0 +1,111 @@
+/* Generated by cbindgen from src/ffi.rs, don't edit by hand. */
+
+#ifndef TICTACTOE_WITH_AI_H
+#define TICTACTOE_WITH_AI_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * A game against the bot, only ever handled through a pointer.
+ */
+typedef struct TttGame TttGame;
+
+typedef enum TttStatus {
+  TTT_STATUS_OK = 0,
+  TTT_STATUS_NULL_POINTER = 1,
+  TTT_STATUS_OUT_OF_RANGE = 2,
+  TTT_STATUS_TILE_TAKEN = 3,
+  TTT_STATUS_GAME_OVER = 4,
+  TTT_STATUS_NOT_YOUR_TURN = 5,
+} TttStatus;
+
+typedef enum TttSymbol {
+  TTT_SYMBOL_EMPTY = 0,
+  TTT_SYMBOL_X = 1,
+  TTT_SYMBOL_O = 2,
+  /**
+   * Only on boards set up with blocked tiles, never in ttt_game_new's games.
+   */
+  TTT_SYMBOL_BLOCKED = 3,
+} TttSymbol;
+
+typedef enum TttOutcome {
+  TTT_OUTCOME_IN_PROGRESS = 0,
+  TTT_OUTCOME_X_WON = 1,
+  TTT_OUTCOME_O_WON = 2,
+  TTT_OUTCOME_DRAW = 3,
+} TttOutcome;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Starts a game where the caller places player_symbol, TTT_SYMBOL_X or TTT_SYMBOL_O,
+ * and the bot places the other. Anything else picks randomly.
+ * X places first, so if the bot is X call ttt_game_bot_move before placing.
+ *
+ * The game must be freed with ttt_game_free.
+ */
+struct TttGame *ttt_game_new(uint32_t player_symbol);
+
+/**
+ * # Safety
+ *
+ * game must come from ttt_game_new and not have been freed already.
+ * Passing null does nothing.
+ */
+void ttt_game_free(struct TttGame *game);
+
+/**
+ * Places the caller's symbol at row and column.
+ *
+ * # Safety
+ *
+ * game must be null or come from ttt_game_new and not have been freed.
+ */
+enum TttStatus ttt_game_place_tile(struct TttGame *game, size_t row, size_t column);
+
+/**
+ * Has the bot place its symbol, writing where it placed into row and column.
+ * Either pointer can be null if the caller doesn't need it.
+ *
+ * # Safety
+ *
+ * game must be null or come from ttt_game_new and not have been freed.
+ * row and column must be null or valid to write to.
+ */
+enum TttStatus ttt_game_bot_move(struct TttGame *game, size_t *row, size_t *column);
+
+/**
+ * # Safety
+ *
+ * game must be null or come from ttt_game_new and not have been freed.
+ * Null or out of range tiles are reported as empty.
+ */
+enum TttSymbol ttt_game_get_tile(const struct TttGame *game, size_t row, size_t column);
+
+/**
+ * # Safety
+ *
+ * game must be null or come from ttt_game_new and not have been freed.
+ * Null is reported as TTT_OUTCOME_IN_PROGRESS.
+ */
+enum TttOutcome ttt_game_outcome(const struct TttGame *game);
+
+/**
+ * # Safety
+ *
+ * game must be null or come from ttt_game_new and not have been freed.
+ */
+enum TttSymbol ttt_game_player_symbol(const struct TttGame *game);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* TICTACTOE_WITH_AI_H */
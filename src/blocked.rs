@@ -0,0 +1,306 @@
+//! Blocked mode, started with `blocked [layout file [win length]]`.
+//!
+//! Some tiles can't be placed on, and lines through them can't be made. With
+//! no layout file a 4x4 board gets a few blocked tiles at random, otherwise
+//! the file draws the board a row per line, '.' for a tile and '#' for a
+//! blocked one, so boards can be any shape that fits in a rectangle:
+//!
+//! ```text
+//! #..#
+//! ....
+//! ....
+//! #..#
+//! ```
+//!
+//! Tiles are written like on the flat boards, e.g. "c4".
+
+use crate::bot::{Difficulty, SearchBudget};
+use crate::coordinate_methods::Coordinates;
+use crate::gameboard::BoardStates;
+use crate::gameplay::*;
+use crate::grid_board::*;
+use crate::mcts::player_of;
+use crate::search::best_move_within;
+use crate::session::{play_session, two_player_seats};
+use crate::solver::opposing_symbol;
+use rand::prelude::*;
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::time::Duration;
+
+pub const DEFAULT_SIZE: usize = 4;
+pub const DEFAULT_WIN_LENGTH: usize = 3;
+pub const DEFAULT_BLOCKED_TILES: usize = 3;
+
+const BLOCKED_USAGE: &str =
+  "expected 'blocked', 'blocked <layout file>' or 'blocked <layout file> <win length>'";
+
+/// Reads a layout of '.' and '#' rows into a board, ignoring blank lines and spaces.
+pub fn parse_layout(layout: &str, win_length: usize) -> Result<GridBoard, Box<dyn Error>> {
+  let rows: Vec<Vec<char>> = layout
+    .lines()
+    .map(|row| row.chars().filter(|tile| !tile.is_whitespace()).collect())
+    .filter(|row: &Vec<char>| !row.is_empty())
+    .collect();
+
+  let width = rows.first().map_or(0, Vec::len);
+
+  if let Some((index, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+    return Err(Box::from(format!(
+      "row {} of the layout is {} tiles long, the first is {width}",
+      index + 1,
+      row.len()
+    )));
+  }
+
+  let mut grid_board = GridBoard::new(width, rows.len(), win_length)?;
+
+  for (row, tiles) in rows.iter().enumerate() {
+    for (column, tile) in tiles.iter().enumerate() {
+      match tile {
+        '.' => (),
+        '#' => grid_board.place_tile(&(row, column), BoardStates::Blocked),
+        _ => return Err(Box::from(format!("unknown tile '{tile}' in the layout"))),
+      }
+    }
+  }
+
+  grid_board.last_modified_tile = None;
+
+  Ok(grid_board)
+}
+
+/// A board with blocked_tiles of its tiles blocked at random, rerolled until a line can still be made.
+pub fn random_layout(
+  size: usize,
+  win_length: usize,
+  blocked_tiles: usize,
+) -> Result<GridBoard, Box<dyn Error>> {
+  if blocked_tiles >= size * size {
+    return Err(Box::from(format!(
+      "a {size}x{size} board can't have {blocked_tiles} blocked tiles"
+    )));
+  }
+
+  loop {
+    let mut grid_board = GridBoard::new(size, size, win_length)?;
+
+    for coords in grid_board
+      .all_coordinates()
+      .choose_multiple(&mut thread_rng(), blocked_tiles)
+    {
+      grid_board.place_tile(coords, BoardStates::Blocked);
+    }
+
+    grid_board.last_modified_tile = None;
+
+    if !grid_board.lines().is_empty() {
+      return Ok(grid_board);
+    }
+  }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct BlockedGame {
+  pub grid_board: GridBoard,
+  pub next_symbol: BoardStates,
+  /// How many lines go through each tile, row by row. Only blocked tiles
+  /// take lines away, so this is worked out once.
+  lines_through: Vec<usize>,
+}
+
+impl BlockedGame {
+  pub fn new(grid_board: GridBoard) -> Self {
+    let mut lines_through = vec![0; grid_board.width * grid_board.height];
+
+    for coords in grid_board.lines().iter().flatten() {
+      lines_through[coords.0 * grid_board.width + coords.1] += 1;
+    }
+
+    BlockedGame {
+      grid_board,
+      next_symbol: BoardStates::X,
+      lines_through,
+    }
+  }
+
+  /// None while the game can still be played.
+  pub fn result(&self) -> Option<GameState> {
+    self.grid_board.result()
+  }
+
+  /// Places next_symbol on coords, erroring if it can't go there.
+  pub fn place(&mut self, coords: &Coordinates) -> Result<(), Box<dyn Error>> {
+    if self.result().is_some() {
+      return Err(Box::from("the game is over"));
+    }
+
+    match self.grid_board.get_board_state(coords) {
+      BoardStates::Empty => (),
+      BoardStates::Blocked => {
+        return Err(Box::from(format!(
+          "{} is blocked",
+          row_column_notation(coords)
+        )))
+      }
+      _ => {
+        return Err(Box::from(format!(
+          "{} is already taken",
+          row_column_notation(coords)
+        )))
+      }
+    }
+
+    self.grid_board.place_tile(coords, self.next_symbol);
+    self.next_symbol = opposing_symbol(self.next_symbol);
+
+    Ok(())
+  }
+
+  pub fn lines_through(&self, coords: &Coordinates) -> usize {
+    self.lines_through[coords.0 * self.grid_board.width + coords.1]
+  }
+
+  /// Empty tiles next to a placed X or O, the ones on the most lines first.
+  /// Anywhere else is only worth searching before anything is placed.
+  pub fn candidate_tiles(&self) -> Vec<Coordinates> {
+    let empty_tiles = self.grid_board.empty_tiles();
+
+    let near_pieces: Vec<Coordinates> = empty_tiles
+      .iter()
+      .filter(|coords| {
+        self
+          .grid_board
+          .neighbors(coords)
+          .iter()
+          .any(|next| self.grid_board.get_board_state(next).is_symbol())
+      })
+      .copied()
+      .collect();
+
+    let mut candidates = if near_pieces.is_empty() {
+      empty_tiles
+    } else {
+      near_pieces
+    };
+
+    candidates.sort_by_key(|coords| std::cmp::Reverse(self.lines_through(coords)));
+
+    candidates
+  }
+}
+
+/// How deep the bot can search for each difficulty, and for how long. Big
+/// layouts run out of time long before max_depth.
+pub fn search_budget(difficulty: Difficulty) -> SearchBudget {
+  match difficulty {
+    Difficulty::Easy => SearchBudget {
+      max_depth: 1,
+      time_limit: Duration::from_millis(200),
+    },
+    Difficulty::Medium => SearchBudget {
+      max_depth: 3,
+      time_limit: Duration::from_secs(1),
+    },
+    Difficulty::Hard => SearchBudget {
+      max_depth: 5,
+      time_limit: Duration::from_secs(2),
+    },
+  }
+}
+
+impl GridGame for BlockedGame {
+  fn grid_board(&self) -> &GridBoard {
+    &self.grid_board
+  }
+
+  fn next_symbol(&self) -> BoardStates {
+    self.next_symbol
+  }
+
+  fn place(&mut self, coords: &Coordinates) -> Result<(), Box<dyn Error>> {
+    BlockedGame::place(self, coords)
+  }
+
+  fn search_tiles(&self) -> Vec<Coordinates> {
+    self.candidate_tiles()
+  }
+}
+
+/// The best tile for next_symbol within budget, see search::best_move_within.
+pub fn best_move(blocked_game: &BlockedGame, budget: SearchBudget) -> Option<Coordinates> {
+  let candidates = blocked_game.candidate_tiles();
+  let nothing_placed = !blocked_game
+    .grid_board
+    .tiles
+    .iter()
+    .any(BoardStates::is_symbol);
+
+  // Opening on the most lines is as good as searching, and the whole board would be slow.
+  if nothing_placed {
+    return candidates.first().copied();
+  }
+
+  best_move_within(blocked_game, &candidates, budget)
+}
+
+pub fn blocked(
+  gameconfig: GameConfig,
+  gamemode_arguments: &[String],
+) -> Result<(), Box<dyn Error>> {
+  let grid_board = match gamemode_arguments {
+    [] => random_layout(DEFAULT_SIZE, DEFAULT_WIN_LENGTH, DEFAULT_BLOCKED_TILES)?,
+    [path] => read_layout(path, DEFAULT_WIN_LENGTH)?,
+    [path, win_length] => {
+      let win_length = win_length
+        .parse::<usize>()
+        .map_err(|_| format!("the win length has to be a whole number, got '{win_length}'"))?;
+
+      read_layout(path, win_length)?
+    }
+    _ => return Err(Box::from(BLOCKED_USAGE)),
+  };
+
+  blocked_session(
+    gameconfig,
+    BlockedGame::new(grid_board),
+    io::stdin().lock(),
+    io::stdout(),
+  )?;
+
+  Ok(())
+}
+
+fn read_layout(path: &str, win_length: usize) -> Result<GridBoard, Box<dyn Error>> {
+  let layout =
+    fs::read_to_string(path).map_err(|error| format!("failed to read {path}: {error}"))?;
+
+  parse_layout(&layout, win_length)
+}
+
+pub fn blocked_session<R: BufRead, W: Write>(
+  gameconfig: GameConfig,
+  blocked_game: BlockedGame,
+  input: R,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
+  let budget = search_budget(gameconfig.bot.difficulty);
+
+  writeln!(
+    output,
+    "{} {:?} | Bot {:?} | {} in a row wins, nothing goes on #",
+    gameconfig.player_name,
+    gameconfig.player_symbol,
+    gameconfig.bot.bot_symbol,
+    blocked_game.grid_board.win_length
+  )?;
+
+  play_session(
+    blocked_game,
+    &two_player_seats(&gameconfig.player_name, player_of(gameconfig.player_symbol)),
+    |blocked_game| best_move(blocked_game, budget),
+    input,
+    output,
+  )
+}
@@ -0,0 +1,339 @@
+//! Everything that draws the board. Each theme has its own Renderer,
+//! picked with `--theme <name>` or `theme = "<name>"` in the config file.
+//!
+//! ```text
+//! ascii             box                   emoji             compact         numbered
+//!      1   2   3         1   2   3           1   2   3
+//!  a   X | O |        ┌───┬───┬───┐       a  ❌  ⭕  ⬜     xo./.[x]./...   X|O|3
+//!     ---+---+---   a │ X │ O │   │       b  ⬜ [❌] ⬜                     4|[X]|6
+//!  b     |[X]|        ├───┼───┼───┤       c  ⬜  ⬜  ⬜                     7|8|9
+//!     ---+---+---   b │   │[X]│   │
+//!  c     |   |        ├───┼───┼───┤
+//!                   c │   │   │   │
+//!                     └───┴───┴───┘
+//! ```
+//!
+//! Every theme highlights the same way. With colors each symbol gets its own
+//! color, the last placed tile is shown reversed and a winning row is highlighted.
+//! Without colors the last placed tile is wrapped in [ ] and a winning row in * *,
+//! keeping the ascii theme plain ASCII.
+
+use crate::coordinate_methods::*;
+use crate::gameboard::*;
+use crossterm::style::Stylize;
+use std::env;
+use std::error::Error;
+use std::io::{self, IsTerminal, Write};
+use std::str::FromStr;
+
+const ROW_SEPARATOR: &str = "    ---+---+---";
+
+pub trait Renderer {
+  fn render(&self, gameboard: &BoardConfig) -> String;
+
+  fn write_board(&self, gameboard: &BoardConfig, output: &mut dyn Write) -> io::Result<()> {
+    write!(output, "{}", self.render(gameboard))
+  }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum Theme {
+  #[default]
+  Ascii,
+  Box,
+  Emoji,
+  Compact,
+  Numbered,
+}
+
+pub struct AsciiRenderer {
+  pub colors: bool,
+}
+
+pub struct BoxRenderer {
+  pub colors: bool,
+}
+
+pub struct EmojiRenderer {
+  pub colors: bool,
+}
+
+pub struct CompactRenderer {
+  pub colors: bool,
+}
+
+pub struct NumberedRenderer {
+  pub colors: bool,
+}
+
+impl Theme {
+  pub const NAMES: [&'static str; 5] = ["ascii", "box", "emoji", "compact", "numbered"];
+
+  pub fn renderer(self, colors: bool) -> Box<dyn Renderer> {
+    match self {
+      Theme::Ascii => Box::new(AsciiRenderer { colors }),
+      Theme::Box => Box::new(BoxRenderer { colors }),
+      Theme::Emoji => Box::new(EmojiRenderer { colors }),
+      Theme::Compact => Box::new(CompactRenderer { colors }),
+      Theme::Numbered => Box::new(NumberedRenderer { colors }),
+    }
+  }
+
+  /// The renderer for this theme, with colors if stdout supports them.
+  pub fn stdout_renderer(self) -> Box<dyn Renderer> {
+    self.renderer(stdout_supports_colors())
+  }
+}
+
+impl FromStr for Theme {
+  type Err = Box<dyn Error>;
+
+  fn from_str(theme: &str) -> Result<Self, Self::Err> {
+    match theme.to_lowercase().trim() {
+      "ascii" => Ok(Theme::Ascii),
+      "box" | "unicode" => Ok(Theme::Box),
+      "emoji" => Ok(Theme::Emoji),
+      "compact" => Ok(Theme::Compact),
+      "numbered" => Ok(Theme::Numbered),
+      _ => Err(Box::from(format!(
+        "unknown theme '{theme}', expected one of: {}",
+        Theme::NAMES.join(", ")
+      ))),
+    }
+  }
+}
+
+impl Renderer for AsciiRenderer {
+  fn render(&self, gameboard: &BoardConfig) -> String {
+    render_board(gameboard, self.colors)
+  }
+}
+
+impl Renderer for BoxRenderer {
+  fn render(&self, gameboard: &BoardConfig) -> String {
+    let horizontal_line = |left: &str, middle: &str, right: &str| {
+      format!("   {left}{}{right}", ["───"; GRID_SIZE].join(middle))
+    };
+
+    let mut lines = vec![column_labels(), horizontal_line("┌", "┬", "┐")];
+
+    for row in 0..GRID_SIZE {
+      if row != 0 {
+        lines.push(horizontal_line("├", "┼", "┤"));
+      }
+
+      lines.push(format!(
+        " {} │{}│",
+        row_label(row),
+        styled_row(gameboard, row, self.colors, " ", grid_symbol).join("│")
+      ));
+    }
+
+    lines.push(horizontal_line("└", "┴", "┘"));
+
+    lines.join("\n") + "\n"
+  }
+}
+
+/// Emoji are two columns wide, so each tile takes four.
+impl Renderer for EmojiRenderer {
+  fn render(&self, gameboard: &BoardConfig) -> String {
+    let mut lines = vec![format!(
+      "   {}",
+      (1..=GRID_SIZE)
+        .map(|column| format!(" {column}  "))
+        .collect::<String>()
+        .trim_end()
+    )];
+
+    for row in 0..GRID_SIZE {
+      let tiles = styled_row(gameboard, row, self.colors, " ", |board_state, _| {
+        match board_state {
+          BoardStates::X => "❌",
+          BoardStates::O => "⭕",
+          BoardStates::Empty => "⬜",
+          BoardStates::Blocked => "⬛",
+        }
+        .to_string()
+      });
+
+      lines.push(
+        format!(" {} {}", row_label(row), tiles.concat())
+          .trim_end()
+          .to_string(),
+      );
+    }
+
+    lines.join("\n") + "\n"
+  }
+}
+
+/// The board notation with '/' between rows, e.g. "xo./.x./...".
+impl Renderer for CompactRenderer {
+  fn render(&self, gameboard: &BoardConfig) -> String {
+    (0..GRID_SIZE)
+      .map(|row| {
+        styled_row(gameboard, row, self.colors, "", |board_state, _| {
+          match board_state {
+            BoardStates::X => 'x',
+            BoardStates::O => 'o',
+            BoardStates::Empty => '.',
+            BoardStates::Blocked => '#',
+          }
+          .to_string()
+        })
+        .concat()
+      })
+      .collect::<Vec<String>>()
+      .join("/")
+      + "\n"
+  }
+}
+
+/// Empty tiles show the 1-9 index used to select them.
+impl Renderer for NumberedRenderer {
+  fn render(&self, gameboard: &BoardConfig) -> String {
+    (0..GRID_SIZE)
+      .map(|row| {
+        styled_row(
+          gameboard,
+          row,
+          self.colors,
+          "",
+          |board_state, (row, column)| match board_state {
+            BoardStates::X => "X".to_string(),
+            BoardStates::O => "O".to_string(),
+            BoardStates::Empty => (row * GRID_SIZE + column + 1).to_string(),
+            BoardStates::Blocked => "#".to_string(),
+          },
+        )
+        .join("|")
+          + "\n"
+      })
+      .collect()
+  }
+}
+
+/// Colors are only used when stdout is a terminal and NO_COLOR isn't set.
+pub fn stdout_supports_colors() -> bool {
+  io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none()
+}
+
+/// The ascii theme.
+pub fn render_board(gameboard: &BoardConfig, colors: bool) -> String {
+  let mut lines = vec![column_labels()];
+
+  for row in 0..GRID_SIZE {
+    if row != 0 {
+      lines.push(ROW_SEPARATOR.to_string());
+    }
+
+    lines.push(
+      format!(
+        " {}  {}",
+        row_label(row),
+        styled_row(gameboard, row, colors, " ", grid_symbol).join("|")
+      )
+      .trim_end()
+      .to_string(),
+    );
+  }
+
+  lines.join("\n") + "\n"
+}
+
+fn column_labels() -> String {
+  format!(
+    "    {}",
+    (1..=GRID_SIZE)
+      .map(|column| format!(" {column} "))
+      .collect::<Vec<String>>()
+      .join(" ")
+      .trim_end()
+  )
+}
+
+fn row_label(row: usize) -> char {
+  (b'a' + row as u8) as char
+}
+
+/// How a tile is written on the plain text boards, empty ones being blank.
+pub fn symbol_text(board_state: &BoardStates) -> &'static str {
+  match board_state {
+    BoardStates::X => "X",
+    BoardStates::O => "O",
+    BoardStates::Empty => " ",
+    BoardStates::Blocked => "#",
+  }
+}
+
+/// The ascii and box themes leave empty tiles blank.
+fn grid_symbol(board_state: &BoardStates, _: Coordinates) -> String {
+  symbol_text(board_state).to_string()
+}
+
+/// The tiles of a row, each one padded on both sides unless it's highlighted.
+fn styled_row(
+  gameboard: &BoardConfig,
+  row: usize,
+  colors: bool,
+  padding: &str,
+  symbol: impl Fn(&BoardStates, Coordinates) -> String,
+) -> Vec<String> {
+  let winning_row = gameboard.winning_row();
+
+  (0..GRID_SIZE)
+    .map(|column| {
+      let coords = (row, column);
+      let board_state = gameboard.get_board_state(&coords);
+      let in_winning_row = winning_row.is_some_and(|winning_row| winning_row.contains(&coords));
+      let is_last_placed = gameboard.last_modified_tile == Some(coords);
+
+      render_tile(
+        &symbol(board_state, coords),
+        board_state,
+        in_winning_row,
+        is_last_placed,
+        colors,
+        padding,
+      )
+    })
+    .collect()
+}
+
+/// Without colors the highlight replaces the padding, so a padded grid still lines up.
+fn render_tile(
+  symbol: &str,
+  board_state: &BoardStates,
+  in_winning_row: bool,
+  is_last_placed: bool,
+  colors: bool,
+  padding: &str,
+) -> String {
+  if !colors {
+    return if in_winning_row {
+      format!("*{symbol}*")
+    } else if is_last_placed {
+      format!("[{symbol}]")
+    } else {
+      format!("{padding}{symbol}{padding}")
+    };
+  }
+
+  let tile = format!("{padding}{symbol}{padding}");
+
+  let styled_tile = match board_state {
+    BoardStates::X => tile.cyan().bold(),
+    BoardStates::O => tile.yellow().bold(),
+    BoardStates::Empty => tile.stylize(),
+    BoardStates::Blocked => tile.dark_grey(),
+  };
+
+  if in_winning_row {
+    styled_tile.on_green().to_string()
+  } else if is_last_placed {
+    styled_tile.reverse().to_string()
+  } else {
+    styled_tile.to_string()
+  }
+}
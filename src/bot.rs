@@ -1,6 +1,12 @@
 use crate::coordinate_methods::*;
 use crate::gameboard::*;
+use crate::solver::{
+  empty_tiles, misere_best_move, opposing_symbol, wild_best_move, wild_moves, WildMove,
+};
 use rand::prelude::*;
+use std::error::Error;
+use std::str::FromStr;
+use std::time::Duration;
 
 const CENTER_TILE: Coordinates = (1, 1);
 const FIRST_MOVE: u8 = 0;
@@ -10,6 +16,27 @@ pub struct Bot {
   pub path: CurrentPath,
   pub bot_symbol: BoardStates,
   pub most_recent_chosen_coords: Result<Coordinates, String>,
+  pub difficulty: Difficulty,
+}
+
+/// Only Hard follows the paths below, the others never look further than one move ahead.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum Difficulty {
+  /// Places on a random empty tile.
+  Easy,
+  /// Wins or blocks when it can, otherwise places randomly.
+  Medium,
+  /// Can't be beaten.
+  #[default]
+  Hard,
+}
+
+/// How long the bot can take over a move on the bigger boards, and how deep
+/// it can go in that time.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct SearchBudget {
+  pub max_depth: u32,
+  pub time_limit: Duration,
 }
 
 #[derive(PartialEq, Clone, Debug)]
@@ -34,10 +61,81 @@ impl Bot {
       path: CurrentPath::FirstMove,
       bot_symbol: BoardStates::Empty,
       most_recent_chosen_coords: Err("No error has been given".to_string()),
+      difficulty: Difficulty::default(),
     }
   }
 
   pub fn choose_coordinates(&mut self, gameboard: &BoardConfig) {
+    match self.difficulty {
+      Difficulty::Easy => self.most_recent_chosen_coords = random_empty_tile(gameboard),
+      Difficulty::Medium => {
+        self.most_recent_chosen_coords = self
+          .winning_or_blocking_tile(gameboard)
+          .map_or_else(|| random_empty_tile(gameboard), Ok)
+      }
+      Difficulty::Hard => self.follow_path(gameboard),
+    }
+  }
+
+  /// For the game where completing a row loses, the paths only know how to win.
+  pub fn choose_misere_coordinates(&mut self, gameboard: &BoardConfig) {
+    self.most_recent_chosen_coords = match self.difficulty {
+      Difficulty::Easy => random_empty_tile(gameboard),
+      Difficulty::Medium => {
+        let safe_tiles: Vec<Coordinates> = empty_tiles(gameboard)
+          .into_iter()
+          .filter(|coords| {
+            let mut gameboard = gameboard.clone();
+            gameboard.place_tile(coords, self.bot_symbol);
+
+            !gameboard.check_if_win()
+          })
+          .collect();
+
+        match safe_tiles.choose(&mut thread_rng()) {
+          Some(coords) => Ok(*coords),
+          None => random_empty_tile(gameboard),
+        }
+      }
+      Difficulty::Hard => {
+        misere_best_move(gameboard).ok_or_else(|| "No possible tile to place on".to_string())
+      }
+    };
+  }
+
+  /// For the game where either symbol can be placed, bot_symbol doesn't matter there.
+  pub fn choose_wild_move(&self, gameboard: &BoardConfig) -> Option<WildMove> {
+    let moves = wild_moves(gameboard);
+
+    match self.difficulty {
+      Difficulty::Easy => moves.choose(&mut thread_rng()).copied(),
+      Difficulty::Medium => {
+        // A move is safe when the other side can't complete a row straight after it.
+        let safe_moves: Vec<WildMove> = moves
+          .iter()
+          .copied()
+          .filter(|wild_move| {
+            let mut gameboard = gameboard.clone();
+            gameboard.place_tile(&wild_move.1, wild_move.0);
+
+            !wild_moves(&gameboard)
+              .iter()
+              .any(|reply| completes_a_row(&gameboard, reply))
+          })
+          .collect();
+
+        moves
+          .iter()
+          .find(|wild_move| completes_a_row(gameboard, wild_move))
+          .or_else(|| safe_moves.choose(&mut thread_rng()))
+          .or_else(|| moves.choose(&mut thread_rng()))
+          .copied()
+      }
+      Difficulty::Hard => wild_best_move(gameboard),
+    }
+  }
+
+  fn follow_path(&mut self, gameboard: &BoardConfig) {
     match &self.path {
       CurrentPath::Center(_) => {
         center_position_checks(self, gameboard);
@@ -124,6 +222,20 @@ impl Bot {
     }
   }
 
+  /// A tile that wins for the bot, otherwise one that would win for the other side.
+  pub fn winning_or_blocking_tile(&self, gameboard: &BoardConfig) -> Option<Coordinates> {
+    [self.bot_symbol, opposing_symbol(self.bot_symbol)]
+      .into_iter()
+      .find_map(|symbol| {
+        empty_tiles(gameboard).into_iter().find(|coords| {
+          let mut gameboard = gameboard.clone();
+          gameboard.place_tile(coords, symbol);
+
+          gameboard.check_if_win()
+        })
+      })
+  }
+
   pub fn auto_play(&self, gameboard: &BoardConfig) -> Result<Coordinates, String> {
     if let Some(coords) =
       gameboard.check_if_two_in_series(self.most_recent_chosen_coords.as_ref().unwrap())
@@ -141,6 +253,40 @@ impl Bot {
   }
 }
 
+impl Difficulty {
+  pub const NAMES: [&'static str; 3] = ["easy", "medium", "hard"];
+}
+
+impl FromStr for Difficulty {
+  type Err = Box<dyn Error>;
+
+  fn from_str(difficulty: &str) -> Result<Self, Self::Err> {
+    match difficulty.to_lowercase().trim() {
+      "easy" => Ok(Difficulty::Easy),
+      "medium" => Ok(Difficulty::Medium),
+      "hard" => Ok(Difficulty::Hard),
+      _ => Err(Box::from(format!(
+        "unknown difficulty '{difficulty}', expected one of: {}",
+        Difficulty::NAMES.join(", ")
+      ))),
+    }
+  }
+}
+
+fn completes_a_row(gameboard: &BoardConfig, wild_move: &WildMove) -> bool {
+  let mut gameboard = gameboard.clone();
+  gameboard.place_tile(&wild_move.1, wild_move.0);
+
+  gameboard.check_if_win()
+}
+
+fn random_empty_tile(gameboard: &BoardConfig) -> Result<Coordinates, String> {
+  empty_tiles(gameboard)
+    .choose(&mut thread_rng())
+    .copied()
+    .ok_or_else(|| "No possible tile to place on".to_string())
+}
+
 fn center_position_checks(bot: &mut Bot, gameboard: &BoardConfig) {
   match gameboard.get_board_position(&gameboard.last_modified_tile.unwrap()) {
     BoardPositions::Corner => {
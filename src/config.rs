@@ -0,0 +1,267 @@
+//! Settings read from `~/.config/tictactoe/config.toml` or the file given with
+//! `--config <path>`, for example
+//!
+//! ```toml
+//! mode = "tui"
+//! symbol = "o"
+//! starts = "bot"
+//! difficulty = "medium"
+//! bot_play_delay_ms = 250
+//! theme = "box"
+//! player_name = "Sam"
+//! misere = true
+//! ```
+//!
+//! Every setting can also be given as a flag, `player_name` being `--player-name`,
+//! and flags take priority over the file.
+
+use crate::board_render::Theme;
+use crate::bot::Difficulty;
+use crate::gameboard::BoardStates;
+use crate::gameplay::GAMEMODES;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const SETTINGS: [&str; 8] = [
+  "mode",
+  "symbol",
+  "starts",
+  "difficulty",
+  "bot_play_delay_ms",
+  "theme",
+  "player_name",
+  "misere",
+];
+
+pub const MAX_BOT_PLAY_DELAY_MS: u64 = 60_000;
+pub const MAX_PLAYER_NAME_LENGTH: usize = 20;
+
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct ConfigFile {
+  pub mode: Option<String>,
+  pub symbol: Option<BoardStates>,
+  pub starts: Option<Starts>,
+  pub difficulty: Option<Difficulty>,
+  pub bot_play_delay: Option<Duration>,
+  pub theme: Option<Theme>,
+  pub player_name: Option<String>,
+  pub misere: Option<bool>,
+}
+
+/// Who places first. X always places first, so this picks who gets X.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Starts {
+  Player,
+  Bot,
+  Random,
+}
+
+impl ConfigFile {
+  /// Reads the file at default_config_path, a missing file being an empty config.
+  pub fn load_default() -> Result<Self, Box<dyn Error>> {
+    let Some(path) = default_config_path() else {
+      return Ok(ConfigFile::default());
+    };
+
+    match fs::read_to_string(&path) {
+      Ok(contents) => Self::parse_file(&contents, &path),
+      Err(error) if error.kind() == ErrorKind::NotFound => Ok(ConfigFile::default()),
+      Err(error) => Err(Box::from(format!(
+        "failed to read {}: {error}",
+        path.display()
+      ))),
+    }
+  }
+
+  /// Reads a file that has to exist, such as one given with `--config`.
+  pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+    let contents = fs::read_to_string(path)
+      .map_err(|error| format!("failed to read {}: {error}", path.display()))?;
+
+    Self::parse_file(&contents, path)
+  }
+
+  pub fn parse(contents: &str) -> Result<Self, Box<dyn Error>> {
+    let table: toml::Table = contents.parse()?;
+    let mut config_file = ConfigFile::default();
+
+    for (key, value) in table {
+      let value = match value {
+        toml::Value::String(value) => value,
+        toml::Value::Integer(value) => value.to_string(),
+        toml::Value::Boolean(value) => value.to_string(),
+        _ => {
+          return Err(Box::from(format!(
+            "{key} must be a string, a number or true/false"
+          )))
+        }
+      };
+
+      config_file.set(&key, &value)?;
+    }
+
+    config_file.validate()?;
+
+    Ok(config_file)
+  }
+
+  /// Sets a setting from its text form, which is the same in the file and as a flag.
+  pub fn set(&mut self, key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+    match key {
+      "mode" => self.mode = Some(parse_mode(value)?),
+      "symbol" => self.symbol = Some(parse_symbol(value)?),
+      "starts" => self.starts = Some(value.parse()?),
+      "difficulty" => self.difficulty = Some(value.parse()?),
+      "bot_play_delay_ms" => self.bot_play_delay = Some(parse_delay(value)?),
+      "theme" => self.theme = Some(value.parse()?),
+      "player_name" => self.player_name = Some(parse_player_name(value)?),
+      "misere" => self.misere = Some(parse_bool(key, value)?),
+      _ => {
+        return Err(Box::from(format!(
+          "unknown setting '{key}', expected one of: {}",
+          SETTINGS.join(", ")
+        )))
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Takes every setting given as a flag out of the arguments, overriding the file.
+  pub fn apply_flags(&mut self, arguments: &mut Vec<String>) -> Result<(), Box<dyn Error>> {
+    for key in SETTINGS {
+      let flag = format!("--{}", key.replace('_', "-"));
+
+      if let Some(value) = take_flag(arguments, &flag)? {
+        self
+          .set(key, &value)
+          .map_err(|error| format!("{flag}: {error}"))?;
+      }
+    }
+
+    self.validate()
+  }
+
+  /// Checks the settings that can't be checked on their own.
+  pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+    match (self.symbol, self.starts) {
+      (Some(BoardStates::O), Some(Starts::Player)) | (Some(BoardStates::X), Some(Starts::Bot)) => {
+        Err(Box::from(
+          "symbol and starts disagree, X always places first so pick one or the other",
+        ))
+      }
+      _ => Ok(()),
+    }
+  }
+
+  fn parse_file(contents: &str, path: &Path) -> Result<Self, Box<dyn Error>> {
+    Self::parse(contents).map_err(|error| Box::from(format!("in {}: {error}", path.display())))
+  }
+}
+
+impl std::str::FromStr for Starts {
+  type Err = Box<dyn Error>;
+
+  fn from_str(starts: &str) -> Result<Self, Self::Err> {
+    match starts.to_lowercase().trim() {
+      "player" => Ok(Starts::Player),
+      "bot" => Ok(Starts::Bot),
+      "random" => Ok(Starts::Random),
+      _ => Err(Box::from(format!(
+        "unknown starts '{starts}', expected one of: player, bot, random"
+      ))),
+    }
+  }
+}
+
+fn parse_mode(mode: &str) -> Result<String, Box<dyn Error>> {
+  let mode = mode.to_lowercase();
+
+  if GAMEMODES.contains(&mode.trim()) {
+    Ok(mode.trim().to_string())
+  } else {
+    Err(Box::from(format!(
+      "unknown mode '{mode}', expected one of: {}",
+      GAMEMODES.join(", ")
+    )))
+  }
+}
+
+fn parse_symbol(symbol: &str) -> Result<BoardStates, Box<dyn Error>> {
+  match symbol.to_lowercase().trim() {
+    "x" => Ok(BoardStates::X),
+    "o" => Ok(BoardStates::O),
+    _ => Err(Box::from(format!(
+      "unknown symbol '{symbol}', expected x or o"
+    ))),
+  }
+}
+
+fn parse_delay(milliseconds: &str) -> Result<Duration, Box<dyn Error>> {
+  match milliseconds.trim().parse::<u64>() {
+    Ok(milliseconds) if milliseconds <= MAX_BOT_PLAY_DELAY_MS => {
+      Ok(Duration::from_millis(milliseconds))
+    }
+    _ => Err(Box::from(format!(
+      "bot_play_delay_ms must be a whole number of milliseconds from 0 to {MAX_BOT_PLAY_DELAY_MS}, got '{milliseconds}'"
+    ))),
+  }
+}
+
+fn parse_player_name(player_name: &str) -> Result<String, Box<dyn Error>> {
+  let player_name = player_name.trim();
+
+  if player_name.is_empty() {
+    Err(Box::from("player_name can't be empty"))
+  } else if player_name.chars().count() > MAX_PLAYER_NAME_LENGTH {
+    Err(Box::from(format!(
+      "player_name can't be longer than {MAX_PLAYER_NAME_LENGTH} characters"
+    )))
+  } else if player_name.chars().any(char::is_control) {
+    Err(Box::from("player_name can't contain control characters"))
+  } else {
+    Ok(player_name.to_string())
+  }
+}
+
+fn parse_bool(key: &str, value: &str) -> Result<bool, Box<dyn Error>> {
+  match value.to_lowercase().trim() {
+    "true" | "yes" | "on" => Ok(true),
+    "false" | "no" | "off" => Ok(false),
+    _ => Err(Box::from(format!(
+      "{key} must be true or false, got '{value}'"
+    ))),
+  }
+}
+
+/// `$XDG_CONFIG_HOME/tictactoe/config.toml`, falling back to `~/.config`.
+pub fn default_config_path() -> Option<PathBuf> {
+  let config_directory = env::var_os("XDG_CONFIG_HOME")
+    .filter(|directory| !directory.is_empty())
+    .map(PathBuf::from)
+    .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+  Some(config_directory.join("tictactoe").join("config.toml"))
+}
+
+/// Removes `flag <value>` from the arguments, returning the value.
+pub fn take_flag(
+  arguments: &mut Vec<String>,
+  flag: &str,
+) -> Result<Option<String>, Box<dyn Error>> {
+  let Some(index) = arguments.iter().position(|argument| argument == flag) else {
+    return Ok(None);
+  };
+
+  if index + 1 >= arguments.len() {
+    return Err(Box::from(format!("'{flag}' is missing a value")));
+  }
+
+  arguments.remove(index);
+
+  Ok(Some(arguments.remove(index)))
+}
@@ -6,6 +6,13 @@ const ISIZE_MIDDLE_COORDS: (isize, isize) = (1, 1);
 
 pub type Coordinates = (usize, usize);
 
+/// Every tile of the grid, row by row from the top left.
+pub fn all_coordinates() -> Vec<Coordinates> {
+  (0..GRID_SIZE)
+    .flat_map(|row| (0..GRID_SIZE).map(move |column| (row, column)))
+    .collect()
+}
+
 pub trait CoordinateMethods {
   fn get_opposite_coordinates(&self, adjacent_coords: &Coordinates) -> Coordinates;
 
@@ -102,10 +109,10 @@ impl CoordinateMethods for Coordinates {
   ) -> bool {
     if let Some(is_matching) = self.is_in_between_matching(adjacent_coords, board_config) {
       is_matching
-    } else if let Some(is_matching) = self.is_side_matching(adjacent_coords, board_config) {
-      is_matching
     } else {
-      false
+      self
+        .is_side_matching(adjacent_coords, board_config)
+        .unwrap_or_default()
     }
   }
 
@@ -0,0 +1,536 @@
+//! 3D tic-tac-toe, started with `cube [3|4]`.
+//!
+//! The board is a stack of layers, and a line can run through them as well as
+//! across one. 3x3x3 has 49 lines and is an easy win for whoever goes first,
+//! so the default is 4x4x4 Qubic with 76.
+//!
+//! Tiles are written like on the flat boards followed by their layer, so
+//! "b2@1" is the middle of the top layer of the small cube.
+
+use crate::gameboard::BoardStates;
+use crate::gameplay::*;
+use crate::mcts::{player_of, symbol_of, Game, Outcome};
+use crate::search::{
+  best_move, line_score, search_depth, total_evaluation, Searchable, MAX_EVALUATION,
+};
+use crate::session::{play_session, two_player_seats, SessionGame};
+use crate::solver::opposing_symbol;
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+/// (layer, row, column)
+pub type CubeCoordinates = (usize, usize, usize);
+
+pub const DEFAULT_CUBE_SIZE: usize = 4;
+pub const CUBE_SIZES: [usize; 2] = [3, 4];
+
+const CUBE_USAGE: &str = "expected 'cube', 'cube 3' or 'cube 4'";
+
+/// Every direction a line can run in, the opposite ones being the same lines walked backwards.
+pub const CUBE_DIRECTIONS: [(isize, isize, isize); 13] = [
+  (0, 0, 1),
+  (0, 1, 0),
+  (0, 1, 1),
+  (0, 1, -1),
+  (1, 0, 0),
+  (1, 0, 1),
+  (1, 0, -1),
+  (1, 1, 0),
+  (1, -1, 0),
+  (1, 1, 1),
+  (1, 1, -1),
+  (1, -1, 1),
+  (1, -1, -1),
+];
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct CubeBoard {
+  pub size: usize,
+  /// Layer by layer, each row by row from the top left.
+  pub tiles: Vec<BoardStates>,
+  pub tiles_covered: usize,
+  pub last_modified_tile: Option<CubeCoordinates>,
+  lines: Vec<Vec<CubeCoordinates>>,
+  /// The indexes into lines of the ones going through each tile.
+  tile_lines: Vec<Vec<usize>>,
+}
+
+impl CubeBoard {
+  pub fn new(size: usize) -> Result<Self, Box<dyn Error>> {
+    if !CUBE_SIZES.contains(&size) {
+      return Err(Box::from(format!(
+        "cubes are 3 or 4 tiles across, got {size}"
+      )));
+    }
+
+    let mut cube_board = CubeBoard {
+      size,
+      tiles: vec![BoardStates::Empty; size.pow(3)],
+      tiles_covered: 0,
+      last_modified_tile: None,
+      lines: vec![],
+      tile_lines: vec![vec![]; size.pow(3)],
+    };
+
+    // A line is every step from a tile that has nothing behind it.
+    for start in cube_board.all_coordinates() {
+      for direction in CUBE_DIRECTIONS {
+        if cube_board.step(&start, direction, -1).is_some() {
+          continue;
+        }
+
+        let line = (0..size as isize)
+          .map(|distance| cube_board.step(&start, direction, distance))
+          .collect::<Option<Vec<CubeCoordinates>>>();
+
+        if let Some(line) = line {
+          for coords in &line {
+            let index = cube_board.index(coords);
+            cube_board.tile_lines[index].push(cube_board.lines.len());
+          }
+
+          cube_board.lines.push(line);
+        }
+      }
+    }
+
+    Ok(cube_board)
+  }
+
+  fn index(&self, coords: &CubeCoordinates) -> usize {
+    (coords.0 * self.size + coords.1) * self.size + coords.2
+  }
+
+  pub fn is_in_bounds(&self, coords: &CubeCoordinates) -> bool {
+    coords.0 < self.size && coords.1 < self.size && coords.2 < self.size
+  }
+
+  pub fn get_board_state(&self, coords: &CubeCoordinates) -> &BoardStates {
+    &self.tiles[self.index(coords)]
+  }
+
+  /// Keeps tiles_covered up to date, placing Empty takes a tile back.
+  pub fn place_tile(&mut self, coords: &CubeCoordinates, changed_state: BoardStates) {
+    let index = self.index(coords);
+    let tile = &mut self.tiles[index];
+
+    match (
+      *tile == BoardStates::Empty,
+      changed_state == BoardStates::Empty,
+    ) {
+      (true, false) => self.tiles_covered += 1,
+      (false, true) => self.tiles_covered -= 1,
+      _ => (),
+    }
+
+    *tile = changed_state;
+    self.last_modified_tile = Some(*coords);
+  }
+
+  pub fn all_tiles_covered(&self) -> bool {
+    self.tiles_covered == self.tiles.len()
+  }
+
+  pub fn all_coordinates(&self) -> Vec<CubeCoordinates> {
+    let size = self.size;
+
+    (0..size)
+      .flat_map(|layer| {
+        (0..size).flat_map(move |row| (0..size).map(move |column| (layer, row, column)))
+      })
+      .collect()
+  }
+
+  pub fn empty_tiles(&self) -> Vec<CubeCoordinates> {
+    self
+      .all_coordinates()
+      .into_iter()
+      .filter(|coords| self.get_board_state(coords) == &BoardStates::Empty)
+      .collect()
+  }
+
+  /// The tile distance steps away in direction, None when that's off the cube.
+  pub fn step(
+    &self,
+    coords: &CubeCoordinates,
+    direction: (isize, isize, isize),
+    distance: isize,
+  ) -> Option<CubeCoordinates> {
+    let layer = coords.0 as isize + direction.0 * distance;
+    let row = coords.1 as isize + direction.1 * distance;
+    let column = coords.2 as isize + direction.2 * distance;
+
+    if layer < 0 || row < 0 || column < 0 {
+      return None;
+    }
+
+    Some((layer as usize, row as usize, column as usize)).filter(|coords| self.is_in_bounds(coords))
+  }
+
+  /// Every line through the cube, 49 on 3x3x3 and 76 on 4x4x4.
+  pub fn lines(&self) -> &[Vec<CubeCoordinates>] {
+    &self.lines
+  }
+
+  /// The lines going through coords.
+  pub fn lines_through(
+    &self,
+    coords: &CubeCoordinates,
+  ) -> impl Iterator<Item = &Vec<CubeCoordinates>> {
+    self.tile_lines[self.index(coords)]
+      .iter()
+      .map(|line_index| &self.lines[*line_index])
+  }
+
+  fn is_complete(&self, line: &[CubeCoordinates]) -> bool {
+    let symbol = self.get_board_state(&line[0]);
+
+    symbol != &BoardStates::Empty
+      && line
+        .iter()
+        .all(|coords| self.get_board_state(coords) == symbol)
+  }
+
+  /// Returns true if the last placed tile completed a line.
+  pub fn check_if_win(&self) -> bool {
+    self.last_modified_tile.is_some_and(|coords| {
+      self
+        .lines_through(&coords)
+        .any(|line| self.is_complete(line))
+    })
+  }
+
+  /// Searches every line rather than just the ones through the last tile.
+  pub fn winner(&self) -> Option<BoardStates> {
+    self
+      .lines
+      .iter()
+      .find(|line| self.is_complete(line))
+      .map(|line| *self.get_board_state(&line[0]))
+  }
+
+  /// Empty tiles that would finish a line for symbol.
+  pub fn winning_tiles(&self, symbol: BoardStates) -> Vec<CubeCoordinates> {
+    let mut winning_tiles = vec![];
+
+    for line in &self.lines {
+      let mut empty_tiles = line
+        .iter()
+        .filter(|coords| self.get_board_state(coords) == &BoardStates::Empty);
+
+      let (Some(empty_tile), None) = (empty_tiles.next(), empty_tiles.next()) else {
+        continue;
+      };
+
+      let filled = line
+        .iter()
+        .filter(|coords| self.get_board_state(coords) == &symbol)
+        .count();
+
+      if filled == self.size - 1 && !winning_tiles.contains(empty_tile) {
+        winning_tiles.push(*empty_tile);
+      }
+    }
+
+    winning_tiles
+  }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct CubeGame {
+  pub cube_board: CubeBoard,
+  pub next_symbol: BoardStates,
+}
+
+impl CubeGame {
+  pub fn new(size: usize) -> Result<Self, Box<dyn Error>> {
+    Ok(CubeGame {
+      cube_board: CubeBoard::new(size)?,
+      next_symbol: BoardStates::X,
+    })
+  }
+
+  /// None while the game can still be played.
+  pub fn result(&self) -> Option<GameState> {
+    match self.cube_board.winner() {
+      Some(BoardStates::X) => Some(GameState::XWon),
+      Some(BoardStates::O) => Some(GameState::OWon),
+      _ if self.cube_board.all_tiles_covered() => Some(GameState::Draw),
+      _ => None,
+    }
+  }
+
+  /// Places next_symbol on coords, erroring if it can't go there.
+  pub fn place(&mut self, coords: &CubeCoordinates) -> Result<(), Box<dyn Error>> {
+    if self.result().is_some() {
+      return Err(Box::from("the game is over"));
+    }
+
+    if !self.cube_board.is_in_bounds(coords) {
+      let size = self.cube_board.size;
+
+      return Err(Box::from(format!(
+        "{} is off the {size}x{size}x{size} cube",
+        cube_tile_notation(coords)
+      )));
+    }
+
+    if self.cube_board.get_board_state(coords) != &BoardStates::Empty {
+      return Err(Box::from(format!(
+        "{} is already taken",
+        cube_tile_notation(coords)
+      )));
+    }
+
+    self.cube_board.place_tile(coords, self.next_symbol);
+    self.next_symbol = opposing_symbol(self.next_symbol);
+
+    Ok(())
+  }
+}
+
+/// "b2@1" into (0, 1, 1), the layer after the @.
+pub fn parse_cube_tile(player_input: &str) -> Result<CubeCoordinates, Box<dyn Error>> {
+  let player_input = player_input.trim().to_lowercase();
+
+  let Some((tile, layer)) = player_input.split_once('@') else {
+    return Err(Box::from("give the tile then its layer, e.g. 'b2@1'"));
+  };
+
+  let mut characters = tile.chars();
+
+  let row = match characters.next() {
+    Some(letter @ 'a'..='z') => letter as usize - 'a' as usize,
+    _ => return Err(Box::from("incorrect input")),
+  };
+
+  match (characters.as_str().parse::<usize>(), layer.parse::<usize>()) {
+    (Ok(column), Ok(layer)) if column != 0 && layer != 0 => Ok((layer - 1, row, column - 1)),
+    _ => Err(Box::from("incorrect input")),
+  }
+}
+
+pub fn cube_tile_notation(coords: &CubeCoordinates) -> String {
+  format!(
+    "{}{}@{}",
+    (b'a' + coords.1 as u8) as char,
+    coords.2 + 1,
+    coords.0 + 1
+  )
+}
+
+/// A win when there is one, the block when the other side has a single
+/// winning tile, and otherwise every empty tile, the ones on the most lines first.
+fn candidate_tiles(cube_board: &CubeBoard, symbol: BoardStates) -> Vec<CubeCoordinates> {
+  let winning_tiles = cube_board.winning_tiles(symbol);
+
+  if let Some(coords) = winning_tiles.first() {
+    return vec![*coords];
+  }
+
+  let threats = cube_board.winning_tiles(opposing_symbol(symbol));
+
+  if let Some(coords) = threats.first() {
+    return vec![*coords];
+  }
+
+  let mut empty_tiles = cube_board.empty_tiles();
+
+  empty_tiles.sort_by_key(|coords| std::cmp::Reverse(cube_board.lines_through(coords).count()));
+
+  empty_tiles
+}
+
+/// Lines only one side has pieces in, worth more the closer they are to done,
+/// for symbol minus the other side.
+pub fn evaluate(cube_board: &CubeBoard, symbol: BoardStates) -> i32 {
+  let opponent = opposing_symbol(symbol);
+
+  total_evaluation(cube_board.lines().iter().map(|line| {
+    let count_of = |state: BoardStates| {
+      line
+        .iter()
+        .filter(|coords| cube_board.get_board_state(coords) == &state)
+        .count()
+    };
+
+    match (count_of(symbol), count_of(opponent)) {
+      (0, 0) => 0,
+      (count, 0) => line_score(count),
+      (0, count) => -line_score(count),
+      _ => 0,
+    }
+  }))
+}
+
+impl Game for CubeGame {
+  type Move = CubeCoordinates;
+
+  fn current_player(&self) -> usize {
+    player_of(self.next_symbol)
+  }
+
+  fn legal_moves(&self) -> Vec<CubeCoordinates> {
+    if self.result().is_some() {
+      return vec![];
+    }
+
+    self.cube_board.empty_tiles()
+  }
+
+  fn apply(&mut self, coords: &CubeCoordinates) {
+    self.place(coords).expect("only legal tiles are placed on");
+  }
+
+  fn outcome(&self) -> Option<Outcome> {
+    self.result().map(Outcome::from)
+  }
+}
+
+impl Searchable for CubeGame {
+  const SEARCH_DEPTHS: [u32; 3] = [1, 2, 5];
+
+  /// A line the side to move can finish, or two the other side can, settles
+  /// the game a move early, so it's worth as much as an evaluation goes.
+  fn evaluate(&self, player: usize) -> i32 {
+    let symbol = self.next_symbol;
+    let for_next = if player_of(symbol) == player { 1 } else { -1 };
+
+    if !self.cube_board.winning_tiles(symbol).is_empty() {
+      return for_next * MAX_EVALUATION;
+    }
+
+    // Only one of them can be blocked.
+    if self.cube_board.winning_tiles(opposing_symbol(symbol)).len() > 1 {
+      return -for_next * MAX_EVALUATION;
+    }
+
+    evaluate(&self.cube_board, symbol_of(player))
+  }
+
+  fn search_moves(&self) -> Vec<CubeCoordinates> {
+    candidate_tiles(&self.cube_board, self.next_symbol)
+  }
+}
+
+impl SessionGame for CubeGame {
+  type Result = GameState;
+
+  fn render(&self) -> String {
+    render_cube(&self.cube_board)
+  }
+
+  fn session_result(&self) -> Option<GameState> {
+    self.result()
+  }
+
+  fn prompt(&self) -> String {
+    "select a tile and its layer, e.g. 'b2@1'".to_string()
+  }
+
+  fn play_input(&mut self, player_input: &str) -> Result<(), Box<dyn Error>> {
+    self.place(&parse_cube_tile(player_input)?)
+  }
+
+  fn describe_move(&self, coords: &CubeCoordinates) -> String {
+    format!("placed on {}", cube_tile_notation(coords))
+  }
+}
+
+/// The layers side by side, the last placed tile in brackets.
+///
+/// ```text
+///   layer 1       layer 2       layer 3
+///    1  2  3       1  2  3       1  2  3
+/// a  .  .  .    a  .  .  .    a  .  .  .
+/// b  .  X  .    b  . [O] .    b  .  .  .
+/// c  .  .  .    c  .  .  .    c  .  .  .
+/// ```
+pub fn render_cube(cube_board: &CubeBoard) -> String {
+  let size = cube_board.size;
+  let width = size * 3 + 2;
+
+  let mut lines = vec![String::new(); size + 2];
+
+  for layer in 0..size {
+    let header: String = (1..=size).map(|column| format!("{column:^3}")).collect();
+
+    let mut layer_lines = vec![
+      format!("{:^width$}", format!("layer {}", layer + 1)),
+      format!("  {header}"),
+    ];
+
+    for row in 0..size {
+      let tiles: String = (0..size)
+        .map(|column| {
+          let symbol = match cube_board.get_board_state(&(layer, row, column)) {
+            BoardStates::X => 'X',
+            BoardStates::O => 'O',
+            BoardStates::Empty => '.',
+            BoardStates::Blocked => '#',
+          };
+
+          if cube_board.last_modified_tile == Some((layer, row, column)) {
+            format!("[{symbol}]")
+          } else {
+            format!(" {symbol} ")
+          }
+        })
+        .collect();
+
+      layer_lines.push(format!("{} {tiles}", (b'a' + row as u8) as char));
+    }
+
+    for (line, layer_line) in lines.iter_mut().zip(layer_lines) {
+      *line += &format!("{layer_line:width$}   ");
+    }
+  }
+
+  lines
+    .iter()
+    .map(|line| format!("{}\n", line.trim_end()))
+    .collect()
+}
+
+pub fn cube(gameconfig: GameConfig, gamemode_arguments: &[String]) -> Result<(), Box<dyn Error>> {
+  let size = match gamemode_arguments {
+    [] => DEFAULT_CUBE_SIZE,
+    [size] => size
+      .parse::<usize>()
+      .map_err(|_| format!("the cube size has to be 3 or 4, got '{size}'"))?,
+    _ => return Err(Box::from(CUBE_USAGE)),
+  };
+
+  cube_session(
+    gameconfig,
+    CubeGame::new(size)?,
+    io::stdin().lock(),
+    io::stdout(),
+  )?;
+
+  Ok(())
+}
+
+pub fn cube_session<R: BufRead, W: Write>(
+  gameconfig: GameConfig,
+  cube_game: CubeGame,
+  input: R,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
+  let depth = search_depth::<CubeGame>(gameconfig.bot.difficulty);
+  let size = cube_game.cube_board.size;
+
+  writeln!(
+    output,
+    "{} {:?} | Bot {:?} | {size} in a row in any direction wins, across layers too",
+    gameconfig.player_name, gameconfig.player_symbol, gameconfig.bot.bot_symbol
+  )?;
+
+  play_session(
+    cube_game,
+    &two_player_seats(&gameconfig.player_name, player_of(gameconfig.player_symbol)),
+    |cube_game| best_move(cube_game, depth),
+    input,
+    output,
+  )
+}
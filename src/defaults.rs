@@ -1,11 +1,12 @@
 use crate::bot::Bot;
+use crate::engine_protocol::EngineSession;
 use crate::gameboard::BoardConfig;
 use crate::gameplay::GameConfig;
+use crate::http_api::ApiState;
 
 impl Default for GameConfig {
   fn default() -> Self {
     Self::new()
-      .unwrap_or_else(|error| panic!("An error has occured while grabbing config: '{error}'"))
   }
 }
 
@@ -20,3 +21,15 @@ impl Default for Bot {
     Self::new()
   }
 }
+
+impl Default for EngineSession {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Default for ApiState {
+  fn default() -> Self {
+    Self::new()
+  }
+}
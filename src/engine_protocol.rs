@@ -0,0 +1,201 @@
+//! A UCI style line protocol so the bot can be driven as a subprocess.
+//!
+//! ```text
+//! > uci
+//! < id name tictactoe_with_ai
+//! < uciok
+//! > position startpos moves b2 a1
+//! > go
+//! < bestmove c3
+//! > quit
+//! ```
+//!
+//! Positions are either "startpos" or a 9 tile notation such as "x...o....",
+//! see BoardConfig::from_notation. A '#' in the notation blocks a tile.
+//! Every position is solved rather than played by the bot's usual openings.
+//! Moves are written as a1-c3 or 1-9.
+
+use crate::coordinate_methods::*;
+use crate::gameboard::*;
+use crate::gameplay::{parse_tile_selection, row_column_notation};
+use crate::solver;
+use std::error::Error;
+use std::io::{BufRead, Write};
+
+pub const ENGINE_NAME: &str = "tictactoe_with_ai";
+
+#[derive(PartialEq, Debug)]
+pub struct EngineSession {
+  pub starting_board: BoardConfig,
+  pub moves: Vec<Coordinates>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum EngineCommand {
+  Uci,
+  IsReady,
+  NewGame,
+  Position(BoardConfig, Vec<Coordinates>),
+  Go,
+  Quit,
+}
+
+impl EngineSession {
+  pub fn new() -> Self {
+    EngineSession {
+      starting_board: BoardConfig::new(),
+      moves: vec![],
+    }
+  }
+
+  /// Replaces the current position, making sure every move in the list is legal.
+  pub fn set_position(
+    &mut self,
+    starting_board: BoardConfig,
+    moves: Vec<Coordinates>,
+  ) -> Result<(), Box<dyn Error>> {
+    let mut gameboard = BoardConfig::from_notation(&starting_board.to_notation())?;
+
+    for coords in &moves {
+      place_engine_move(&mut gameboard, coords)?;
+    }
+
+    self.starting_board = starting_board;
+    self.moves = moves;
+
+    Ok(())
+  }
+
+  pub fn current_board(&self) -> BoardConfig {
+    let mut gameboard = BoardConfig::from_notation(&self.starting_board.to_notation()).unwrap();
+
+    for coords in &self.moves {
+      place_engine_move(&mut gameboard, coords).unwrap();
+    }
+
+    gameboard
+  }
+
+  /// Solves the position for whoever is next to place.
+  /// Returns None if the game is already over.
+  ///
+  /// The bot's openings depend on it having chosen every one of its own moves,
+  /// which a position sent by someone else can't promise, so the solver answers instead.
+  pub fn best_move(&self) -> Option<Coordinates> {
+    solver::best_move(&self.current_board())
+  }
+}
+
+fn place_engine_move(
+  gameboard: &mut BoardConfig,
+  coords: &Coordinates,
+) -> Result<(), Box<dyn Error>> {
+  if gameboard.winning_row().is_some() {
+    return Err(Box::from(format!(
+      "move {} was played after the game ended",
+      row_column_notation(coords)
+    )));
+  }
+
+  match gameboard.get_board_state(coords) {
+    BoardStates::Empty => (),
+    BoardStates::Blocked => {
+      return Err(Box::from(format!(
+        "tile {} is blocked",
+        row_column_notation(coords)
+      )))
+    }
+    _ => {
+      return Err(Box::from(format!(
+        "tile {} is already taken",
+        row_column_notation(coords)
+      )))
+    }
+  }
+
+  let symbol = gameboard.next_symbol_to_place();
+
+  gameboard.place_tile(coords, symbol);
+  gameboard.tiles_covered += 1;
+
+  Ok(())
+}
+
+/// Parses a single move written as a1-c3 or 1-9.
+pub fn parse_engine_move(notation: &str) -> Result<Coordinates, Box<dyn Error>> {
+  match parse_tile_selection(notation) {
+    Ok(coords) if coords.0 < GRID_SIZE && coords.1 < GRID_SIZE => Ok(coords),
+    _ => Err(Box::from(format!("unknown move '{notation}'"))),
+  }
+}
+
+pub fn parse_engine_command(line: &str) -> Result<EngineCommand, Box<dyn Error>> {
+  let mut words = line.split_whitespace();
+
+  match words.next() {
+    Some("uci") => Ok(EngineCommand::Uci),
+    Some("isready") => Ok(EngineCommand::IsReady),
+    Some("ucinewgame") => Ok(EngineCommand::NewGame),
+    Some("go") => Ok(EngineCommand::Go),
+    Some("quit") => Ok(EngineCommand::Quit),
+    Some("position") => {
+      let starting_board = match words.next() {
+        Some(notation) => BoardConfig::from_notation(notation)?,
+        None => return Err(Box::from("position is missing a board")),
+      };
+
+      let moves = match words.next() {
+        Some("moves") => words
+          .map(parse_engine_move)
+          .collect::<Result<Vec<Coordinates>, Box<dyn Error>>>()?,
+        Some(word) => return Err(Box::from(format!("expected 'moves' but got '{word}'"))),
+        None => vec![],
+      };
+
+      Ok(EngineCommand::Position(starting_board, moves))
+    }
+    Some(command) => Err(Box::from(format!("unknown command '{command}'"))),
+    None => Err(Box::from("empty command")),
+  }
+}
+
+/// Reads commands until 'quit' or the input closes.
+/// Problems with a command are reported as "info string <error>" rather than ending the session.
+pub fn run_engine_protocol<R: BufRead, W: Write>(
+  input: R,
+  mut output: W,
+) -> Result<(), Box<dyn Error>> {
+  let mut session = EngineSession::new();
+
+  for line in input.lines() {
+    let line = line?;
+
+    if line.trim().is_empty() {
+      continue;
+    }
+
+    match parse_engine_command(&line) {
+      Ok(EngineCommand::Uci) => {
+        writeln!(output, "id name {ENGINE_NAME}")?;
+        writeln!(output, "uciok")?;
+      }
+      Ok(EngineCommand::IsReady) => writeln!(output, "readyok")?,
+      Ok(EngineCommand::NewGame) => session = EngineSession::new(),
+      Ok(EngineCommand::Position(starting_board, moves)) => {
+        if let Err(error) = session.set_position(starting_board, moves) {
+          writeln!(output, "info string {error}")?;
+        }
+      }
+      Ok(EngineCommand::Go) => match session.best_move() {
+        Some(coords) => writeln!(output, "bestmove {}", row_column_notation(&coords))?,
+        None => writeln!(output, "bestmove none")?,
+      },
+      Ok(EngineCommand::Quit) => break,
+      Err(error) => writeln!(output, "info string {error}")?,
+    }
+
+    output.flush()?;
+  }
+
+  Ok(())
+}
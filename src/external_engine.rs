@@ -0,0 +1,191 @@
+//! Runs another program as an opponent, talking to it with the same protocol
+//! the 'engine' gamemode speaks. See engine_protocol.rs.
+//!
+//! Every turn the engine is sent the full game and asked for a move:
+//!
+//! ```text
+//! > position startpos moves b2 a1
+//! > go
+//! < bestmove c3
+//! ```
+
+use crate::coordinate_methods::*;
+use crate::engine_protocol::parse_engine_move;
+use crate::gameboard::*;
+use crate::gameplay::row_column_notation;
+use std::error::Error;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_MOVE_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug)]
+pub struct ExternalEngine {
+  pub engine_symbol: BoardStates,
+  pub move_timeout: Duration,
+  pub moves: Vec<Coordinates>,
+  child: Child,
+  engine_input: ChildStdin,
+  engine_output: Receiver<String>,
+}
+
+/// The reason an external engine lost the game without it being played out.
+#[derive(PartialEq, Clone, Debug)]
+pub enum Forfeit {
+  TimedOut,
+  IllegalMove(String),
+  Disconnected,
+}
+
+impl ExternalEngine {
+  /// Splits the command line on whitespace, the first word being the program to run.
+  pub fn launch(command_line: &str, engine_symbol: BoardStates) -> Result<Self, Box<dyn Error>> {
+    let mut words = command_line.split_whitespace();
+    let program = words.next().ok_or("no engine command was given")?;
+
+    let mut command = Command::new(program);
+    command.args(words);
+
+    Self::from_command(command, engine_symbol)
+  }
+
+  /// Spawns the command and waits for it to answer 'uci' with 'uciok'.
+  pub fn from_command(
+    mut command: Command,
+    engine_symbol: BoardStates,
+  ) -> Result<Self, Box<dyn Error>> {
+    let mut child = command
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::null())
+      .spawn()?;
+
+    let engine_input = child
+      .stdin
+      .take()
+      .ok_or("failed to open the engine's input")?;
+    let stdout = child
+      .stdout
+      .take()
+      .ok_or("failed to open the engine's output")?;
+    let (sender, engine_output) = mpsc::channel();
+
+    thread::spawn(move || {
+      for line in BufReader::new(stdout).lines() {
+        let Ok(line) = line else { break };
+
+        if sender.send(line).is_err() {
+          break;
+        }
+      }
+    });
+
+    let mut engine = ExternalEngine {
+      engine_symbol,
+      move_timeout: DEFAULT_MOVE_TIMEOUT,
+      moves: vec![],
+      child,
+      engine_input,
+      engine_output,
+    };
+
+    engine.send("uci")?;
+
+    match engine.wait_for_reply("uciok") {
+      Ok(_) => Ok(engine),
+      Err(forfeit) => Err(Box::from(format!(
+        "engine didn't finish starting up: {forfeit:?}"
+      ))),
+    }
+  }
+
+  /// Sends the game so far and waits for the engine's move.
+  /// A reply after move_timeout, or one that can't be placed, forfeits the game.
+  pub fn request_move(&mut self, gameboard: &BoardConfig) -> Result<Coordinates, Forfeit> {
+    self.record_new_moves(gameboard);
+
+    let moves = self
+      .moves
+      .iter()
+      .map(row_column_notation)
+      .collect::<Vec<String>>()
+      .join(" ");
+
+    self
+      .send(&format!("position startpos moves {moves}"))
+      .and_then(|_| self.send("go"))
+      .map_err(|_| Forfeit::Disconnected)?;
+
+    let reply = self.wait_for_reply("bestmove")?;
+    let notation = reply.split_whitespace().nth(1).unwrap_or_default();
+
+    let coords = parse_engine_move(notation)
+      .map_err(|_| Forfeit::IllegalMove(format!("'{notation}' isn't a move")))?;
+
+    if gameboard.get_board_state(&coords) != &BoardStates::Empty {
+      return Err(Forfeit::IllegalMove(format!("{notation} is already taken")));
+    }
+
+    self.moves.push(coords);
+
+    Ok(coords)
+  }
+
+  /// Adds tiles placed since the engine's last turn to the move list.
+  /// X always places first, so any catching up alternates X then O.
+  fn record_new_moves(&mut self, gameboard: &BoardConfig) {
+    let (mut new_x, mut new_o): (Vec<Coordinates>, Vec<Coordinates>) = (0..GRID_SIZE)
+      .flat_map(|row| (0..GRID_SIZE).map(move |column| (row, column)))
+      .filter(|coords| {
+        gameboard.get_board_state(coords) != &BoardStates::Empty && !self.moves.contains(coords)
+      })
+      .partition(|coords| gameboard.get_board_state(coords) == &BoardStates::X);
+
+    new_x.reverse();
+    new_o.reverse();
+
+    while !new_x.is_empty() || !new_o.is_empty() {
+      let x_to_place = self.moves.len().is_multiple_of(2);
+
+      let next_move = if (x_to_place && !new_x.is_empty()) || new_o.is_empty() {
+        new_x.pop()
+      } else {
+        new_o.pop()
+      };
+
+      self.moves.extend(next_move);
+    }
+  }
+
+  fn send(&mut self, command: &str) -> std::io::Result<()> {
+    writeln!(self.engine_input, "{command}")?;
+    self.engine_input.flush()
+  }
+
+  /// Skips lines such as 'info string ...' until one starts with the expected word.
+  fn wait_for_reply(&self, expected_word: &str) -> Result<String, Forfeit> {
+    let deadline = Instant::now() + self.move_timeout;
+
+    loop {
+      let time_left = deadline.saturating_duration_since(Instant::now());
+
+      match self.engine_output.recv_timeout(time_left) {
+        Ok(line) if line.split_whitespace().next() == Some(expected_word) => return Ok(line),
+        Ok(_) => continue,
+        Err(RecvTimeoutError::Timeout) => return Err(Forfeit::TimedOut),
+        Err(RecvTimeoutError::Disconnected) => return Err(Forfeit::Disconnected),
+      }
+    }
+  }
+}
+
+impl Drop for ExternalEngine {
+  fn drop(&mut self) {
+    let _ = self.send("quit");
+    let _ = self.child.kill();
+    let _ = self.child.wait();
+  }
+}
@@ -0,0 +1,227 @@
+//! A C interface to the game, built into the cdylib.
+//! The header is checked in at include/tictactoe_with_ai.h. build.rs generates
+//! it again into OUT_DIR and the tests fail if the two differ, so after changing
+//! this file run `cbindgen --config cbindgen.toml --output include/tictactoe_with_ai.h`.
+//!
+//! ```c
+//! TttGame *game = ttt_game_new(TTT_SYMBOL_X);
+//!
+//! ttt_game_place_tile(game, 1, 1);
+//! ttt_game_bot_move(game, &row, &column);
+//!
+//! if (ttt_game_outcome(game) != TTT_OUTCOME_IN_PROGRESS) { ... }
+//!
+//! ttt_game_free(game);
+//! ```
+//!
+//! Rows and columns go from 0 to 2, row 0 being 'a' in "a1".
+
+use crate::coordinate_methods::*;
+use crate::gameboard::*;
+use crate::gameplay::*;
+
+/// A game against the bot, only ever handled through a pointer.
+pub struct TttGame {
+  gameconfig: GameConfig,
+}
+
+#[repr(C)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum TttSymbol {
+  Empty = 0,
+  X = 1,
+  O = 2,
+  /// Only on boards set up with blocked tiles, never in ttt_game_new's games.
+  Blocked = 3,
+}
+
+#[repr(C)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum TttOutcome {
+  InProgress = 0,
+  XWon = 1,
+  OWon = 2,
+  Draw = 3,
+}
+
+#[repr(C)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum TttStatus {
+  Ok = 0,
+  NullPointer = 1,
+  OutOfRange = 2,
+  TileTaken = 3,
+  GameOver = 4,
+  NotYourTurn = 5,
+}
+
+impl From<BoardStates> for TttSymbol {
+  fn from(board_state: BoardStates) -> Self {
+    match board_state {
+      BoardStates::X => TttSymbol::X,
+      BoardStates::O => TttSymbol::O,
+      BoardStates::Empty => TttSymbol::Empty,
+      BoardStates::Blocked => TttSymbol::Blocked,
+    }
+  }
+}
+
+/// Starts a game where the caller places player_symbol, TTT_SYMBOL_X or TTT_SYMBOL_O,
+/// and the bot places the other. Anything else picks randomly.
+/// X places first, so if the bot is X call ttt_game_bot_move before placing.
+///
+/// The game must be freed with ttt_game_free.
+#[no_mangle]
+pub extern "C" fn ttt_game_new(player_symbol: u32) -> *mut TttGame {
+  let mut gameconfig = GameConfig::new();
+
+  let (player_symbol, bot_symbol) = match player_symbol {
+    1 => (BoardStates::X, BoardStates::O),
+    2 => (BoardStates::O, BoardStates::X),
+    _ => (gameconfig.player_symbol, gameconfig.bot.bot_symbol),
+  };
+
+  gameconfig.player_symbol = player_symbol;
+  gameconfig.bot.bot_symbol = bot_symbol;
+
+  Box::into_raw(Box::new(TttGame { gameconfig }))
+}
+
+/// # Safety
+///
+/// game must come from ttt_game_new and not have been freed already.
+/// Passing null does nothing.
+#[no_mangle]
+pub unsafe extern "C" fn ttt_game_free(game: *mut TttGame) {
+  if !game.is_null() {
+    drop(Box::from_raw(game));
+  }
+}
+
+/// Places the caller's symbol at row and column.
+///
+/// # Safety
+///
+/// game must be null or come from ttt_game_new and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn ttt_game_place_tile(
+  game: *mut TttGame,
+  row: usize,
+  column: usize,
+) -> TttStatus {
+  let Some(game) = game.as_mut() else {
+    return TttStatus::NullPointer;
+  };
+
+  let gameconfig = &mut game.gameconfig;
+
+  if row >= GRID_SIZE || column >= GRID_SIZE {
+    return TttStatus::OutOfRange;
+  }
+
+  if gameconfig.current_result().is_some() {
+    return TttStatus::GameOver;
+  }
+
+  if gameconfig.gameboard.next_symbol_to_place() != gameconfig.player_symbol {
+    return TttStatus::NotYourTurn;
+  }
+
+  if gameconfig.gameboard.get_board_state(&(row, column)) != &BoardStates::Empty {
+    return TttStatus::TileTaken;
+  }
+
+  gameconfig
+    .gameboard
+    .place_tile(&(row, column), gameconfig.player_symbol);
+  gameconfig.gameboard.tiles_covered += 1;
+
+  TttStatus::Ok
+}
+
+/// Has the bot place its symbol, writing where it placed into row and column.
+/// Either pointer can be null if the caller doesn't need it.
+///
+/// # Safety
+///
+/// game must be null or come from ttt_game_new and not have been freed.
+/// row and column must be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn ttt_game_bot_move(
+  game: *mut TttGame,
+  row: *mut usize,
+  column: *mut usize,
+) -> TttStatus {
+  let Some(game) = game.as_mut() else {
+    return TttStatus::NullPointer;
+  };
+
+  let gameconfig = &mut game.gameconfig;
+
+  if gameconfig.current_result().is_some() {
+    return TttStatus::GameOver;
+  }
+
+  if gameconfig.gameboard.next_symbol_to_place() != gameconfig.bot.bot_symbol {
+    return TttStatus::NotYourTurn;
+  }
+
+  let coords = bot_place_tile(gameconfig);
+
+  if let Some(row) = row.as_mut() {
+    *row = coords.0;
+  }
+
+  if let Some(column) = column.as_mut() {
+    *column = coords.1;
+  }
+
+  TttStatus::Ok
+}
+
+/// # Safety
+///
+/// game must be null or come from ttt_game_new and not have been freed.
+/// Null or out of range tiles are reported as empty.
+#[no_mangle]
+pub unsafe extern "C" fn ttt_game_get_tile(
+  game: *const TttGame,
+  row: usize,
+  column: usize,
+) -> TttSymbol {
+  match game.as_ref() {
+    Some(game) if row < GRID_SIZE && column < GRID_SIZE => {
+      TttSymbol::from(*game.gameconfig.gameboard.get_board_state(&(row, column)))
+    }
+    _ => TttSymbol::Empty,
+  }
+}
+
+/// # Safety
+///
+/// game must be null or come from ttt_game_new and not have been freed.
+/// Null is reported as TTT_OUTCOME_IN_PROGRESS.
+#[no_mangle]
+pub unsafe extern "C" fn ttt_game_outcome(game: *const TttGame) -> TttOutcome {
+  let Some(game) = game.as_ref() else {
+    return TttOutcome::InProgress;
+  };
+
+  match game.gameconfig.current_result() {
+    Some(GameState::XWon) => TttOutcome::XWon,
+    Some(GameState::OWon) => TttOutcome::OWon,
+    Some(GameState::Draw) => TttOutcome::Draw,
+    None => TttOutcome::InProgress,
+  }
+}
+
+/// # Safety
+///
+/// game must be null or come from ttt_game_new and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn ttt_game_player_symbol(game: *const TttGame) -> TttSymbol {
+  match game.as_ref() {
+    Some(game) => TttSymbol::from(game.gameconfig.player_symbol),
+    None => TttSymbol::Empty,
+  }
+}
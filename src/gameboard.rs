@@ -1,13 +1,20 @@
 use crate::coordinate_methods::*;
 use crate::gameplay::GameState;
 use rand::prelude::*;
-use std::fmt::{Display, Formatter, Result};
+use std::error::Error;
+
+pub const ALL_ROWS: [[Coordinates; GRID_SIZE]; 8] = [
+  [(0, 0), (0, 1), (0, 2)],
+  [(1, 0), (1, 1), (1, 2)],
+  [(2, 0), (2, 1), (2, 2)],
+  [(0, 0), (1, 0), (2, 0)],
+  [(0, 1), (1, 1), (2, 1)],
+  [(0, 2), (1, 2), (2, 2)],
+  [(0, 0), (1, 1), (2, 2)],
+  [(0, 2), (1, 1), (2, 0)],
+];
 
-const VISUALIZED_X: &str = "X";
-const VISUALIZED_O: &str = "O";
-const VISUALIZED_EMPTY: &str = "▮";
-
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Clone, Debug)]
 pub struct BoardConfig {
   pub tiles: [[BoardTile; GRID_SIZE]; GRID_SIZE],
   pub tiles_covered: u8,
@@ -26,6 +33,15 @@ pub enum BoardStates {
   X,
   O,
   Empty,
+  /// A tile nothing can be placed on, so no row through it can be completed.
+  Blocked,
+}
+
+impl BoardStates {
+  /// X or O, as opposed to an empty or blocked tile.
+  pub fn is_symbol(&self) -> bool {
+    matches!(self, BoardStates::X | BoardStates::O)
+  }
 }
 
 impl AsRef<BoardStates> for BoardStates {
@@ -69,19 +85,132 @@ impl BoardConfig {
     }
   }
 
-  pub fn all_tiles_covered(self) -> bool {
-    self.tiles_covered == 9
+  /// Builds a board from a 9 character notation read row by row, where 'x' and 'o'
+  /// are placed symbols, '.' is an empty tile and '#' a blocked one.
+  /// e.g. "x...o...." or "startpos".
+  pub fn from_notation(notation: &str) -> Result<Self, Box<dyn Error>> {
+    let mut gameboard = BoardConfig::new();
+
+    if notation == "startpos" {
+      return Ok(gameboard);
+    }
+
+    let symbols: Vec<char> = notation.chars().collect();
+
+    if symbols.len() != GRID_SIZE * GRID_SIZE {
+      return Err(Box::from(format!(
+        "notation '{notation}' must be {} tiles long",
+        GRID_SIZE * GRID_SIZE
+      )));
+    }
+
+    for (index, symbol) in symbols.into_iter().enumerate() {
+      let board_state = match symbol.to_ascii_lowercase() {
+        'x' => BoardStates::X,
+        'o' => BoardStates::O,
+        '.' | '-' => continue,
+        '#' => {
+          gameboard.place_tile(
+            &(index / GRID_SIZE, index % GRID_SIZE),
+            BoardStates::Blocked,
+          );
+          continue;
+        }
+        _ => return Err(Box::from(format!("unknown tile '{symbol}' in notation"))),
+      };
+
+      gameboard.place_tile(&(index / GRID_SIZE, index % GRID_SIZE), board_state);
+      gameboard.tiles_covered += 1;
+    }
+
+    gameboard.last_modified_tile = None;
+
+    let x_count = notation
+      .chars()
+      .filter(|c| c.eq_ignore_ascii_case(&'x'))
+      .count();
+    let o_count = notation
+      .chars()
+      .filter(|c| c.eq_ignore_ascii_case(&'o'))
+      .count();
+
+    if x_count != o_count && x_count != o_count + 1 {
+      return Err(Box::from(format!(
+        "notation '{notation}' can't be reached when X places first"
+      )));
+    }
+
+    Ok(gameboard)
   }
 
-  pub fn print_board(&self) {
+  /// The inverse of from_notation.
+  pub fn to_notation(&self) -> String {
     self
       .tiles
       .iter()
       .flatten()
-      .map(|tile| format!("{}", tile))
-      .collect::<Vec<String>>()
-      .chunks(GRID_SIZE)
-      .for_each(|row| println!("{}|{}|{}", row[0], row[1], row[2]));
+      .map(|tile| match tile.board_state {
+        BoardStates::X => 'x',
+        BoardStates::O => 'o',
+        BoardStates::Empty => '.',
+        BoardStates::Blocked => '#',
+      })
+      .collect()
+  }
+
+  /// X always places first, so whoever has placed less is the one to move.
+  pub fn next_symbol_to_place(&self) -> BoardStates {
+    let count_of = |symbol: BoardStates| {
+      self
+        .tiles
+        .iter()
+        .flatten()
+        .filter(|tile| tile.board_state == symbol)
+        .count()
+    };
+
+    if count_of(BoardStates::X) > count_of(BoardStates::O) {
+      BoardStates::O
+    } else {
+      BoardStates::X
+    }
+  }
+
+  /// Returns true if the last placed tile completed a row.
+  pub fn check_if_win(&self) -> bool {
+    let Some(last_modified_tile) = self.last_modified_tile else {
+      return false;
+    };
+
+    self
+      .matching_adjacent_tiles(&last_modified_tile)
+      .iter()
+      .any(|coords| last_modified_tile.is_matching_in_a_row(coords, self))
+  }
+
+  /// Searches the whole board for a completed row, unlike check_if_win
+  /// which only looks around the last placed tile.
+  pub fn winning_row(&self) -> Option<[Coordinates; GRID_SIZE]> {
+    ALL_ROWS.into_iter().find(|row| {
+      self.get_board_state(&row[0]).is_symbol()
+        && row
+          .iter()
+          .all(|coords| self.get_board_state(coords) == self.get_board_state(&row[0]))
+    })
+  }
+
+  /// Blocked tiles count as covered since nothing can go on them.
+  pub fn all_tiles_covered(&self) -> bool {
+    self.tiles_covered as usize + self.blocked_tile_count() == GRID_SIZE * GRID_SIZE
+  }
+
+  pub fn blocked_tile_count(&self) -> usize {
+    self
+      .tiles
+      .iter()
+      .flatten()
+      .filter(|tile| tile.board_state == BoardStates::Blocked)
+      .count()
   }
 
   /// this will return all coordinates around an input that are of the same boardstate
@@ -90,7 +219,7 @@ impl BoardConfig {
     let matching_symbol: &BoardStates = {
       let symbol = &self.get_board_state(coords);
 
-      if *symbol == &BoardStates::Empty {
+      if !symbol.is_symbol() {
         return vec![];
       } else {
         symbol
@@ -173,7 +302,7 @@ impl BoardConfig {
     }
   }
 
-  pub fn last_placed_tile_to_game_state(&mut self) -> GameState {
+  pub fn last_placed_tile_to_game_state(&self) -> GameState {
     match self.get_board_state(&self.last_modified_tile.unwrap()) {
       BoardStates::X => GameState::XWon,
       BoardStates::O => GameState::OWon,
@@ -211,18 +340,6 @@ impl BoardTile {
   }
 }
 
-impl Display for BoardTile {
-  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-    let output = match self.board_state {
-      BoardStates::X => VISUALIZED_X,
-      BoardStates::O => VISUALIZED_O,
-      BoardStates::Empty => VISUALIZED_EMPTY,
-    };
-
-    write!(f, "{}", output)
-  }
-}
-
 fn series_of_two_edge_check(
   gameboard: &BoardConfig,
   check_from: &Coordinates,
@@ -1,8 +1,61 @@
+use crate::blocked::blocked;
+use crate::board_render::Theme;
 use crate::bot::*;
+use crate::config::{ConfigFile, Starts};
 use crate::coordinate_methods::*;
+use crate::cube::cube;
+use crate::engine_protocol::run_engine_protocol;
+use crate::external_engine::*;
 use crate::gameboard::*;
+use crate::gomoku::gomoku;
+use crate::gravity::gravity;
+use crate::http_api::{serve, DEFAULT_ADDRESS};
+use crate::morris::morris;
+use crate::multiplayer::multiplayer;
+use crate::network_play::{host_game, join_game};
+use crate::notakto::notakto;
+use crate::numerical::numerical;
+use crate::order_chaos::order_chaos;
+use crate::quantum::quantum;
+use crate::solver;
+use crate::torus::torus;
+use crate::tui::run_tui;
+use crate::ultimate::ultimate;
+use crate::wild::wild;
 use std::error::Error;
-use std::io;
+use std::io::{self, BufRead, Write};
+use std::thread;
+use std::time::Duration;
+
+pub const BOT_PLAY_DELAY: Duration = Duration::from_millis(500);
+pub const INPUT_ENDED_ERROR: &str = "no more input";
+pub const DEFAULT_PLAYER_NAME: &str = "You";
+pub const MISERE_REMINDER: &str = " | Misère, three in a row loses";
+
+/// Everything run_gamemode accepts, player_vs_bot being the one run without a mode.
+pub const GAMEMODES: [&str; 21] = [
+  "player_vs_bot",
+  "bot_play",
+  "free_play",
+  "tui",
+  "engine",
+  "serve",
+  "host",
+  "join",
+  "ultimate",
+  "notakto",
+  "morris",
+  "gravity",
+  "wild",
+  "numerical",
+  "quantum",
+  "cube",
+  "torus",
+  "blocked",
+  "multiplayer",
+  "order_chaos",
+  "gomoku",
+];
 
 #[derive(PartialEq, Debug)]
 pub struct GameConfig {
@@ -11,12 +64,40 @@ pub struct GameConfig {
   pub gameboard: BoardConfig,
   pub end_gamestate: GameState,
   pub bot: Bot,
+  pub theme: Theme,
+  /// Off unless main finds stdout is a terminal, so captured output is plain.
+  pub colors: bool,
+  pub bot_play_delay: Duration,
+  pub player_name: String,
+  /// Completing a row loses instead of wins.
+  pub misere: bool,
+  pub config_file: ConfigFile,
 }
 
 impl GameConfig {
-  pub fn new() -> Result<GameConfig, Box<dyn Error>> {
-    let player_turn = rand::random::<bool>();
+  /// A game with every setting at its default. The config file is never read
+  /// here, main loads it and passes it to from_config.
+  pub fn new() -> GameConfig {
+    GameConfig::from_valid_config(&ConfigFile::default())
+  }
+
+  /// A game set up from the config file, see config.rs.
+  pub fn from_config(config_file: &ConfigFile) -> Result<GameConfig, Box<dyn Error>> {
+    config_file.validate()?;
+
+    Ok(GameConfig::from_valid_config(config_file))
+  }
+
+  fn from_valid_config(config_file: &ConfigFile) -> GameConfig {
+    let player_turn = match (config_file.symbol, config_file.starts) {
+      (Some(symbol), _) => symbol == BoardStates::X,
+      (None, Some(Starts::Player)) => true,
+      (None, Some(Starts::Bot)) => false,
+      (None, Some(Starts::Random) | None) => rand::random::<bool>(),
+    };
+
     let mut bot = Bot::new();
+    bot.difficulty = config_file.difficulty.unwrap_or_default();
 
     let player_symbol = if player_turn {
       bot.bot_symbol = BoardStates::O;
@@ -26,29 +107,74 @@ impl GameConfig {
       BoardStates::O
     };
 
-    Ok(GameConfig {
+    GameConfig {
       player_turn,
       player_symbol,
       gameboard: BoardConfig::new(),
       end_gamestate: GameState::Draw,
       bot,
-    })
+      theme: config_file.theme.unwrap_or_default(),
+      colors: false,
+      bot_play_delay: config_file.bot_play_delay.unwrap_or(BOT_PLAY_DELAY),
+      player_name: config_file
+        .player_name
+        .clone()
+        .unwrap_or_else(|| DEFAULT_PLAYER_NAME.to_string()),
+      misere: config_file.misere.unwrap_or(false),
+      config_file: config_file.clone(),
+    }
   }
 
-  pub fn check_if_win(&mut self) -> bool {
+  /// Prints the board with the chosen theme.
+  pub fn print_board(&self) {
+    let _ = self.write_board(&mut io::stdout());
+  }
+
+  pub fn write_board(&self, output: &mut dyn Write) -> io::Result<()> {
     self
-      .gameboard
-      .matching_adjacent_tiles(&self.gameboard.last_modified_tile.unwrap())
-      .iter()
-      .filter(|coords| {
-        self
-          .gameboard
-          .last_modified_tile
-          .unwrap()
-          .is_matching_in_a_row(coords, &self.gameboard)
-      })
-      .count()
-      != 0
+      .theme
+      .renderer(self.colors)
+      .write_board(&self.gameboard, output)
+  }
+
+  pub fn check_if_win(&mut self) -> bool {
+    self.gameboard.check_if_win()
+  }
+
+  /// Who the completed row on the board wins for, the other side when playing misère.
+  pub fn completed_row_result(&self) -> GameState {
+    match (self.gameboard.last_placed_tile_to_game_state(), self.misere) {
+      (GameState::XWon, true) => GameState::OWon,
+      (GameState::OWon, true) => GameState::XWon,
+      (end_gamestate, _) => end_gamestate,
+    }
+  }
+
+  /// None while the game can still be played.
+  pub fn current_result(&self) -> Option<GameState> {
+    if self.gameboard.winning_row().is_some() {
+      Some(self.completed_row_result())
+    } else if self.gameboard.all_tiles_covered() {
+      Some(GameState::Draw)
+    } else {
+      None
+    }
+  }
+}
+
+/// Anything choosing tiles without someone at the keyboard.
+#[derive(Debug)]
+pub enum Opponent {
+  Bot(Bot),
+  External(ExternalEngine),
+}
+
+impl Opponent {
+  pub fn symbol(&self) -> BoardStates {
+    match self {
+      Opponent::Bot(bot) => bot.bot_symbol,
+      Opponent::External(engine) => engine.engine_symbol,
+    }
   }
 }
 
@@ -59,51 +185,140 @@ pub enum GameState {
   Draw,
 }
 
-pub fn player_vs_bot(mut gameconfig: GameConfig) -> Result<(), Box<dyn Error>> {
-  println!("\n\n -- run the program with 'bot_play' or 'free_play' for other modes -- \n\n");
+pub fn player_vs_bot(gameconfig: GameConfig) -> Result<(), Box<dyn Error>> {
+  player_vs_bot_session(gameconfig, io::stdin().lock(), io::stdout())?;
+
+  Ok(())
+}
+
+/// Plays against the bot, reading selections from input and writing everything to output.
+pub fn player_vs_bot_session<R: BufRead, W: Write>(
+  mut gameconfig: GameConfig,
+  mut input: R,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
+  writeln!(
+    output,
+    "\n\n -- run the program with 'bot_play' or 'free_play' for other modes -- \n\n"
+  )?;
+  writeln!(
+    output,
+    "{} {:?} | Bot {:?}{}",
+    gameconfig.player_name,
+    gameconfig.player_symbol,
+    gameconfig.bot.bot_symbol,
+    if gameconfig.misere {
+      MISERE_REMINDER
+    } else {
+      ""
+    }
+  )?;
 
   while gameconfig.gameboard.tiles_covered < 9 {
-    println!();
+    writeln!(output)?;
 
     if gameconfig.player_turn {
-      player_turn(&mut gameconfig);
+      player_turn(&mut gameconfig, &mut input, &mut output)?;
 
       gameconfig.player_turn = false;
     } else {
-      bot_turn(&mut gameconfig.bot, &mut gameconfig.gameboard);
+      gameconfig.write_board(&mut output)?;
+      bot_place_tile(&mut gameconfig);
+
+      writeln!(output, " -- bot turn over --\n")?;
 
       gameconfig.player_turn = true;
     }
 
     if gameconfig.check_if_win() {
-      gameconfig.end_gamestate = gameconfig.gameboard.last_placed_tile_to_game_state();
+      gameconfig.end_gamestate = gameconfig.completed_row_result();
 
       break;
     }
   }
 
-  println!("{:?}", gameconfig.end_gamestate);
-  gameconfig.gameboard.print_board();
+  write_end_gamestate(&gameconfig, &mut output)?;
+  gameconfig.write_board(&mut output)?;
 
-  Ok(())
+  Ok(gameconfig.end_gamestate)
+}
+
+/// What sessions error with once input runs out, so it can be told apart
+/// from bad input by is_input_ended rather than by its message.
+pub fn input_ended_error() -> Box<dyn Error> {
+  Box::new(io::Error::new(
+    io::ErrorKind::UnexpectedEof,
+    INPUT_ENDED_ERROR,
+  ))
+}
+
+pub fn is_input_ended(error: &(dyn Error + 'static)) -> bool {
+  error
+    .downcast_ref::<io::Error>()
+    .is_some_and(|error| error.kind() == io::ErrorKind::UnexpectedEof)
 }
 
-pub fn parse_player_input() -> Result<Coordinates, Box<dyn Error>> {
-  println!("Select a tile 1-9 or a1, b2, c3, etc.");
+/// Asks for a tile and parses the reply, erroring with input_ended_error once input runs out.
+pub fn parse_player_input<R: BufRead, W: Write>(
+  input: &mut R,
+  output: &mut W,
+) -> Result<Coordinates, Box<dyn Error>> {
+  writeln!(output, "Select a tile 1-9 or a1, b2, c3, etc.")?;
 
   let mut player_input = String::new();
-  io::stdin().read_line(&mut player_input).unwrap();
 
-  if player_input.trim().len() == 1 {
-    return index_parsing(player_input);
-  } else if player_input.trim().len() == 2 {
-    return row_column_parsing(player_input);
+  if input.read_line(&mut player_input)? == 0 {
+    return Err(input_ended_error());
+  }
+
+  parse_tile_selection(&player_input)
+}
+
+/// Asks for tiles with parse_player_input until one is empty on gameboard,
+/// writing out why any others can't be placed on.
+pub fn select_empty_tile<R: BufRead, W: Write>(
+  gameboard: &BoardConfig,
+  input: &mut R,
+  output: &mut W,
+) -> Result<Coordinates, Box<dyn Error>> {
+  loop {
+    let selected_tile = parse_player_input(input, output).and_then(|coords| {
+      if gameboard.get_board_state(&coords) == &BoardStates::Empty {
+        Ok(coords)
+      } else {
+        Err(Box::from(format!(
+          "{} is already taken",
+          row_column_notation(&coords)
+        )))
+      }
+    });
+
+    match selected_tile {
+      Ok(coords) => return Ok(coords),
+      Err(error) if is_input_ended(error.as_ref()) => return Err(error),
+      Err(error) => writeln!(output, "{error}")?,
+    }
+  }
+}
+
+/// Turns a selection such as "5" or "b2" into coordinates.
+pub fn parse_tile_selection(player_input: &str) -> Result<Coordinates, Box<dyn Error>> {
+  let player_input = player_input.trim();
+
+  if !player_input.is_ascii() {
+    return Err(Box::from("incorrect input"));
+  }
+
+  if player_input.len() == 1 {
+    return index_parsing(player_input.to_string());
+  } else if player_input.len() == 2 {
+    return row_column_parsing(player_input.to_string());
   }
 
   Err(Box::from("incorrect input"))
 }
 
-fn row_column_parsing(player_input: String) -> Result<Coordinates, Box<dyn Error>> {
+pub fn row_column_parsing(player_input: String) -> Result<Coordinates, Box<dyn Error>> {
   let coord_1 = match player_input[0..1].to_lowercase().trim() {
     "a" => 0,
     "b" => 1,
@@ -120,7 +335,7 @@ fn row_column_parsing(player_input: String) -> Result<Coordinates, Box<dyn Error
   Err(Box::from("incorrect input"))
 }
 
-fn index_parsing(player_input: String) -> Result<Coordinates, Box<dyn Error>> {
+pub fn index_parsing(player_input: String) -> Result<Coordinates, Box<dyn Error>> {
   if let Ok(num) = player_input.trim().parse::<usize>() {
     if num != 0 {
       return Ok(((num - 1) / GRID_SIZE, (num - 1) % GRID_SIZE));
@@ -130,12 +345,28 @@ fn index_parsing(player_input: String) -> Result<Coordinates, Box<dyn Error>> {
   Err(Box::from("incorrect input"))
 }
 
-pub fn free_play(mut gameconfig: GameConfig) -> Result<(), Box<dyn Error>> {
+/// The inverse of row_column_parsing, (0, 0) becomes "a1".
+pub fn row_column_notation(coords: &Coordinates) -> String {
+  format!("{}{}", (b'a' + coords.0 as u8) as char, coords.1 + 1)
+}
+
+pub fn free_play(gameconfig: GameConfig) -> Result<(), Box<dyn Error>> {
+  free_play_session(gameconfig, io::stdin().lock(), io::stdout())?;
+
+  Ok(())
+}
+
+/// Two players taking turns on the same input.
+pub fn free_play_session<R: BufRead, W: Write>(
+  mut gameconfig: GameConfig,
+  mut input: R,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
   let player_one_symbol = gameconfig.player_symbol;
   let player_two_symbol = gameconfig.bot.bot_symbol;
 
   while gameconfig.gameboard.tiles_covered < 9 {
-    println!();
+    writeln!(output)?;
 
     if gameconfig.player_turn {
       gameconfig.player_symbol = player_one_symbol;
@@ -143,72 +374,154 @@ pub fn free_play(mut gameconfig: GameConfig) -> Result<(), Box<dyn Error>> {
       gameconfig.player_symbol = player_two_symbol;
     }
 
-    player_turn(&mut gameconfig);
+    player_turn(&mut gameconfig, &mut input, &mut output)?;
 
     if gameconfig.check_if_win() {
-      gameconfig.end_gamestate = gameconfig.gameboard.last_placed_tile_to_game_state();
+      gameconfig.end_gamestate = gameconfig.completed_row_result();
 
       break;
     }
 
-    if gameconfig.player_turn {
-      gameconfig.player_turn = false
-    } else {
-      gameconfig.player_turn = true
-    }
+    gameconfig.player_turn = !gameconfig.player_turn;
   }
 
-  println!("{:?}", gameconfig.end_gamestate);
-  gameconfig.gameboard.print_board();
+  write_end_gamestate(&gameconfig, &mut output)?;
+  gameconfig.write_board(&mut output)?;
 
-  Ok(())
+  Ok(gameconfig.end_gamestate)
 }
 
-pub fn bot_play(mut gameconfig: GameConfig) -> Result<(), Box<dyn Error>> {
-  use std::thread;
-  use std::time::Duration;
+/// `bot_play [--x <command>] [--o <command>] [--timeout <milliseconds>]`
+///
+/// Either side can be replaced by an external engine, see external_engine.rs.
+pub fn bot_play(
+  mut gameconfig: GameConfig,
+  gamemode_arguments: &[String],
+) -> Result<(), Box<dyn Error>> {
+  let mut x_engine_command = None;
+  let mut o_engine_command = None;
+  let mut move_timeout = DEFAULT_MOVE_TIMEOUT;
+
+  let mut arguments = gamemode_arguments.iter();
+
+  while let Some(flag) = arguments.next() {
+    let value = arguments
+      .next()
+      .ok_or_else(|| format!("'{flag}' is missing a value"))?;
+
+    match flag.as_str() {
+      "--x" => x_engine_command = Some(value),
+      "--o" => o_engine_command = Some(value),
+      "--timeout" => move_timeout = Duration::from_millis(value.parse()?),
+      _ => return Err(Box::from(format!("unknown bot_play argument '{flag}'"))),
+    }
+  }
 
   let mut second_bot = Bot::new();
-
   second_bot.bot_symbol = gameconfig.player_symbol;
+  second_bot.difficulty = gameconfig.bot.difficulty;
+
+  let mut opponents = [
+    Opponent::Bot(std::mem::take(&mut gameconfig.bot)),
+    Opponent::Bot(second_bot),
+  ];
+
+  for (engine_command, symbol) in [
+    (x_engine_command, BoardStates::X),
+    (o_engine_command, BoardStates::O),
+  ] {
+    if let Some(engine_command) = engine_command {
+      let mut engine = ExternalEngine::launch(engine_command, symbol)?;
+      engine.move_timeout = move_timeout;
+
+      let replacing = opponents
+        .iter_mut()
+        .find(|opponent| opponent.symbol() == symbol)
+        .unwrap();
+
+      *replacing = Opponent::External(engine);
+    }
+  }
 
-  while gameconfig.gameboard.tiles_covered < 9 {
-    println!();
+  let [first_opponent, second_opponent] = opponents;
+  let bot_play_delay = gameconfig.bot_play_delay;
 
-    if gameconfig.player_turn {
-      bot_turn(&mut gameconfig.bot, &mut gameconfig.gameboard);
+  opponents_play(
+    gameconfig,
+    first_opponent,
+    second_opponent,
+    bot_play_delay,
+    io::stdout(),
+  )?;
 
-      gameconfig.player_turn = false;
+  Ok(())
+}
+
+/// Plays two opponents against each other, X placing first.
+/// An external engine that times out or places illegally forfeits.
+pub fn opponents_play<W: Write>(
+  mut gameconfig: GameConfig,
+  mut first_opponent: Opponent,
+  mut second_opponent: Opponent,
+  delay_between_turns: Duration,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
+  while gameconfig.gameboard.tiles_covered < 9 {
+    writeln!(output)?;
+
+    let next_symbol = gameconfig.gameboard.next_symbol_to_place();
 
-      thread::sleep(Duration::from_millis(500));
+    let opponent = if first_opponent.symbol() == next_symbol {
+      &mut first_opponent
     } else {
-      bot_turn(&mut second_bot, &mut gameconfig.gameboard);
+      &mut second_opponent
+    };
 
-      gameconfig.player_turn = true;
+    gameconfig.write_board(&mut output)?;
+
+    if let Err(forfeit) = opponent_turn(opponent, &mut gameconfig.gameboard, gameconfig.misere) {
+      writeln!(output, "{next_symbol:?} forfeited: {forfeit:?}")?;
 
-      thread::sleep(Duration::from_millis(500));
+      gameconfig.end_gamestate = match next_symbol {
+        BoardStates::X => GameState::OWon,
+        _ => GameState::XWon,
+      };
+
+      break;
     }
 
+    match opponent {
+      Opponent::Bot(_) => writeln!(output, " -- bot turn over --\n")?,
+      Opponent::External(_) => writeln!(output, " -- engine turn over --\n")?,
+    }
+
+    thread::sleep(delay_between_turns);
+
     if gameconfig.check_if_win() {
-      gameconfig.end_gamestate = gameconfig.gameboard.last_placed_tile_to_game_state();
+      gameconfig.end_gamestate = gameconfig.completed_row_result();
       break;
     }
   }
 
-  println!("{:?}", gameconfig.end_gamestate);
-  gameconfig.gameboard.print_board();
+  write_end_gamestate(&gameconfig, &mut output)?;
+  gameconfig.write_board(&mut output)?;
 
-  Ok(())
+  Ok(gameconfig.end_gamestate)
 }
 
-fn player_turn(gameconfig: &mut GameConfig) {
+fn player_turn<R: BufRead, W: Write>(
+  gameconfig: &mut GameConfig,
+  input: &mut R,
+  output: &mut W,
+) -> Result<(), Box<dyn Error>> {
   loop {
-    gameconfig.gameboard.print_board();
+    gameconfig.write_board(output)?;
 
-    let selected_tile = match parse_player_input() {
+    let selected_tile = match parse_player_input(input, output) {
       Ok(x) => x,
+      Err(error) if is_input_ended(error.as_ref()) => return Err(error),
       Err(error) => {
-        println!("{error}");
+        writeln!(output, "{error}")?;
 
         continue;
       }
@@ -221,35 +534,126 @@ fn player_turn(gameconfig: &mut GameConfig) {
 
       gameconfig.gameboard.tiles_covered += 1;
 
-      break;
+      return Ok(());
     }
+
+    writeln!(
+      output,
+      "{} is already taken",
+      row_column_notation(&selected_tile)
+    )?;
   }
 }
 
-fn bot_turn(bot: &mut Bot, gameboard: &mut BoardConfig) {
-  gameboard.print_board();
+/// External engines are only told the moves, so they play the regular game even when misere is set.
+fn opponent_turn(
+  opponent: &mut Opponent,
+  gameboard: &mut BoardConfig,
+  misere: bool,
+) -> Result<(), Forfeit> {
+  match opponent {
+    Opponent::Bot(bot) => {
+      bot_turn(bot, gameboard, misere);
+    }
+    Opponent::External(engine) => {
+      let coords = engine.request_move(gameboard)?;
+
+      gameboard.place_tile(&coords, engine.engine_symbol);
+      gameboard.tiles_covered += 1;
+    }
+  }
 
-  bot.choose_coordinates(gameboard);
+  Ok(())
+}
 
-  gameboard.place_tile(
-    bot.most_recent_chosen_coords.as_ref().unwrap(),
-    bot.bot_symbol,
-  );
+/// Places the bot's choice without printing anything.
+pub fn bot_place_tile(gameconfig: &mut GameConfig) -> Coordinates {
+  bot_turn(
+    &mut gameconfig.bot,
+    &mut gameconfig.gameboard,
+    gameconfig.misere,
+  )
+}
 
-  println!(" -- bot turn over --\n");
+/// If the bot can't come up with an empty tile the solver picks one instead.
+fn bot_turn(bot: &mut Bot, gameboard: &mut BoardConfig, misere: bool) -> Coordinates {
+  if misere {
+    bot.choose_misere_coordinates(gameboard);
+  } else {
+    bot.choose_coordinates(gameboard);
+  }
 
+  let coords = match bot.most_recent_chosen_coords {
+    Ok(coords) if gameboard.get_board_state(&coords) == &BoardStates::Empty => coords,
+    _ if misere => solver::misere_best_move(gameboard).unwrap(),
+    _ => solver::best_move(gameboard).unwrap(),
+  };
+
+  bot.most_recent_chosen_coords = Ok(coords);
+  gameboard.place_tile(&coords, bot.bot_symbol);
   gameboard.tiles_covered += 1;
+
+  coords
+}
+
+/// The result, saying who completed the row when that lost them the game.
+fn write_end_gamestate<W: Write>(gameconfig: &GameConfig, output: &mut W) -> io::Result<()> {
+  writeln!(output, "{:?}", gameconfig.end_gamestate)?;
+
+  match gameconfig.gameboard.winning_row() {
+    Some(row) if gameconfig.misere => writeln!(
+      output,
+      " -- {:?} completed three in a row, which loses in misère --",
+      gameconfig.gameboard.get_board_state(&row[0])
+    ),
+    _ => Ok(()),
+  }
 }
 
 pub fn run_gamemode(
   user_arguments: Option<String>,
+  gamemode_arguments: &[String],
   gameconfig: GameConfig,
 ) -> Result<(), Box<dyn Error>> {
   if let Some(gamemode) = user_arguments {
     match gamemode.to_lowercase().trim() {
-      "bot_play" => bot_play(gameconfig),
+      "player_vs_bot" => player_vs_bot(gameconfig),
+      "bot_play" => bot_play(gameconfig, gamemode_arguments),
       "free_play" => free_play(gameconfig),
-      _ => Ok(()),
+      "tui" => run_tui(gameconfig),
+      "ultimate" => ultimate(gameconfig),
+      "notakto" => notakto(gameconfig, gamemode_arguments),
+      "morris" => morris(gameconfig, gamemode_arguments),
+      "gravity" => gravity(gameconfig, gamemode_arguments),
+      "wild" => wild(gameconfig),
+      "numerical" => numerical(gameconfig),
+      "quantum" => quantum(gameconfig),
+      "cube" => cube(gameconfig, gamemode_arguments),
+      "torus" => torus(gameconfig, gamemode_arguments),
+      "blocked" => blocked(gameconfig, gamemode_arguments),
+      "multiplayer" => multiplayer(gameconfig, gamemode_arguments),
+      "order_chaos" => order_chaos(gameconfig, gamemode_arguments),
+      "gomoku" => gomoku(gameconfig, gamemode_arguments),
+      "engine" => run_engine_protocol(io::stdin().lock(), io::stdout()),
+      "serve" => serve(
+        gamemode_arguments
+          .first()
+          .map_or(DEFAULT_ADDRESS, String::as_str),
+      ),
+      "host" => match gamemode_arguments.first() {
+        Some(port) => host_game(port, gameconfig),
+        None => Err(Box::from("host needs a port, e.g. 'host 7878'")),
+      },
+      "join" => match gamemode_arguments.first() {
+        Some(address) => join_game(address, gameconfig),
+        None => Err(Box::from(
+          "join needs an address, e.g. 'join 127.0.0.1:7878'",
+        )),
+      },
+      unknown => Err(Box::from(format!(
+        "unknown gamemode '{unknown}', expected one of: {}",
+        GAMEMODES.join(", ")
+      ))),
     }
   } else {
     player_vs_bot(gameconfig)
@@ -0,0 +1,530 @@
+//! Gomoku, started with `gomoku [seconds per bot move]`.
+//!
+//! Five in a row on a 15x15 board. That's far too big to search every move,
+//! so the bot only looks at tiles near the stones already down, best looking
+//! first, and gives up on a search once its time runs out.
+//!
+//! What makes a tile look good is the threat placing on it makes:
+//!
+//! - a five wins outright
+//! - an open four can be finished two ways, so it can't be stopped
+//! - a four can be finished one way, so it has to be blocked
+//! - an open three is one move from an open four
+//!
+//! Before searching, the bot tries to win by threat space search, placing
+//! nothing but fours so every reply is forced, until one can't be stopped.
+//!
+//! Tiles are written like on the flat boards, e.g. "h8".
+
+use crate::bot::{Difficulty, SearchBudget};
+use crate::coordinate_methods::Coordinates;
+use crate::gameboard::BoardStates;
+use crate::gameplay::*;
+use crate::grid_board::*;
+use crate::mcts::player_of;
+use crate::search::{best_move_within, total_evaluation};
+use crate::session::{play_session, two_player_seats};
+use crate::solver::opposing_symbol;
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+use std::time::{Duration, Instant};
+
+pub const BOARD_SIZE: usize = 15;
+pub const WIN_LENGTH: usize = 5;
+
+/// What a line of five tiles is worth with 0 to 4 of one side's stones in and none of the other's.
+pub const LINE_SCORES: [i32; WIN_LENGTH] = [0, 1, 8, 64, 512];
+
+/// How many tiles are searched at the top of the search, and below it.
+pub const ROOT_WIDTH: usize = 12;
+pub const SEARCH_WIDTH: usize = 8;
+/// How many fours in a row the threat space search will try.
+pub const THREAT_SEARCH_DEPTH: u32 = 6;
+
+const GOMOKU_USAGE: &str = "expected 'gomoku' or 'gomoku <seconds per bot move>'";
+
+/// What placing on a tile makes along one line, weakest first.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Threat {
+  Nothing,
+  OpenThree,
+  Four,
+  OpenFour,
+  Five,
+}
+
+pub fn search_budget(difficulty: Difficulty) -> SearchBudget {
+  match difficulty {
+    Difficulty::Easy => SearchBudget {
+      max_depth: 1,
+      time_limit: Duration::from_millis(200),
+    },
+    Difficulty::Medium => SearchBudget {
+      max_depth: 3,
+      time_limit: Duration::from_secs(1),
+    },
+    Difficulty::Hard => SearchBudget {
+      max_depth: 8,
+      time_limit: Duration::from_secs(3),
+    },
+  }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct GomokuGame {
+  pub grid_board: GridBoard,
+  pub next_symbol: BoardStates,
+  lines: Vec<Vec<Coordinates>>,
+}
+
+impl GomokuGame {
+  pub fn new() -> Self {
+    let grid_board = GridBoard::new(BOARD_SIZE, BOARD_SIZE, WIN_LENGTH).unwrap();
+
+    GomokuGame {
+      lines: grid_board.lines(),
+      grid_board,
+      next_symbol: BoardStates::X,
+    }
+  }
+
+  /// None while the game can still be played. Only whoever placed last can
+  /// have won, so only the lines through the last stone are checked.
+  pub fn result(&self) -> Option<GameState> {
+    self.grid_board.result()
+  }
+
+  /// Places next_symbol on coords, erroring if it can't go there.
+  pub fn place(&mut self, coords: &Coordinates) -> Result<(), Box<dyn Error>> {
+    if self.result().is_some() {
+      return Err(Box::from("the game is over"));
+    }
+
+    if self.grid_board.get_board_state(coords) != &BoardStates::Empty {
+      return Err(Box::from(format!(
+        "{} is already taken",
+        row_column_notation(coords)
+      )));
+    }
+
+    self.grid_board.place_tile(coords, self.next_symbol);
+    self.next_symbol = opposing_symbol(self.next_symbol);
+
+    Ok(())
+  }
+
+  /// Empty tiles up to two away from a stone, or the middle of an empty board.
+  pub fn nearby_tiles(&self) -> Vec<Coordinates> {
+    let mut nearby = HashSet::new();
+
+    for coords in self.grid_board.all_coordinates() {
+      if self.grid_board.get_board_state(&coords) == &BoardStates::Empty {
+        continue;
+      }
+
+      for direction in LINE_DIRECTIONS {
+        for distance in [-2, -1, 1, 2] {
+          if let Some(next) = self.grid_board.step(&coords, direction, distance) {
+            if self.grid_board.get_board_state(&next) == &BoardStates::Empty {
+              nearby.insert(next);
+            }
+          }
+        }
+      }
+    }
+
+    if self.grid_board.tiles_covered == 0 {
+      nearby.insert((BOARD_SIZE / 2, BOARD_SIZE / 2));
+    }
+
+    let mut nearby: Vec<Coordinates> = nearby.into_iter().collect();
+    nearby.sort();
+
+    nearby
+  }
+
+  /// The tiles worth searching for next_symbol, at most width of them.
+  /// A five is the only tile worth placing on, and blocking the other side's
+  /// five comes next. Otherwise the tiles making or stopping the biggest threats are first.
+  pub fn candidate_tiles(&self, width: usize) -> Vec<Coordinates> {
+    let symbol = self.next_symbol;
+    let opponent = opposing_symbol(symbol);
+
+    if let Some(coords) = winning_tiles(&self.grid_board, symbol).first() {
+      return vec![*coords];
+    }
+
+    let blocks = winning_tiles(&self.grid_board, opponent);
+
+    if !blocks.is_empty() {
+      return blocks;
+    }
+
+    let mut scored: Vec<(Coordinates, i32)> = self
+      .nearby_tiles()
+      .into_iter()
+      .map(|coords| {
+        let score = tile_score(&self.grid_board, &coords, symbol)
+          + tile_score(&self.grid_board, &coords, opponent);
+
+        (coords, score)
+      })
+      .collect();
+
+    scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+
+    scored
+      .into_iter()
+      .take(width)
+      .map(|(coords, _)| coords)
+      .collect()
+  }
+
+  /// Lines only one side has stones in, worth more the closer they are to
+  /// done, for symbol minus the other side.
+  pub fn evaluate(&self, symbol: BoardStates) -> i32 {
+    let opponent = opposing_symbol(symbol);
+
+    total_evaluation(self.lines.iter().map(|line| {
+      let count_of = |state: BoardStates| {
+        line
+          .iter()
+          .filter(|coords| self.grid_board.get_board_state(coords) == &state)
+          .count()
+      };
+
+      match (count_of(symbol), count_of(opponent)) {
+        (count, 0) if count < WIN_LENGTH => LINE_SCORES[count] as i64,
+        (0, count) if count < WIN_LENGTH => -LINE_SCORES[count] as i64,
+        _ => 0,
+      }
+    }))
+  }
+}
+
+impl Default for GomokuGame {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl GridGame for GomokuGame {
+  const EXAMPLE_TILE: &'static str = "h8";
+
+  fn grid_board(&self) -> &GridBoard {
+    &self.grid_board
+  }
+
+  fn next_symbol(&self) -> BoardStates {
+    self.next_symbol
+  }
+
+  fn place(&mut self, coords: &Coordinates) -> Result<(), Box<dyn Error>> {
+    GomokuGame::place(self, coords)
+  }
+
+  fn search_tiles(&self) -> Vec<Coordinates> {
+    self.candidate_tiles(SEARCH_WIDTH)
+  }
+
+  fn evaluate(&self, symbol: BoardStates) -> i32 {
+    GomokuGame::evaluate(self, symbol)
+  }
+}
+
+/// A tile along a line as seen by whoever is placing.
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum Cell {
+  Own,
+  Empty,
+  /// The other side's stone or off the board.
+  Closed,
+}
+
+/// The tiles from four before coords to four after along direction, coords itself counting as placed.
+fn line_segment(
+  grid_board: &GridBoard,
+  coords: &Coordinates,
+  symbol: BoardStates,
+  direction: (isize, isize),
+) -> [Cell; 2 * WIN_LENGTH - 1] {
+  let reach = WIN_LENGTH as isize - 1;
+  let mut segment = [Cell::Closed; 2 * WIN_LENGTH - 1];
+
+  for (index, distance) in (-reach..=reach).enumerate() {
+    segment[index] = match grid_board.step(coords, direction, distance) {
+      _ if distance == 0 => Cell::Own,
+      Some(next) if grid_board.get_board_state(&next) == &symbol => Cell::Own,
+      Some(next) if grid_board.get_board_state(&next) == &BoardStates::Empty => Cell::Empty,
+      _ => Cell::Closed,
+    };
+  }
+
+  segment
+}
+
+/// The runs of five through the middle of the segment with nothing closed in them.
+fn open_windows(segment: &[Cell]) -> impl Iterator<Item = (usize, &[Cell])> {
+  (0..WIN_LENGTH)
+    .map(move |start| (start, &segment[start..start + WIN_LENGTH]))
+    .filter(|(_, window)| !window.contains(&Cell::Closed))
+}
+
+/// The empty places in the segment that would finish a five.
+fn completing_cells(segment: &[Cell]) -> HashSet<usize> {
+  open_windows(segment)
+    .filter(|(_, window)| {
+      window.iter().filter(|cell| **cell == Cell::Own).count() == WIN_LENGTH - 1
+    })
+    .filter_map(|(start, window)| {
+      window
+        .iter()
+        .position(|cell| *cell == Cell::Empty)
+        .map(|offset| start + offset)
+    })
+    .collect()
+}
+
+fn segment_threat(segment: &[Cell]) -> Threat {
+  if open_windows(segment).any(|(_, window)| window.iter().all(|cell| *cell == Cell::Own)) {
+    return Threat::Five;
+  }
+
+  match completing_cells(segment).len() {
+    0 => (),
+    1 => return Threat::Four,
+    _ => return Threat::OpenFour,
+  }
+
+  // An open three needs just one more stone to make an open four.
+  let makes_open_four = (0..segment.len()).any(|index| {
+    if segment[index] != Cell::Empty {
+      return false;
+    }
+
+    let mut next_segment = segment.to_vec();
+    next_segment[index] = Cell::Own;
+
+    completing_cells(&next_segment).len() >= 2
+  });
+
+  if makes_open_four {
+    Threat::OpenThree
+  } else {
+    Threat::Nothing
+  }
+}
+
+/// What placing symbol on coords would make along direction.
+pub fn threat(
+  grid_board: &GridBoard,
+  coords: &Coordinates,
+  symbol: BoardStates,
+  direction: (isize, isize),
+) -> Threat {
+  segment_threat(&line_segment(grid_board, coords, symbol, direction))
+}
+
+/// The biggest threat placing symbol on coords would make along any line.
+pub fn strongest_threat(
+  grid_board: &GridBoard,
+  coords: &Coordinates,
+  symbol: BoardStates,
+) -> Threat {
+  LINE_DIRECTIONS
+    .iter()
+    .map(|direction| threat(grid_board, coords, symbol, *direction))
+    .max()
+    .unwrap_or(Threat::Nothing)
+}
+
+/// How good placing symbol on coords would be for symbol, going by the threats
+/// it makes, then by how many of symbol's stones are in the lines through it.
+pub fn tile_score(grid_board: &GridBoard, coords: &Coordinates, symbol: BoardStates) -> i32 {
+  LINE_DIRECTIONS
+    .iter()
+    .map(|direction| {
+      let segment = line_segment(grid_board, coords, symbol, *direction);
+
+      match segment_threat(&segment) {
+        Threat::Five => 100_000,
+        Threat::OpenFour => 10_000,
+        Threat::Four => 1_000,
+        Threat::OpenThree => 800,
+        Threat::Nothing => open_windows(&segment)
+          .map(|(_, window)| window.iter().filter(|cell| **cell == Cell::Own).count() as i32)
+          .sum(),
+      }
+    })
+    .sum()
+}
+
+/// Every empty tile symbol could make five on.
+pub fn winning_tiles(grid_board: &GridBoard, symbol: BoardStates) -> Vec<Coordinates> {
+  let mut tiles: Vec<Coordinates> = grid_board
+    .all_coordinates()
+    .into_iter()
+    .filter(|coords| grid_board.get_board_state(coords) == &symbol)
+    .flat_map(|coords| winning_tiles_through(grid_board, &coords, symbol))
+    .collect();
+
+  tiles.sort();
+  tiles.dedup();
+
+  tiles
+}
+
+/// The empty tiles symbol could make five on along the lines through coords.
+/// Only these can change when a stone goes on coords, so after a move there's
+/// no need to look at the whole board.
+pub fn winning_tiles_through(
+  grid_board: &GridBoard,
+  coords: &Coordinates,
+  symbol: BoardStates,
+) -> Vec<Coordinates> {
+  let reach = WIN_LENGTH as isize - 1;
+  let mut tiles = vec![];
+
+  for direction in LINE_DIRECTIONS {
+    for distance in -reach..=reach {
+      if let Some(next) = grid_board.step(coords, direction, distance) {
+        if grid_board.get_board_state(&next) == &BoardStates::Empty
+          && threat(grid_board, &next, symbol, direction) == Threat::Five
+          && !tiles.contains(&next)
+        {
+          tiles.push(next);
+        }
+      }
+    }
+  }
+
+  tiles.sort();
+
+  tiles
+}
+
+/// A first move for next_symbol that wins by placing nothing but fours, so the
+/// other side only ever has one tile to block, until they have two. None if
+/// there isn't one within depth fours or by the deadline, or if the other side
+/// has a four to deal with.
+pub fn threat_space_search(
+  gomoku_game: &GomokuGame,
+  depth: u32,
+  deadline: Instant,
+) -> Option<Coordinates> {
+  let symbol = gomoku_game.next_symbol;
+  let opponent = opposing_symbol(symbol);
+
+  if let Some(coords) = winning_tiles(&gomoku_game.grid_board, symbol).first() {
+    return Some(*coords);
+  }
+
+  if !winning_tiles(&gomoku_game.grid_board, opponent).is_empty() {
+    return None;
+  }
+
+  four_sequence(gomoku_game, depth, deadline)
+}
+
+/// threat_space_search once neither side has a five to make. Every four gets
+/// blocked, so after that only the last two stones can have changed that.
+fn four_sequence(gomoku_game: &GomokuGame, depth: u32, deadline: Instant) -> Option<Coordinates> {
+  let symbol = gomoku_game.next_symbol;
+  let opponent = opposing_symbol(symbol);
+
+  if depth == 0 || Instant::now() >= deadline {
+    return None;
+  }
+
+  gomoku_game.nearby_tiles().into_iter().find(|coords| {
+    if strongest_threat(&gomoku_game.grid_board, coords, symbol) < Threat::Four {
+      return false;
+    }
+
+    let mut next_game = gomoku_game.clone();
+
+    if next_game.place(coords).is_err() {
+      return false;
+    }
+
+    match winning_tiles_through(&next_game.grid_board, coords, symbol).as_slice() {
+      [] => false,
+      [block] => {
+        next_game.place(block).is_ok()
+          && winning_tiles_through(&next_game.grid_board, block, opponent).is_empty()
+          && four_sequence(&next_game, depth - 1, deadline).is_some()
+      }
+      _ => true,
+    }
+  })
+}
+
+/// The best tile for next_symbol. A win by threat space search is taken if it
+/// finds one within half the time, otherwise the best of the root candidates
+/// within budget, see search::best_move_within.
+pub fn best_move(gomoku_game: &GomokuGame, budget: SearchBudget) -> Option<Coordinates> {
+  let candidates = gomoku_game.candidate_tiles(ROOT_WIDTH);
+
+  if candidates.len() <= 1 {
+    return candidates.first().copied();
+  }
+
+  let threat_deadline = Instant::now() + budget.time_limit / 2;
+
+  if let Some(coords) = threat_space_search(gomoku_game, THREAT_SEARCH_DEPTH, threat_deadline) {
+    return Some(coords);
+  }
+
+  best_move_within(gomoku_game, &candidates, budget)
+}
+
+pub fn gomoku(gameconfig: GameConfig, gamemode_arguments: &[String]) -> Result<(), Box<dyn Error>> {
+  let mut budget = search_budget(gameconfig.bot.difficulty);
+
+  match gamemode_arguments {
+    [] => (),
+    [seconds] => {
+      let seconds = seconds
+        .parse::<f64>()
+        .ok()
+        .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+        .ok_or(GOMOKU_USAGE)?;
+
+      budget.time_limit = Duration::from_secs_f64(seconds);
+    }
+    _ => return Err(Box::from(GOMOKU_USAGE)),
+  }
+
+  gomoku_session(
+    gameconfig,
+    GomokuGame::new(),
+    budget,
+    io::stdin().lock(),
+    io::stdout(),
+  )?;
+
+  Ok(())
+}
+
+pub fn gomoku_session<R: BufRead, W: Write>(
+  gameconfig: GameConfig,
+  gomoku_game: GomokuGame,
+  budget: SearchBudget,
+  input: R,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
+  writeln!(
+    output,
+    "{} {:?} | Bot {:?} | {WIN_LENGTH} in a row wins",
+    gameconfig.player_name, gameconfig.player_symbol, gameconfig.bot.bot_symbol
+  )?;
+
+  play_session(
+    gomoku_game,
+    &two_player_seats(&gameconfig.player_name, player_of(gameconfig.player_symbol)),
+    |gomoku_game| best_move(gomoku_game, budget),
+    input,
+    output,
+  )
+}
@@ -0,0 +1,225 @@
+//! Gravity mode, started with `gravity [width height [win length]]`.
+//!
+//! A piece dropped in a column falls to the lowest empty tile in it. With the
+//! default 7x6 board and four in a row that's Connect Four, `gravity 3 3` is
+//! tic-tac-toe with gravity.
+//!
+//! Moves are just the column number.
+
+use crate::coordinate_methods::Coordinates;
+use crate::gameboard::BoardStates;
+use crate::gameplay::*;
+use crate::grid_board::*;
+use crate::mcts::{player_of, symbol_of, Game, Outcome};
+use crate::search::{best_move, search_depth, Searchable};
+use crate::session::{play_session, two_player_seats, SessionGame};
+use crate::solver::opposing_symbol;
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+pub const DEFAULT_WIDTH: usize = 7;
+pub const DEFAULT_HEIGHT: usize = 6;
+pub const DEFAULT_WIN_LENGTH: usize = 4;
+
+const GRAVITY_USAGE: &str =
+  "expected 'gravity', 'gravity <width> <height>' or 'gravity <width> <height> <win length>'";
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct GravityGame {
+  pub grid_board: GridBoard,
+  pub next_symbol: BoardStates,
+}
+
+impl GravityGame {
+  pub fn new(width: usize, height: usize, win_length: usize) -> Result<Self, Box<dyn Error>> {
+    Ok(GravityGame {
+      grid_board: GridBoard::new(width, height, win_length)?,
+      next_symbol: BoardStates::X,
+    })
+  }
+
+  /// The tile a piece dropped in column lands on, None when it's full.
+  pub fn landing_tile(&self, column: usize) -> Option<Coordinates> {
+    (0..self.grid_board.height)
+      .rev()
+      .map(|row| (row, column))
+      .find(|coords| self.grid_board.get_board_state(coords) == &BoardStates::Empty)
+  }
+
+  /// None while the game can still be played.
+  pub fn result(&self) -> Option<GameState> {
+    self.grid_board.result()
+  }
+
+  /// Open columns from the middle outwards, which is also the order worth searching them in.
+  pub fn legal_columns(&self) -> Vec<usize> {
+    let middle = (self.grid_board.width - 1) as f32 / 2.0;
+    let mut columns: Vec<usize> = (0..self.grid_board.width)
+      .filter(|column| self.landing_tile(*column).is_some())
+      .collect();
+
+    columns.sort_by(|left, right| {
+      (*left as f32 - middle)
+        .abs()
+        .total_cmp(&(*right as f32 - middle).abs())
+    });
+
+    columns
+  }
+
+  /// Drops next_symbol in column, returning where it landed.
+  pub fn drop_piece(&mut self, column: usize) -> Result<Coordinates, Box<dyn Error>> {
+    if self.result().is_some() {
+      return Err(Box::from("the game is over"));
+    }
+
+    if column >= self.grid_board.width {
+      return Err(Box::from(format!(
+        "there are only {} columns",
+        self.grid_board.width
+      )));
+    }
+
+    let coords = self
+      .landing_tile(column)
+      .ok_or_else(|| format!("column {} is full", column + 1))?;
+
+    self.grid_board.place_tile(&coords, self.next_symbol);
+    self.next_symbol = opposing_symbol(self.next_symbol);
+
+    Ok(coords)
+  }
+}
+
+/// "4" into column index 3.
+pub fn parse_column(player_input: &str) -> Result<usize, Box<dyn Error>> {
+  match player_input.trim().parse::<usize>() {
+    Ok(column) if column != 0 => Ok(column - 1),
+    _ => Err(Box::from("incorrect input, give a column number")),
+  }
+}
+
+/// Moves are the column dropped in.
+impl Game for GravityGame {
+  type Move = usize;
+
+  fn current_player(&self) -> usize {
+    player_of(self.next_symbol)
+  }
+
+  fn legal_moves(&self) -> Vec<usize> {
+    if self.result().is_some() {
+      return vec![];
+    }
+
+    self.legal_columns()
+  }
+
+  fn apply(&mut self, column: &usize) {
+    self
+      .drop_piece(*column)
+      .expect("only legal columns are played");
+  }
+
+  fn outcome(&self) -> Option<Outcome> {
+    self.result().map(Outcome::from)
+  }
+}
+
+impl Searchable for GravityGame {
+  const SEARCH_DEPTHS: [u32; 3] = [1, 3, 6];
+
+  fn evaluate(&self, player: usize) -> i32 {
+    evaluate(&self.grid_board, symbol_of(player))
+  }
+}
+
+impl SessionGame for GravityGame {
+  type Result = GameState;
+
+  fn render(&self) -> String {
+    render_gravity(self)
+  }
+
+  fn session_result(&self) -> Option<GameState> {
+    self.result()
+  }
+
+  fn prompt(&self) -> String {
+    format!("select a column from 1 to {}", self.grid_board.width)
+  }
+
+  fn play_input(&mut self, player_input: &str) -> Result<(), Box<dyn Error>> {
+    self.drop_piece(parse_column(player_input)?)?;
+
+    Ok(())
+  }
+
+  fn describe_move(&self, column: &usize) -> String {
+    format!("dropped in column {}", column + 1)
+  }
+}
+
+/// The grid with an arrow under the column last dropped in.
+pub fn render_gravity(gravity_game: &GravityGame) -> String {
+  let mut rendered = render_grid(&gravity_game.grid_board);
+
+  if let Some((_, column)) = gravity_game.grid_board.last_modified_tile {
+    rendered += &format!("   {}^\n", " ".repeat(column * 3 + 1));
+  }
+
+  rendered
+}
+
+pub fn gravity(
+  gameconfig: GameConfig,
+  gamemode_arguments: &[String],
+) -> Result<(), Box<dyn Error>> {
+  let sizes = gamemode_arguments
+    .iter()
+    .map(|size| {
+      size
+        .parse::<usize>()
+        .map_err(|_| format!("gravity sizes have to be whole numbers, got '{size}'"))
+    })
+    .collect::<Result<Vec<usize>, String>>()?;
+
+  let gravity_game = match sizes.as_slice() {
+    [] => GravityGame::new(DEFAULT_WIDTH, DEFAULT_HEIGHT, DEFAULT_WIN_LENGTH)?,
+    [width, height] => {
+      GravityGame::new(*width, *height, DEFAULT_WIN_LENGTH.min(*width.max(height)))?
+    }
+    [width, height, win_length] => GravityGame::new(*width, *height, *win_length)?,
+    _ => return Err(Box::from(GRAVITY_USAGE)),
+  };
+
+  gravity_session(gameconfig, gravity_game, io::stdin().lock(), io::stdout())?;
+
+  Ok(())
+}
+
+pub fn gravity_session<R: BufRead, W: Write>(
+  gameconfig: GameConfig,
+  gravity_game: GravityGame,
+  input: R,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
+  let depth = search_depth::<GravityGame>(gameconfig.bot.difficulty);
+
+  writeln!(
+    output,
+    "{} {:?} | Bot {:?} | {} in a row wins",
+    gameconfig.player_name,
+    gameconfig.player_symbol,
+    gameconfig.bot.bot_symbol,
+    gravity_game.grid_board.win_length
+  )?;
+
+  play_session(
+    gravity_game,
+    &two_player_seats(&gameconfig.player_name, player_of(gameconfig.player_symbol)),
+    |gravity_game| best_move(gravity_game, depth),
+    input,
+    output,
+  )
+}
@@ -0,0 +1,443 @@
+//! A board of any size for the variants that outgrow the 3x3 BoardConfig,
+//! with however many in a row it takes to win.
+//!
+//! Rows are lettered from a and columns numbered from 1 like on BoardConfig,
+//! so "c4" is the third row down and fourth column across.
+//!
+//! The board can also be a torus, where stepping off one edge comes back on
+//! at the opposite one and lines wrap around with it.
+
+use crate::coordinate_methods::Coordinates;
+use crate::gameboard::BoardStates;
+use crate::gameplay::{row_column_notation, GameState};
+use crate::mcts::{player_of, symbol_of, Game, Outcome};
+use crate::search::{line_score, total_evaluation, Searchable};
+use crate::session::SessionGame;
+use crate::solver::opposing_symbol;
+use std::collections::HashSet;
+use std::error::Error;
+
+/// Rows are lettered, so there can't be more than the alphabet.
+pub const MAX_GRID_SIZE: usize = 26;
+
+/// Across, down and both diagonals. The opposite directions are walked by negating these.
+pub const LINE_DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+/// How the edges of the board join up.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum Topology {
+  #[default]
+  Flat,
+  /// The left edge joins the right and the top joins the bottom.
+  Toroidal,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct GridBoard {
+  pub width: usize,
+  pub height: usize,
+  pub win_length: usize,
+  /// Row by row from the top left.
+  pub tiles: Vec<BoardStates>,
+  /// Blocked tiles count as covered.
+  pub tiles_covered: usize,
+  pub last_modified_tile: Option<Coordinates>,
+  pub topology: Topology,
+}
+
+impl GridBoard {
+  pub fn new(width: usize, height: usize, win_length: usize) -> Result<Self, Box<dyn Error>> {
+    if !(1..=MAX_GRID_SIZE).contains(&width) || !(1..=MAX_GRID_SIZE).contains(&height) {
+      return Err(Box::from(format!(
+        "boards have to be 1 to {MAX_GRID_SIZE} tiles across and down, got {width}x{height}"
+      )));
+    }
+
+    if win_length < 2 || win_length > width.max(height) {
+      return Err(Box::from(format!(
+        "{win_length} in a row can't be made on a {width}x{height} board"
+      )));
+    }
+
+    Ok(GridBoard {
+      width,
+      height,
+      win_length,
+      tiles: vec![BoardStates::Empty; width * height],
+      tiles_covered: 0,
+      last_modified_tile: None,
+      topology: Topology::Flat,
+    })
+  }
+
+  /// A board whose lines wrap around the edges. A line can't be longer than
+  /// the board is across or down, or it would wrap back onto itself.
+  pub fn toroidal(width: usize, height: usize, win_length: usize) -> Result<Self, Box<dyn Error>> {
+    let grid_board = GridBoard::new(width, height, win_length)?;
+
+    if win_length > width.min(height) {
+      return Err(Box::from(format!(
+        "{win_length} in a row would wrap onto itself on a {width}x{height} torus"
+      )));
+    }
+
+    Ok(GridBoard {
+      topology: Topology::Toroidal,
+      ..grid_board
+    })
+  }
+
+  pub fn is_in_bounds(&self, coords: &Coordinates) -> bool {
+    coords.0 < self.height && coords.1 < self.width
+  }
+
+  pub fn get_board_state(&self, coords: &Coordinates) -> &BoardStates {
+    &self.tiles[coords.0 * self.width + coords.1]
+  }
+
+  /// Unlike BoardConfig this keeps tiles_covered up to date itself.
+  pub fn place_tile(&mut self, coords: &Coordinates, changed_state: BoardStates) {
+    let tile = &mut self.tiles[coords.0 * self.width + coords.1];
+
+    match (
+      *tile == BoardStates::Empty,
+      changed_state == BoardStates::Empty,
+    ) {
+      (true, false) => self.tiles_covered += 1,
+      (false, true) => self.tiles_covered -= 1,
+      _ => (),
+    }
+
+    *tile = changed_state;
+    self.last_modified_tile = Some(*coords);
+  }
+
+  pub fn all_tiles_covered(&self) -> bool {
+    self.tiles_covered == self.tiles.len()
+  }
+
+  /// None while the game can still be played. Only the last placed tile is
+  /// checked for a win, so it's for games placing a piece at a time.
+  pub fn result(&self) -> Option<GameState> {
+    let last_placed = self
+      .last_modified_tile
+      .map(|coords| *self.get_board_state(&coords));
+
+    match last_placed {
+      Some(BoardStates::X) if self.check_if_win() => Some(GameState::XWon),
+      Some(BoardStates::O) if self.check_if_win() => Some(GameState::OWon),
+      _ if self.all_tiles_covered() => Some(GameState::Draw),
+      _ => None,
+    }
+  }
+
+  pub fn all_coordinates(&self) -> Vec<Coordinates> {
+    let width = self.width;
+
+    (0..self.height)
+      .flat_map(|row| (0..width).map(move |column| (row, column)))
+      .collect()
+  }
+
+  pub fn empty_tiles(&self) -> Vec<Coordinates> {
+    self
+      .all_coordinates()
+      .into_iter()
+      .filter(|coords| self.get_board_state(coords) == &BoardStates::Empty)
+      .collect()
+  }
+
+  /// The tile distance steps away in direction, None when that's off a flat board.
+  pub fn step(
+    &self,
+    coords: &Coordinates,
+    direction: (isize, isize),
+    distance: isize,
+  ) -> Option<Coordinates> {
+    let row = coords.0 as isize + direction.0 * distance;
+    let column = coords.1 as isize + direction.1 * distance;
+
+    if self.topology == Topology::Toroidal {
+      return Some((
+        row.rem_euclid(self.height as isize) as usize,
+        column.rem_euclid(self.width as isize) as usize,
+      ));
+    }
+
+    if row < 0 || column < 0 {
+      return None;
+    }
+
+    Some((row as usize, column as usize)).filter(|coords| self.is_in_bounds(coords))
+  }
+
+  /// The up to 8 tiles around coords, wrapping around on a torus.
+  pub fn neighbors(&self, coords: &Coordinates) -> Vec<Coordinates> {
+    let mut neighbors = vec![];
+
+    for direction in LINE_DIRECTIONS {
+      for distance in [-1, 1] {
+        if let Some(next) = self.step(coords, direction, distance) {
+          if next != *coords && !neighbors.contains(&next) {
+            neighbors.push(next);
+          }
+        }
+      }
+    }
+
+    neighbors
+  }
+
+  /// How many of the symbol on coords are in an unbroken line through it in direction.
+  pub fn line_length(&self, coords: &Coordinates, direction: (isize, isize)) -> usize {
+    let symbol = self.get_board_state(coords);
+
+    if !symbol.is_symbol() {
+      return 0;
+    }
+
+    let count_towards = |sign: isize| {
+      (1..self.win_length as isize)
+        .map_while(|distance| self.step(coords, direction, sign * distance))
+        .take_while(|next| self.get_board_state(next) == symbol)
+        .count()
+    };
+
+    1 + count_towards(1) + count_towards(-1)
+  }
+
+  /// Returns true if the last placed tile completed a line of win_length.
+  pub fn check_if_win(&self) -> bool {
+    let Some(last_modified_tile) = self.last_modified_tile else {
+      return false;
+    };
+
+    LINE_DIRECTIONS
+      .iter()
+      .any(|direction| self.line_length(&last_modified_tile, *direction) >= self.win_length)
+  }
+
+  /// Every run of win_length tiles a line could be made on, so none through blocked tiles.
+  pub fn lines(&self) -> Vec<Vec<Coordinates>> {
+    let mut lines: Vec<Vec<Coordinates>> = self
+      .all_coordinates()
+      .into_iter()
+      .flat_map(|start| {
+        LINE_DIRECTIONS.iter().filter_map(move |direction| {
+          (0..self.win_length as isize)
+            .map(|distance| self.step(&start, *direction, distance))
+            .collect::<Option<Vec<Coordinates>>>()
+        })
+      })
+      .filter(|line| {
+        line
+          .iter()
+          .all(|coords| self.get_board_state(coords) != &BoardStates::Blocked)
+      })
+      .collect();
+
+    // A line all the way around a torus is found again from each of its tiles.
+    if self.topology == Topology::Toroidal {
+      let mut seen = HashSet::new();
+
+      lines.retain(|line| {
+        let mut tiles = line.clone();
+        tiles.sort();
+
+        seen.insert(tiles)
+      });
+    }
+
+    lines
+  }
+
+  /// Searches the whole board for a completed line, unlike check_if_win
+  /// which only looks around the last placed tile.
+  pub fn winning_line(&self) -> Option<Vec<Coordinates>> {
+    self.lines().into_iter().find(|line| {
+      self.get_board_state(&line[0]).is_symbol()
+        && line
+          .iter()
+          .all(|coords| self.get_board_state(coords) == self.get_board_state(&line[0]))
+    })
+  }
+
+  pub fn winner(&self) -> Option<BoardStates> {
+    self
+      .winning_line()
+      .map(|line| *self.get_board_state(&line[0]))
+  }
+}
+
+/// Lines only one side has pieces in, worth more the closer they are to done,
+/// for symbol minus the other side.
+pub fn evaluate(grid_board: &GridBoard, symbol: BoardStates) -> i32 {
+  let opponent = opposing_symbol(symbol);
+
+  total_evaluation(grid_board.lines().iter().map(|line| {
+    let count_of = |state: BoardStates| {
+      line
+        .iter()
+        .filter(|coords| grid_board.get_board_state(coords) == &state)
+        .count()
+    };
+
+    match (count_of(symbol), count_of(opponent)) {
+      (0, 0) => 0,
+      (count, 0) => line_score(count),
+      (0, count) => -line_score(count),
+      _ => 0,
+    }
+  }))
+}
+
+/// "c4" or "C4" into (2, 3), checked against the board's size.
+/// row_column_notation turns it back.
+pub fn parse_grid_tile(
+  player_input: &str,
+  grid_board: &GridBoard,
+) -> Result<Coordinates, Box<dyn Error>> {
+  let player_input = player_input.trim().to_lowercase();
+  let mut characters = player_input.chars();
+
+  let row = match characters.next() {
+    Some(letter @ 'a'..='z') => letter as usize - 'a' as usize,
+    _ => return Err(Box::from("incorrect input")),
+  };
+
+  let column = match characters.as_str().parse::<usize>() {
+    Ok(column) if column != 0 => column - 1,
+    _ => return Err(Box::from("incorrect input")),
+  };
+
+  if !grid_board.is_in_bounds(&(row, column)) {
+    return Err(Box::from(format!(
+      "{player_input} is off the {}x{} board",
+      grid_board.width, grid_board.height
+    )));
+  }
+
+  Ok((row, column))
+}
+
+/// ```text
+///     1  2  3  4
+///  a  .  .  .  .
+///  b  .  X  .  .
+///  c  . [O] X  .
+/// ```
+pub fn render_grid(grid_board: &GridBoard) -> String {
+  let header: String = (1..=grid_board.width)
+    .map(|column| format!("{column:^3}"))
+    .collect();
+
+  let mut rendered = format!("   {}\n", header.trim_end());
+
+  for row in 0..grid_board.height {
+    let tiles: String = (0..grid_board.width)
+      .map(|column| {
+        let symbol = match grid_board.get_board_state(&(row, column)) {
+          BoardStates::X => 'X',
+          BoardStates::O => 'O',
+          BoardStates::Empty => '.',
+          BoardStates::Blocked => '#',
+        };
+
+        if grid_board.last_modified_tile == Some((row, column)) {
+          format!("[{symbol}]")
+        } else {
+          format!(" {symbol} ")
+        }
+      })
+      .collect();
+
+    rendered += &format!(" {} {}\n", (b'a' + row as u8) as char, tiles.trim_end());
+  }
+
+  rendered
+}
+
+/// X and O taking turns placing anywhere free on a GridBoard. Implementing
+/// this is enough for a game to be searched and played at the prompt.
+pub trait GridGame: Clone {
+  /// How many moves ahead the search looks on Easy, Medium and Hard.
+  const SEARCH_DEPTHS: [u32; 3] = [1, 3, 5];
+  /// The tile the prompt gives as an example.
+  const EXAMPLE_TILE: &'static str = "c4";
+
+  fn grid_board(&self) -> &GridBoard;
+
+  fn next_symbol(&self) -> BoardStates;
+
+  /// Places next_symbol on coords, erroring if it can't go there.
+  fn place(&mut self, coords: &Coordinates) -> Result<(), Box<dyn Error>>;
+
+  /// The tiles worth searching, best looking first.
+  fn search_tiles(&self) -> Vec<Coordinates>;
+
+  fn evaluate(&self, symbol: BoardStates) -> i32 {
+    evaluate(self.grid_board(), symbol)
+  }
+}
+
+impl<G: GridGame> Game for G {
+  type Move = Coordinates;
+
+  fn current_player(&self) -> usize {
+    player_of(self.next_symbol())
+  }
+
+  fn legal_moves(&self) -> Vec<Coordinates> {
+    if self.grid_board().result().is_some() {
+      return vec![];
+    }
+
+    self.grid_board().empty_tiles()
+  }
+
+  fn apply(&mut self, coords: &Coordinates) {
+    self.place(coords).expect("only legal tiles are placed on");
+  }
+
+  fn outcome(&self) -> Option<Outcome> {
+    self.grid_board().result().map(Outcome::from)
+  }
+}
+
+impl<G: GridGame> Searchable for G {
+  const SEARCH_DEPTHS: [u32; 3] = <G as GridGame>::SEARCH_DEPTHS;
+
+  fn evaluate(&self, player: usize) -> i32 {
+    GridGame::evaluate(self, symbol_of(player))
+  }
+
+  fn search_moves(&self) -> Vec<Coordinates> {
+    self.search_tiles()
+  }
+}
+
+impl<G: GridGame> SessionGame for G {
+  type Result = GameState;
+
+  fn render(&self) -> String {
+    render_grid(self.grid_board())
+  }
+
+  fn session_result(&self) -> Option<GameState> {
+    self.grid_board().result()
+  }
+
+  fn prompt(&self) -> String {
+    format!("select a tile, e.g. '{}'", G::EXAMPLE_TILE)
+  }
+
+  fn play_input(&mut self, player_input: &str) -> Result<(), Box<dyn Error>> {
+    let coords = parse_grid_tile(player_input, self.grid_board())?;
+
+    self.place(&coords)
+  }
+
+  fn describe_move(&self, coords: &Coordinates) -> String {
+    format!("placed on {}", row_column_notation(coords))
+  }
+}
@@ -0,0 +1,375 @@
+//! A small HTTP server for the bot, started with `serve [address]`.
+//!
+//! ```text
+//! POST /move          {"position": "startpos", "moves": ["b2"]} -> {"move": "a1"}
+//! POST /analyze       {"position": "x........"}                -> {"scores": {"b2": 0, ...}}
+//! POST /games         {"player_symbol": "X"}                   -> game
+//! GET  /games/<id>                                             -> game
+//! POST /games/<id>/move  {"move": "b2"}                        -> game
+//! ```
+//!
+//! Positions and moves use the same notation as the 'engine' gamemode.
+//! A game looks like `{"id": 1, "position": "x...o....", "player_symbol": "X",
+//! "bot_symbol": "O", "bot_move": "b2", "result": null}`, where result becomes
+//! "XWon", "OWon" or "Draw" once the game is over.
+//! Only the newest MAX_GAMES games are kept.
+
+use crate::coordinate_methods::*;
+use crate::engine_protocol::{parse_engine_move, EngineSession};
+use crate::gameboard::*;
+use crate::gameplay::*;
+use crate::solver;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+const MAX_BODY_LENGTH: usize = 64 * 1024;
+/// Games kept at once, the oldest is dropped to make room for a new one.
+pub const MAX_GAMES: usize = 1000;
+/// How long a client can leave the server waiting mid request before it gives up on them.
+pub const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(PartialEq, Debug)]
+pub struct ApiState {
+  pub games: HashMap<u64, GameConfig>,
+  pub next_game_id: u64,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct ApiResponse {
+  pub status: u16,
+  pub body: Value,
+}
+
+impl ApiState {
+  pub fn new() -> Self {
+    ApiState {
+      games: HashMap::new(),
+      next_game_id: 1,
+    }
+  }
+}
+
+impl ApiResponse {
+  fn ok(body: Value) -> Self {
+    ApiResponse { status: 200, body }
+  }
+
+  fn error(status: u16, error: impl ToString) -> Self {
+    ApiResponse {
+      status,
+      body: json!({ "error": error.to_string() }),
+    }
+  }
+}
+
+/// Handles requests one at a time until the server is stopped.
+pub fn serve(address: &str) -> Result<(), Box<dyn Error>> {
+  let listener = TcpListener::bind(address)?;
+  let mut api_state = ApiState::new();
+
+  println!("Listening on http://{}", listener.local_addr()?);
+
+  for stream in listener.incoming() {
+    if let Err(error) = stream
+      .map_err(Box::from)
+      .and_then(|stream| handle_connection(&mut api_state, stream))
+    {
+      eprintln!("Failed to handle a request: '{error}'");
+    }
+  }
+
+  Ok(())
+}
+
+/// Reads a single request from the stream and writes back the response.
+pub fn handle_connection(
+  api_state: &mut ApiState,
+  mut stream: TcpStream,
+) -> Result<(), Box<dyn Error>> {
+  stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+  let mut reader = BufReader::new(&stream);
+  let mut request_line = String::new();
+  reader.read_line(&mut request_line)?;
+
+  // None if the client sent one that isn't a number.
+  let mut content_length = Some(0);
+
+  loop {
+    let mut header = String::new();
+
+    if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+      break;
+    }
+
+    if let Some((name, value)) = header.split_once(':') {
+      if name.trim().eq_ignore_ascii_case("content-length") {
+        content_length = value.trim().parse().ok();
+      }
+    }
+  }
+
+  let response = match content_length {
+    None => ApiResponse::error(400, "Content-Length has to be a whole number"),
+    Some(content_length) if content_length > MAX_BODY_LENGTH => {
+      ApiResponse::error(413, "request body is too large")
+    }
+    Some(content_length) => {
+      let mut body = vec![0; content_length];
+      reader.read_exact(&mut body)?;
+
+      let mut request_line = request_line.split_whitespace();
+      let method = request_line.next().unwrap_or_default();
+      let path = request_line.next().unwrap_or_default();
+
+      let body = String::from_utf8_lossy(&body);
+
+      // A bug in one request shouldn't stop the server for everyone else.
+      panic::catch_unwind(AssertUnwindSafe(|| {
+        handle_request(api_state, method, path, &body)
+      }))
+      .unwrap_or_else(|_| ApiResponse::error(500, "the server failed to handle the request"))
+    }
+  };
+
+  let body = response.body.to_string();
+
+  write!(
+    stream,
+    "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+    response.status,
+    reason_phrase(response.status),
+    body.len(),
+    body
+  )?;
+
+  stream.flush()?;
+
+  Ok(())
+}
+
+pub fn handle_request(
+  api_state: &mut ApiState,
+  method: &str,
+  path: &str,
+  body: &str,
+) -> ApiResponse {
+  let request_body = if body.trim().is_empty() {
+    Value::Object(Map::new())
+  } else {
+    match serde_json::from_str::<Value>(body) {
+      Ok(request_body) => request_body,
+      Err(error) => return ApiResponse::error(400, format!("invalid json: {error}")),
+    }
+  };
+
+  let path_segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+  let response = match (method, path_segments.as_slice()) {
+    ("POST", ["move"]) => bot_move(&request_body),
+    ("POST", ["analyze"]) => analyze(&request_body),
+    ("POST", ["games"]) => new_game(api_state, &request_body),
+    ("GET", ["games", id]) => with_game(api_state, id, |id, gameconfig| {
+      Ok(game_to_json(id, gameconfig, None))
+    }),
+    ("POST", ["games", id, "move"]) => with_game(api_state, id, |id, gameconfig| {
+      play_game_move(id, gameconfig, &request_body)
+    }),
+    (_, ["move"] | ["analyze"] | ["games"] | ["games", _] | ["games", _, "move"]) => {
+      return ApiResponse::error(405, format!("{method} isn't allowed on {path}"))
+    }
+    _ => return ApiResponse::error(404, format!("nothing at {path}")),
+  };
+
+  response.unwrap_or_else(|error| error)
+}
+
+fn bot_move(request_body: &Value) -> Result<ApiResponse, ApiResponse> {
+  let session = session_from_request(request_body)?;
+
+  let best_move = session
+    .best_move()
+    .map(|coords| row_column_notation(&coords));
+
+  Ok(ApiResponse::ok(json!({ "move": best_move })))
+}
+
+fn analyze(request_body: &Value) -> Result<ApiResponse, ApiResponse> {
+  let gameboard = session_from_request(request_body)?.current_board();
+
+  let scores: Map<String, Value> = solver::score_moves(&gameboard)
+    .into_iter()
+    .map(|(coords, score)| (row_column_notation(&coords), json!(score)))
+    .collect();
+
+  Ok(ApiResponse::ok(json!({
+    "next_symbol": format!("{:?}", gameboard.next_symbol_to_place()),
+    "scores": scores,
+  })))
+}
+
+fn new_game(api_state: &mut ApiState, request_body: &Value) -> Result<ApiResponse, ApiResponse> {
+  let mut gameconfig = GameConfig::new();
+
+  match request_body.get("player_symbol").and_then(Value::as_str) {
+    Some("X") | Some("x") => {
+      gameconfig.player_symbol = BoardStates::X;
+      gameconfig.bot.bot_symbol = BoardStates::O;
+    }
+    Some("O") | Some("o") => {
+      gameconfig.player_symbol = BoardStates::O;
+      gameconfig.bot.bot_symbol = BoardStates::X;
+    }
+    Some(symbol) => {
+      return Err(ApiResponse::error(
+        400,
+        format!("player_symbol must be X or O, not '{symbol}'"),
+      ))
+    }
+    None => (),
+  }
+
+  let bot_move = if gameconfig.bot.bot_symbol == BoardStates::X {
+    Some(bot_place_tile(&mut gameconfig))
+  } else {
+    None
+  };
+
+  let id = api_state.next_game_id;
+  api_state.next_game_id += 1;
+
+  if api_state.games.len() >= MAX_GAMES {
+    if let Some(oldest_id) = api_state.games.keys().min().copied() {
+      api_state.games.remove(&oldest_id);
+    }
+  }
+
+  let response = game_to_json(id, &gameconfig, bot_move);
+  api_state.games.insert(id, gameconfig);
+
+  Ok(ApiResponse::ok(response))
+}
+
+fn with_game(
+  api_state: &mut ApiState,
+  id: &str,
+  handler: impl FnOnce(u64, &mut GameConfig) -> Result<Value, ApiResponse>,
+) -> Result<ApiResponse, ApiResponse> {
+  let id_and_game = id.parse::<u64>().ok().and_then(|id| {
+    api_state
+      .games
+      .get_mut(&id)
+      .map(|gameconfig| (id, gameconfig))
+  });
+
+  match id_and_game {
+    Some((id, gameconfig)) => handler(id, gameconfig).map(ApiResponse::ok),
+    None => Err(ApiResponse::error(
+      404,
+      format!("no game with the id '{id}'"),
+    )),
+  }
+}
+
+/// Places the player's move, then the bot's reply if the game isn't over.
+fn play_game_move(
+  id: u64,
+  gameconfig: &mut GameConfig,
+  request_body: &Value,
+) -> Result<Value, ApiResponse> {
+  if gameconfig.current_result().is_some() {
+    return Err(ApiResponse::error(409, "the game is already over"));
+  }
+
+  let notation = request_body
+    .get("move")
+    .and_then(Value::as_str)
+    .ok_or_else(|| ApiResponse::error(400, "missing \"move\""))?;
+
+  let coords = parse_engine_move(notation).map_err(|error| ApiResponse::error(400, error))?;
+
+  if gameconfig.gameboard.get_board_state(&coords) != &BoardStates::Empty {
+    return Err(ApiResponse::error(
+      409,
+      format!("{notation} is already taken"),
+    ));
+  }
+
+  gameconfig
+    .gameboard
+    .place_tile(&coords, gameconfig.player_symbol);
+  gameconfig.gameboard.tiles_covered += 1;
+
+  // the bot keeps following its path across requests since each game keeps its own bot
+  let bot_move = if gameconfig.current_result().is_none() {
+    Some(bot_place_tile(gameconfig))
+  } else {
+    None
+  };
+
+  Ok(game_to_json(id, gameconfig, bot_move))
+}
+
+fn game_to_json(id: u64, gameconfig: &GameConfig, bot_move: Option<Coordinates>) -> Value {
+  json!({
+    "id": id,
+    "position": gameconfig.gameboard.to_notation(),
+    "player_symbol": format!("{:?}", gameconfig.player_symbol),
+    "bot_symbol": format!("{:?}", gameconfig.bot.bot_symbol),
+    "bot_move": bot_move.map(|coords| row_column_notation(&coords)),
+    "result": gameconfig.current_result().map(|game_state| format!("{game_state:?}")),
+  })
+}
+
+fn session_from_request(request_body: &Value) -> Result<EngineSession, ApiResponse> {
+  let position = match request_body.get("position") {
+    Some(Value::String(position)) => position.as_str(),
+    Some(_) => return Err(ApiResponse::error(400, "\"position\" must be a string")),
+    None => "startpos",
+  };
+
+  let starting_board =
+    BoardConfig::from_notation(position).map_err(|error| ApiResponse::error(400, error))?;
+
+  let moves = match request_body.get("moves") {
+    Some(Value::Array(moves)) => moves
+      .iter()
+      .map(|notation| {
+        notation
+          .as_str()
+          .ok_or_else(|| Box::from("moves must be strings"))
+          .and_then(parse_engine_move)
+      })
+      .collect::<Result<Vec<Coordinates>, Box<dyn Error>>>()
+      .map_err(|error| ApiResponse::error(400, error))?,
+    Some(_) => return Err(ApiResponse::error(400, "\"moves\" must be a list")),
+    None => vec![],
+  };
+
+  let mut session = EngineSession::new();
+
+  session
+    .set_position(starting_board, moves)
+    .map_err(|error| ApiResponse::error(400, error))?;
+
+  Ok(session)
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+  match status {
+    200 => "OK",
+    400 => "Bad Request",
+    404 => "Not Found",
+    405 => "Method Not Allowed",
+    409 => "Conflict",
+    413 => "Payload Too Large",
+    500 => "Internal Server Error",
+    _ => "Internal Server Error",
+  }
+}
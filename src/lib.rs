@@ -1,5 +1,31 @@
+pub mod blocked;
+pub mod board_render;
 pub mod bot;
+pub mod config;
 pub mod coordinate_methods;
+pub mod cube;
 pub mod defaults;
+pub mod engine_protocol;
+pub mod external_engine;
+pub mod ffi;
 pub mod gameboard;
 pub mod gameplay;
+pub mod gomoku;
+pub mod gravity;
+pub mod grid_board;
+pub mod http_api;
+pub mod mcts;
+pub mod morris;
+pub mod multiplayer;
+pub mod network_play;
+pub mod notakto;
+pub mod numerical;
+pub mod order_chaos;
+pub mod quantum;
+pub mod search;
+pub mod session;
+pub mod solver;
+pub mod torus;
+pub mod tui;
+pub mod ultimate;
+pub mod wild;
@@ -1,13 +1,45 @@
 use std::env::args;
+use std::error::Error;
+use std::path::Path;
+use tictactoe_with_ai::board_render::stdout_supports_colors;
+use tictactoe_with_ai::config::{take_flag, ConfigFile};
 use tictactoe_with_ai::gameplay::{run_gamemode, GameConfig};
 
 fn main() {
-  let possible_gamemode_arguments = args().nth(1);
+  let mut arguments: Vec<String> = args().skip(1).collect();
 
-  let gameconfig = GameConfig::new()
+  let config_file = match load_config(&mut arguments) {
+    Ok(config_file) => config_file,
+    Err(error) => {
+      eprintln!("An error has occured: '{error}'");
+
+      return;
+    }
+  };
+
+  let possible_gamemode_arguments = if arguments.is_empty() {
+    config_file.mode.clone()
+  } else {
+    Some(arguments.remove(0))
+  };
+
+  let mut gameconfig = GameConfig::from_config(&config_file)
     .unwrap_or_else(|error| panic!("An error has occured while grabbing config: '{error}'"));
+  gameconfig.colors = stdout_supports_colors();
 
-  if let Err(error) = run_gamemode(possible_gamemode_arguments, gameconfig) {
+  if let Err(error) = run_gamemode(possible_gamemode_arguments, &arguments, gameconfig) {
     eprintln!("An error has occured: '{error}'");
   }
 }
+
+/// The file from `--config <path>` or the default path, then any setting flags on top.
+fn load_config(arguments: &mut Vec<String>) -> Result<ConfigFile, Box<dyn Error>> {
+  let mut config_file = match take_flag(arguments, "--config")? {
+    Some(path) => ConfigFile::load(Path::new(&path))?,
+    None => ConfigFile::load_default()?,
+  };
+
+  config_file.apply_flags(arguments)?;
+
+  Ok(config_file)
+}
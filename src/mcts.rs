@@ -0,0 +1,277 @@
+//! Monte Carlo tree search, for games with no hand written strategy.
+//!
+//! Rather than scoring positions, it plays lots of random games out from
+//! the current one and grows a tree towards the moves that won the most,
+//! balancing trying the best looking moves again against trying the ones
+//! it hasn't played much (UCT). It only needs to know the rules, so anything
+//! implementing Game can be played by it.
+
+use crate::coordinate_methods::Coordinates;
+use crate::gameboard::*;
+use crate::gameplay::GameState;
+use crate::solver;
+use rand::prelude::*;
+use std::time::{Duration, Instant};
+
+/// The usual UCT exploration constant, the square root of two.
+pub const DEFAULT_EXPLORATION: f64 = std::f64::consts::SQRT_2;
+
+/// How a game ended. Players are numbered from 0 in the order they play.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Outcome {
+  Won(usize),
+  Draw,
+}
+
+/// The rules the search needs to know.
+pub trait Game: Clone {
+  type Move: Copy + PartialEq;
+
+  /// Whoever places next.
+  fn current_player(&self) -> usize;
+
+  /// Every move that can be played, none once the game is over.
+  fn legal_moves(&self) -> Vec<Self::Move>;
+
+  /// Plays a move from legal_moves for current_player.
+  fn apply(&mut self, game_move: &Self::Move);
+
+  /// None while the game can still be played.
+  fn outcome(&self) -> Option<Outcome>;
+}
+
+/// X is player 0 and O player 1.
+impl Game for BoardConfig {
+  type Move = Coordinates;
+
+  fn current_player(&self) -> usize {
+    player_of(self.next_symbol_to_place())
+  }
+
+  fn legal_moves(&self) -> Vec<Coordinates> {
+    if self.outcome().is_some() {
+      return vec![];
+    }
+
+    solver::empty_tiles(self)
+  }
+
+  fn apply(&mut self, coords: &Coordinates) {
+    let symbol = self.next_symbol_to_place();
+
+    self.place_tile(coords, symbol);
+    self.tiles_covered += 1;
+  }
+
+  fn outcome(&self) -> Option<Outcome> {
+    match self.winning_row() {
+      Some(row) if self.get_board_state(&row[0]) == &BoardStates::X => Some(Outcome::Won(0)),
+      Some(_) => Some(Outcome::Won(1)),
+      None if self.all_tiles_covered() => Some(Outcome::Draw),
+      None => None,
+    }
+  }
+}
+
+/// X is player 0 and O player 1 in every two player game.
+pub fn player_of(symbol: BoardStates) -> usize {
+  match symbol {
+    BoardStates::X => 0,
+    _ => 1,
+  }
+}
+
+pub fn symbol_of(player: usize) -> BoardStates {
+  match player {
+    0 => BoardStates::X,
+    _ => BoardStates::O,
+  }
+}
+
+impl From<GameState> for Outcome {
+  fn from(end_gamestate: GameState) -> Self {
+    match end_gamestate {
+      GameState::XWon => Outcome::Won(0),
+      GameState::OWon => Outcome::Won(1),
+      GameState::Draw => Outcome::Draw,
+    }
+  }
+}
+
+/// How long the search goes on for.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum MctsBudget {
+  Iterations(u32),
+  Time(Duration),
+}
+
+/// What the search found out about one of the moves from the current position.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct MoveStats<M> {
+  pub game_move: M,
+  pub visits: u32,
+  /// The average result for whoever played it, 1 a win, 0.5 a draw and 0 a loss.
+  pub score: f64,
+}
+
+struct Node<G: Game> {
+  game: G,
+  game_move: Option<G::Move>,
+  parent: Option<usize>,
+  children: Vec<usize>,
+  untried_moves: Vec<G::Move>,
+  visits: u32,
+  /// Summed results for whoever played the move into this node.
+  total_score: f64,
+  /// Who played the move into this node, None for the root as nobody did.
+  player: Option<usize>,
+}
+
+impl<G: Game> Node<G> {
+  fn new(
+    game: G,
+    game_move: Option<G::Move>,
+    parent: Option<usize>,
+    player: Option<usize>,
+  ) -> Self {
+    Node {
+      untried_moves: game.legal_moves(),
+      game,
+      game_move,
+      parent,
+      children: vec![],
+      visits: 0,
+      total_score: 0.0,
+      player,
+    }
+  }
+
+  fn uct(&self, parent_visits: u32, exploration: f64) -> f64 {
+    let visits = self.visits as f64;
+
+    self.total_score / visits + exploration * ((parent_visits as f64).ln() / visits).sqrt()
+  }
+}
+
+/// Searches from game within budget, returning every move tried from it.
+/// At least one iteration is always run.
+pub fn search<G: Game, R: Rng>(
+  game: &G,
+  budget: MctsBudget,
+  exploration: f64,
+  rng: &mut R,
+) -> Vec<MoveStats<G::Move>> {
+  let started = Instant::now();
+  let mut nodes = vec![Node::new(game.clone(), None, None, None)];
+  let mut iterations = 0;
+
+  loop {
+    iterate(&mut nodes, exploration, rng);
+    iterations += 1;
+
+    let finished = match budget {
+      MctsBudget::Iterations(limit) => iterations >= limit,
+      MctsBudget::Time(limit) => started.elapsed() >= limit,
+    };
+
+    if finished {
+      break;
+    }
+  }
+
+  nodes[0]
+    .children
+    .iter()
+    .filter_map(|child| {
+      let node = &nodes[*child];
+
+      Some(MoveStats {
+        game_move: node.game_move?,
+        visits: node.visits,
+        score: node.total_score / node.visits as f64,
+      })
+    })
+    .collect()
+}
+
+/// The move the search visited most, None if the game is already over.
+pub fn best_move<G: Game, R: Rng>(game: &G, budget: MctsBudget, rng: &mut R) -> Option<G::Move> {
+  search(game, budget, DEFAULT_EXPLORATION, rng)
+    .into_iter()
+    .max_by_key(|move_stats| move_stats.visits)
+    .map(|move_stats| move_stats.game_move)
+}
+
+/// Picks a path down the tree, adds a node at the end of it, plays a random
+/// game out from there and passes the result back up the path.
+fn iterate<G: Game, R: Rng>(nodes: &mut Vec<Node<G>>, exploration: f64, rng: &mut R) {
+  let mut current = 0;
+
+  while nodes[current].untried_moves.is_empty() && !nodes[current].children.is_empty() {
+    let parent_visits = nodes[current].visits;
+
+    current = *nodes[current]
+      .children
+      .iter()
+      .max_by(|first, second| {
+        let first = nodes[**first].uct(parent_visits, exploration);
+        let second = nodes[**second].uct(parent_visits, exploration);
+
+        first.total_cmp(&second)
+      })
+      .unwrap();
+  }
+
+  if !nodes[current].untried_moves.is_empty() {
+    let index = rng.gen_range(0..nodes[current].untried_moves.len());
+    let game_move = nodes[current].untried_moves.swap_remove(index);
+
+    let mut game = nodes[current].game.clone();
+    let player = game.current_player();
+    game.apply(&game_move);
+
+    nodes.push(Node::new(
+      game,
+      Some(game_move),
+      Some(current),
+      Some(player),
+    ));
+
+    let child = nodes.len() - 1;
+    nodes[current].children.push(child);
+    current = child;
+  }
+
+  let outcome = playout(&nodes[current].game, rng);
+  let mut next = Some(current);
+
+  while let Some(index) = next {
+    let node = &mut nodes[index];
+
+    node.visits += 1;
+    node.total_score += match outcome {
+      Outcome::Won(winner) if Some(winner) == node.player => 1.0,
+      Outcome::Won(_) => 0.0,
+      Outcome::Draw => 0.5,
+    };
+
+    next = node.parent;
+  }
+}
+
+/// Plays random moves until the game ends.
+fn playout<G: Game, R: Rng>(game: &G, rng: &mut R) -> Outcome {
+  let mut game = game.clone();
+
+  loop {
+    if let Some(outcome) = game.outcome() {
+      return outcome;
+    }
+
+    let Some(game_move) = game.legal_moves().choose(rng).copied() else {
+      return Outcome::Draw;
+    };
+
+    game.apply(&game_move);
+  }
+}
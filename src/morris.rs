@@ -0,0 +1,408 @@
+//! Three Men's Morris, started with `morris [orthogonal]`.
+//!
+//! Each side only has three pieces. Once they're all placed a turn is sliding one
+//! of your own pieces to an empty tile next to it, diagonals included unless the
+//! game was started with 'orthogonal'. Three in a row still wins.
+//!
+//! The same position coming up for the third time is a draw, as is reaching
+//! MAX_MORRIS_MOVES. With three pieces each nobody can be boxed in without the
+//! other side already having three in a row, so there's no rule for that.
+//!
+//! Placing is written as a tile, "b2" or "5", and sliding as the tile to move
+//! from then the tile to move to, "a1 b2" or "1 5".
+
+use crate::board_render::Theme;
+use crate::coordinate_methods::*;
+use crate::gameboard::*;
+use crate::gameplay::*;
+use crate::mcts::{player_of, symbol_of, Game, Outcome};
+use crate::search::{best_move, search_depth, Searchable};
+use crate::session::{play_session, two_player_seats, Seat, SessionGame};
+use crate::solver::{empty_tiles, opposing_symbol};
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+pub const PIECES_PER_PLAYER: u8 = 3;
+pub const MAX_MORRIS_MOVES: usize = 100;
+pub const REPETITIONS_TO_DRAW: u8 = 3;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum MorrisMove {
+  Place(Coordinates),
+  Slide(Coordinates, Coordinates),
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct MorrisGame {
+  pub gameboard: BoardConfig,
+  pub next_symbol: BoardStates,
+  /// Pieces only slide across, never diagonally.
+  pub orthogonal_only: bool,
+  pub moves_made: usize,
+  /// How often each position came up, the board notation then whoever was to move.
+  pub positions_seen: HashMap<String, u8>,
+  pub last_move: Option<MorrisMove>,
+  /// How the board is drawn, a session takes them from the gameconfig.
+  pub theme: Theme,
+  pub colors: bool,
+}
+
+impl MorrisGame {
+  pub fn new(orthogonal_only: bool) -> Self {
+    let mut morris_game = MorrisGame {
+      gameboard: BoardConfig::new(),
+      next_symbol: BoardStates::X,
+      orthogonal_only,
+      moves_made: 0,
+      positions_seen: HashMap::new(),
+      last_move: None,
+      theme: Theme::default(),
+      colors: false,
+    };
+
+    morris_game.record_position();
+
+    morris_game
+  }
+
+  pub fn pieces_placed(&self, symbol: BoardStates) -> u8 {
+    self
+      .gameboard
+      .tiles
+      .iter()
+      .flatten()
+      .filter(|tile| tile.board_state == symbol)
+      .count() as u8
+  }
+
+  /// Pieces are placed until both sides have all three on the board.
+  pub fn is_placing(&self) -> bool {
+    self.pieces_placed(self.next_symbol) < PIECES_PER_PLAYER
+  }
+
+  pub fn is_adjacent(&self, from: &Coordinates, to: &Coordinates) -> bool {
+    if self.orthogonal_only {
+      to.is_across_from(from)
+    } else {
+      from.get_coords_around().contains(to)
+    }
+  }
+
+  pub fn winner(&self) -> Option<BoardStates> {
+    self
+      .gameboard
+      .winning_row()
+      .map(|row| *self.gameboard.get_board_state(&row[0]))
+  }
+
+  pub fn is_repeated(&self) -> bool {
+    self
+      .positions_seen
+      .values()
+      .any(|times_seen| *times_seen >= REPETITIONS_TO_DRAW)
+  }
+
+  /// None while the game can still be played.
+  pub fn result(&self) -> Option<GameState> {
+    match self.winner() {
+      Some(BoardStates::X) => Some(GameState::XWon),
+      Some(BoardStates::O) => Some(GameState::OWon),
+      _ if self.is_repeated() || self.moves_made >= MAX_MORRIS_MOVES => Some(GameState::Draw),
+      _ => None,
+    }
+  }
+
+  pub fn legal_moves(&self) -> Vec<MorrisMove> {
+    if self.winner().is_some() {
+      return vec![];
+    }
+
+    let empty = empty_tiles(&self.gameboard);
+
+    if self.is_placing() {
+      return empty.into_iter().map(MorrisMove::Place).collect();
+    }
+
+    self
+      .pieces_of(self.next_symbol)
+      .into_iter()
+      .flat_map(|from| {
+        empty
+          .iter()
+          .filter(move |to| self.is_adjacent(&from, to))
+          .map(move |to| MorrisMove::Slide(from, *to))
+      })
+      .collect()
+  }
+
+  /// Plays the move for next_symbol, erroring if it isn't allowed.
+  pub fn play(&mut self, morris_move: &MorrisMove) -> Result<(), Box<dyn Error>> {
+    if self.result().is_some() {
+      return Err(Box::from("the game is over"));
+    }
+
+    let symbol = self.next_symbol;
+
+    match *morris_move {
+      MorrisMove::Place(to) => {
+        if !self.is_placing() {
+          return Err(Box::from(
+            "all your pieces are placed, slide one with e.g. 'a1 b2'",
+          ));
+        }
+
+        self.check_empty(&to)?;
+        self.gameboard.place_tile(&to, symbol);
+        self.gameboard.tiles_covered += 1;
+      }
+      MorrisMove::Slide(from, to) => {
+        if self.is_placing() {
+          return Err(Box::from(format!(
+            "place all {PIECES_PER_PLAYER} of your pieces before sliding them"
+          )));
+        }
+
+        check_in_bounds(&from)?;
+
+        if self.gameboard.get_board_state(&from) != &symbol {
+          return Err(Box::from(format!(
+            "{} isn't one of your pieces",
+            row_column_notation(&from)
+          )));
+        }
+
+        self.check_empty(&to)?;
+
+        if !self.is_adjacent(&from, &to) {
+          return Err(Box::from(format!(
+            "{} isn't next to {}",
+            row_column_notation(&to),
+            row_column_notation(&from)
+          )));
+        }
+
+        self.gameboard.place_tile(&from, BoardStates::Empty);
+        self.gameboard.place_tile(&to, symbol);
+      }
+    }
+
+    self.next_symbol = opposing_symbol(symbol);
+    self.moves_made += 1;
+    self.last_move = Some(*morris_move);
+    self.record_position();
+
+    Ok(())
+  }
+
+  fn pieces_of(&self, symbol: BoardStates) -> Vec<Coordinates> {
+    all_coordinates()
+      .into_iter()
+      .filter(|coords| self.gameboard.get_board_state(coords) == &symbol)
+      .collect()
+  }
+
+  fn check_empty(&self, coords: &Coordinates) -> Result<(), Box<dyn Error>> {
+    check_in_bounds(coords)?;
+
+    if self.gameboard.get_board_state(coords) != &BoardStates::Empty {
+      return Err(Box::from(format!(
+        "{} is already taken",
+        row_column_notation(coords)
+      )));
+    }
+
+    Ok(())
+  }
+
+  fn record_position(&mut self) {
+    let position = format!("{}{:?}", self.gameboard.to_notation(), self.next_symbol);
+
+    *self.positions_seen.entry(position).or_default() += 1;
+  }
+}
+
+impl Default for MorrisGame {
+  fn default() -> Self {
+    Self::new(false)
+  }
+}
+
+fn check_in_bounds(coords: &Coordinates) -> Result<(), Box<dyn Error>> {
+  if coords.0 >= GRID_SIZE || coords.1 >= GRID_SIZE {
+    return Err(Box::from("incorrect input"));
+  }
+
+  Ok(())
+}
+
+/// "b2" places a piece, "a1 b2" slides the piece on a1 over to b2.
+pub fn parse_morris_move(player_input: &str) -> Result<MorrisMove, Box<dyn Error>> {
+  let selections: Vec<&str> = player_input.split_whitespace().collect();
+
+  match selections.as_slice() {
+    [to] => Ok(MorrisMove::Place(parse_tile_selection(to)?)),
+    [from, to] => Ok(MorrisMove::Slide(
+      parse_tile_selection(from)?,
+      parse_tile_selection(to)?,
+    )),
+    _ => Err(Box::from("incorrect input")),
+  }
+}
+
+pub fn morris_move_notation(morris_move: &MorrisMove) -> String {
+  match morris_move {
+    MorrisMove::Place(to) => row_column_notation(to),
+    MorrisMove::Slide(from, to) => {
+      format!("{} {}", row_column_notation(from), row_column_notation(to))
+    }
+  }
+}
+
+/// Holding the center and having more places to slide to, for symbol minus the other side.
+fn evaluate(morris_game: &MorrisGame, symbol: BoardStates) -> i32 {
+  let center = morris_game.gameboard.get_board_state(&(1, 1));
+  let center_score = if center == &symbol {
+    5
+  } else if center == &opposing_symbol(symbol) {
+    -5
+  } else {
+    0
+  };
+
+  let mobility = |symbol: BoardStates| {
+    let empty = empty_tiles(&morris_game.gameboard);
+
+    morris_game
+      .pieces_of(symbol)
+      .iter()
+      .map(|from| {
+        empty
+          .iter()
+          .filter(|to| morris_game.is_adjacent(from, to))
+          .count() as i32
+      })
+      .sum::<i32>()
+  };
+
+  center_score + mobility(symbol) - mobility(opposing_symbol(symbol))
+}
+
+impl Game for MorrisGame {
+  type Move = MorrisMove;
+
+  fn current_player(&self) -> usize {
+    player_of(self.next_symbol)
+  }
+
+  fn legal_moves(&self) -> Vec<MorrisMove> {
+    if self.result().is_some() {
+      return vec![];
+    }
+
+    MorrisGame::legal_moves(self)
+  }
+
+  fn apply(&mut self, morris_move: &MorrisMove) {
+    self.play(morris_move).expect("only legal moves are played");
+  }
+
+  fn outcome(&self) -> Option<Outcome> {
+    self.result().map(Outcome::from)
+  }
+}
+
+impl Searchable for MorrisGame {
+  const SEARCH_DEPTHS: [u32; 3] = [1, 3, 7];
+
+  fn evaluate(&self, player: usize) -> i32 {
+    evaluate(self, symbol_of(player))
+  }
+}
+
+impl SessionGame for MorrisGame {
+  type Result = GameState;
+
+  fn render(&self) -> String {
+    self.theme.renderer(self.colors).render(&self.gameboard)
+  }
+
+  fn session_result(&self) -> Option<GameState> {
+    self.result()
+  }
+
+  fn prompt(&self) -> String {
+    if self.is_placing() {
+      "select a tile to place on, e.g. 'b2' or '5'".to_string()
+    } else {
+      "select a piece then where to slide it, e.g. 'a1 b2' or '1 5'".to_string()
+    }
+  }
+
+  fn play_input(&mut self, player_input: &str) -> Result<(), Box<dyn Error>> {
+    self.play(&parse_morris_move(player_input)?)
+  }
+
+  fn describe_move(&self, morris_move: &MorrisMove) -> String {
+    format!("played {}", morris_move_notation(morris_move))
+  }
+
+  /// Says why it's a draw when it wasn't a full board.
+  fn announce(&self, end_gamestate: &GameState, _seats: &[Seat]) -> String {
+    if self.is_repeated() {
+      format!(" -- the same position came up three times --\n{end_gamestate:?}")
+    } else if self.moves_made >= MAX_MORRIS_MOVES {
+      format!(" -- {MAX_MORRIS_MOVES} moves were made --\n{end_gamestate:?}")
+    } else {
+      format!("{end_gamestate:?}")
+    }
+  }
+}
+
+pub fn morris(gameconfig: GameConfig, gamemode_arguments: &[String]) -> Result<(), Box<dyn Error>> {
+  let orthogonal_only = match gamemode_arguments.first().map(String::as_str) {
+    Some("orthogonal") => true,
+    None => false,
+    Some(argument) => {
+      return Err(Box::from(format!(
+        "unknown morris option '{argument}', expected 'orthogonal' or nothing"
+      )))
+    }
+  };
+
+  morris_session(
+    gameconfig,
+    orthogonal_only,
+    io::stdin().lock(),
+    io::stdout(),
+  )?;
+
+  Ok(())
+}
+
+pub fn morris_session<R: BufRead, W: Write>(
+  gameconfig: GameConfig,
+  orthogonal_only: bool,
+  input: R,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
+  let depth = search_depth::<MorrisGame>(gameconfig.bot.difficulty);
+
+  let mut morris_game = MorrisGame::new(orthogonal_only);
+  morris_game.theme = gameconfig.theme;
+  morris_game.colors = gameconfig.colors;
+
+  writeln!(
+    output,
+    "{} {:?} | Bot {:?} | {PIECES_PER_PLAYER} pieces each",
+    gameconfig.player_name, gameconfig.player_symbol, gameconfig.bot.bot_symbol
+  )?;
+
+  play_session(
+    morris_game,
+    &two_player_seats(&gameconfig.player_name, player_of(gameconfig.player_symbol)),
+    |morris_game| best_move(morris_game, depth),
+    input,
+    output,
+  )
+}
@@ -0,0 +1,361 @@
+//! Games for more than two, started with `multiplayer [players [people [size [win length]]]]`.
+//!
+//! Everyone takes turns in order placing their own symbol, X, O, △ and so on,
+//! and the first to get win_length in a row wins. The first few seats are
+//! people sharing the keyboard and the rest are bots. The default is three
+//! players, one of them you, going for three in a row on a 4x4 board.
+//!
+//! The bots use paranoid search, assuming everyone else is out to get them.
+//!
+//! Tiles are written like on the flat boards, e.g. "c4".
+
+use crate::coordinate_methods::Coordinates;
+use crate::gameplay::*;
+use crate::grid_board::*;
+use crate::mcts::{Game, Outcome};
+use crate::search::{best_move, line_score, search_depth, total_evaluation, Searchable};
+use crate::session::{play_session, Seat, SessionGame};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+pub const PLAYER_SYMBOLS: [char; 6] = ['X', 'O', '△', '□', '◇', '☆'];
+
+pub const DEFAULT_PLAYERS: usize = 3;
+pub const DEFAULT_PEOPLE: usize = 1;
+pub const DEFAULT_SIZE: usize = 4;
+pub const DEFAULT_WIN_LENGTH: usize = 3;
+
+const MULTIPLAYER_USAGE: &str =
+  "expected 'multiplayer [players [people [size [win length]]]]', all whole numbers";
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum MultiplayerResult {
+  /// The index of whoever won, 0 being X.
+  PlayerWon(usize),
+  Draw,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct MultiplayerGame {
+  /// Only used for its size and lines, the tiles are kept in tiles.
+  pub grid_board: GridBoard,
+  /// Which player placed on each tile, row by row from the top left.
+  pub tiles: Vec<Option<usize>>,
+  pub player_count: usize,
+  pub next_player: usize,
+  pub last_placed_tile: Option<Coordinates>,
+  lines: Vec<Vec<Coordinates>>,
+}
+
+impl MultiplayerGame {
+  pub fn new(
+    player_count: usize,
+    width: usize,
+    height: usize,
+    win_length: usize,
+  ) -> Result<Self, Box<dyn Error>> {
+    if !(2..=PLAYER_SYMBOLS.len()).contains(&player_count) {
+      return Err(Box::from(format!(
+        "there can be 2 to {} players, got {player_count}",
+        PLAYER_SYMBOLS.len()
+      )));
+    }
+
+    let grid_board = GridBoard::new(width, height, win_length)?;
+
+    Ok(MultiplayerGame {
+      tiles: vec![None; width * height],
+      lines: grid_board.lines(),
+      grid_board,
+      player_count,
+      next_player: 0,
+      last_placed_tile: None,
+    })
+  }
+
+  pub fn player_at(&self, coords: &Coordinates) -> Option<usize> {
+    self.tiles[coords.0 * self.grid_board.width + coords.1]
+  }
+
+  pub fn empty_tiles(&self) -> Vec<Coordinates> {
+    self
+      .grid_board
+      .all_coordinates()
+      .into_iter()
+      .filter(|coords| self.player_at(coords).is_none())
+      .collect()
+  }
+
+  /// None while the game can still be played.
+  pub fn result(&self) -> Option<MultiplayerResult> {
+    let winning_line = self.lines.iter().find(|line| {
+      let first = self.player_at(&line[0]);
+
+      first.is_some() && line.iter().all(|coords| self.player_at(coords) == first)
+    });
+
+    match winning_line {
+      Some(line) => self.player_at(&line[0]).map(MultiplayerResult::PlayerWon),
+      None if self.tiles.iter().all(Option::is_some) => Some(MultiplayerResult::Draw),
+      None => None,
+    }
+  }
+
+  /// Places for next_player then passes the turn on, erroring if coords isn't free.
+  pub fn place(&mut self, coords: &Coordinates) -> Result<(), Box<dyn Error>> {
+    if self.result().is_some() {
+      return Err(Box::from("the game is over"));
+    }
+
+    if self.player_at(coords).is_some() {
+      return Err(Box::from(format!(
+        "{} is already taken",
+        row_column_notation(coords)
+      )));
+    }
+
+    self.tiles[coords.0 * self.grid_board.width + coords.1] = Some(self.next_player);
+    self.last_placed_tile = Some(*coords);
+    self.next_player = (self.next_player + 1) % self.player_count;
+
+    Ok(())
+  }
+
+  /// Empty tiles next to a placed one, or every tile on an empty board.
+  pub fn candidate_tiles(&self) -> Vec<Coordinates> {
+    let empty_tiles = self.empty_tiles();
+
+    let near_pieces: Vec<Coordinates> = empty_tiles
+      .iter()
+      .filter(|coords| {
+        self
+          .grid_board
+          .neighbors(coords)
+          .iter()
+          .any(|next| self.player_at(next).is_some())
+      })
+      .copied()
+      .collect();
+
+    if near_pieces.is_empty() {
+      empty_tiles
+    } else {
+      near_pieces
+    }
+  }
+
+  /// Lines only one player has pieces in, worth more the closer they are to done,
+  /// for player minus everyone else's.
+  pub fn evaluate(&self, player: usize) -> i32 {
+    total_evaluation(self.lines.iter().map(|line| {
+      let mut owner = None;
+      let mut count = 0;
+
+      for placed in line.iter().filter_map(|coords| self.player_at(coords)) {
+        match owner {
+          Some(owner) if owner != placed => return 0,
+          _ => owner = Some(placed),
+        }
+
+        count += 1;
+      }
+
+      match owner {
+        Some(owner) if owner == player => line_score(count),
+        Some(_) => -line_score(count),
+        None => 0,
+      }
+    }))
+  }
+}
+
+pub fn player_symbol(player: usize) -> char {
+  PLAYER_SYMBOLS[player]
+}
+
+impl Game for MultiplayerGame {
+  type Move = Coordinates;
+
+  fn current_player(&self) -> usize {
+    self.next_player
+  }
+
+  fn legal_moves(&self) -> Vec<Coordinates> {
+    if self.result().is_some() {
+      return vec![];
+    }
+
+    self.empty_tiles()
+  }
+
+  fn apply(&mut self, coords: &Coordinates) {
+    self.place(coords).expect("only legal tiles are placed on");
+  }
+
+  fn outcome(&self) -> Option<Outcome> {
+    self
+      .result()
+      .map(|multiplayer_result| match multiplayer_result {
+        MultiplayerResult::PlayerWon(winner) => Outcome::Won(winner),
+        MultiplayerResult::Draw => Outcome::Draw,
+      })
+  }
+}
+
+impl Searchable for MultiplayerGame {
+  fn evaluate(&self, player: usize) -> i32 {
+    MultiplayerGame::evaluate(self, player)
+  }
+
+  fn search_moves(&self) -> Vec<Coordinates> {
+    self.candidate_tiles()
+  }
+}
+
+impl SessionGame for MultiplayerGame {
+  type Result = MultiplayerResult;
+
+  fn render(&self) -> String {
+    render_multiplayer(self)
+  }
+
+  fn session_result(&self) -> Option<MultiplayerResult> {
+    self.result()
+  }
+
+  fn prompt(&self) -> String {
+    "select a tile, e.g. 'c4'".to_string()
+  }
+
+  fn play_input(&mut self, player_input: &str) -> Result<(), Box<dyn Error>> {
+    let coords = parse_grid_tile(player_input, &self.grid_board)?;
+
+    self.place(&coords)
+  }
+
+  fn describe_move(&self, coords: &Coordinates) -> String {
+    format!(
+      "{} placed on {}",
+      player_symbol(self.next_player),
+      row_column_notation(coords)
+    )
+  }
+
+  fn announce(&self, multiplayer_result: &MultiplayerResult, seats: &[Seat]) -> String {
+    match multiplayer_result {
+      MultiplayerResult::PlayerWon(winner) => format!("{} won", seats[*winner].name),
+      MultiplayerResult::Draw => "Draw".to_string(),
+    }
+  }
+}
+
+/// ```text
+///     1  2  3  4
+///  a  .  X  .  .
+///  b  .  O [△] .
+///  c  .  .  .  .
+///  d  .  .  .  .
+/// ```
+pub fn render_multiplayer(multiplayer_game: &MultiplayerGame) -> String {
+  let grid_board = &multiplayer_game.grid_board;
+
+  let header: String = (1..=grid_board.width)
+    .map(|column| format!("{column:^3}"))
+    .collect();
+
+  let mut rendered = format!("   {}\n", header.trim_end());
+
+  for row in 0..grid_board.height {
+    let tiles: String = (0..grid_board.width)
+      .map(|column| {
+        let symbol = multiplayer_game
+          .player_at(&(row, column))
+          .map_or('.', player_symbol);
+
+        if multiplayer_game.last_placed_tile == Some((row, column)) {
+          format!("[{symbol}]")
+        } else {
+          format!(" {symbol} ")
+        }
+      })
+      .collect();
+
+    rendered += &format!(" {} {}\n", (b'a' + row as u8) as char, tiles.trim_end());
+  }
+
+  rendered
+}
+
+pub fn multiplayer(
+  gameconfig: GameConfig,
+  gamemode_arguments: &[String],
+) -> Result<(), Box<dyn Error>> {
+  let numbers = gamemode_arguments
+    .iter()
+    .map(|number| number.parse::<usize>())
+    .collect::<Result<Vec<usize>, _>>()
+    .map_err(|_| MULTIPLAYER_USAGE)?;
+
+  let (players, people, size, win_length) = match numbers.as_slice() {
+    [] => (
+      DEFAULT_PLAYERS,
+      DEFAULT_PEOPLE,
+      DEFAULT_SIZE,
+      DEFAULT_WIN_LENGTH,
+    ),
+    [players] => (*players, DEFAULT_PEOPLE, DEFAULT_SIZE, DEFAULT_WIN_LENGTH),
+    [players, people] => (*players, *people, DEFAULT_SIZE, DEFAULT_WIN_LENGTH),
+    [players, people, size] => (*players, *people, *size, DEFAULT_WIN_LENGTH.min(*size)),
+    [players, people, size, win_length] => (*players, *people, *size, *win_length),
+    _ => return Err(Box::from(MULTIPLAYER_USAGE)),
+  };
+
+  multiplayer_session(
+    gameconfig,
+    MultiplayerGame::new(players, size, size, win_length)?,
+    people,
+    io::stdin().lock(),
+    io::stdout(),
+  )?;
+
+  Ok(())
+}
+
+/// The first `people` seats are played from input, the rest by bots.
+pub fn multiplayer_session<R: BufRead, W: Write>(
+  gameconfig: GameConfig,
+  multiplayer_game: MultiplayerGame,
+  people: usize,
+  input: R,
+  mut output: W,
+) -> Result<MultiplayerResult, Box<dyn Error>> {
+  let depth = search_depth::<MultiplayerGame>(gameconfig.bot.difficulty);
+
+  let seats: Vec<Seat> = (0..multiplayer_game.player_count)
+    .map(|player| Seat {
+      name: match player {
+        0 if people == 1 => format!("{} {}", gameconfig.player_name, player_symbol(player)),
+        _ if player < people => format!("Player {} {}", player + 1, player_symbol(player)),
+        _ => format!("Bot {}", player_symbol(player)),
+      },
+      is_person: player < people,
+    })
+    .collect();
+
+  let names: Vec<&str> = seats.iter().map(|seat| seat.name.as_str()).collect();
+
+  writeln!(
+    output,
+    "{} | {} in a row wins",
+    names.join(" | "),
+    multiplayer_game.grid_board.win_length
+  )?;
+
+  play_session(
+    multiplayer_game,
+    &seats,
+    |multiplayer_game| best_move(multiplayer_game, depth),
+    input,
+    output,
+  )
+}
@@ -0,0 +1,217 @@
+//! Two players on separate terminals over TCP, started with `host <port>` and
+//! `join <address:port>`.
+//!
+//! The host owns the game. The joining player only sends the tiles they pick,
+//! and the host checks them before sending the board back to both players.
+//!
+//! ```text
+//! host -> join: welcome O
+//! host -> join: board x........
+//! host -> join: your_turn
+//! join -> host: move b2
+//! host -> join: illegal b2 is already taken
+//! host -> join: result Draw
+//! ```
+
+use crate::coordinate_methods::*;
+use crate::gameboard::*;
+use crate::gameplay::*;
+use std::error::Error;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+
+pub const DISCONNECTED_ERROR: &str = "the other player disconnected";
+
+/// Waits for someone to join on the port, then plays as the host.
+pub fn host_game(port: &str, gameconfig: GameConfig) -> Result<(), Box<dyn Error>> {
+  let listener = TcpListener::bind(("0.0.0.0", port.parse::<u16>()?))?;
+
+  println!("Waiting for a player to join on port {port}");
+
+  let (stream, address) = listener.accept()?;
+
+  println!("{address} joined");
+
+  host_session(stream, gameconfig, io::stdin().lock(), io::stdout())?;
+
+  Ok(())
+}
+
+pub fn join_game(address: &str, gameconfig: GameConfig) -> Result<(), Box<dyn Error>> {
+  let stream = TcpStream::connect(address)?;
+
+  join_session(stream, gameconfig, io::stdin().lock(), io::stdout())?;
+
+  Ok(())
+}
+
+/// Plays the game as the host, the host placing gameconfig.player_symbol.
+/// The local player's selections are read from local_input.
+pub fn host_session<R: BufRead, W: Write>(
+  stream: TcpStream,
+  mut gameconfig: GameConfig,
+  mut local_input: R,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
+  let mut connection = Connection::new(stream)?;
+  let host_symbol = gameconfig.player_symbol;
+  let joined_symbol = gameconfig.bot.bot_symbol;
+
+  connection.send(&format!("welcome {joined_symbol:?}"))?;
+  connection.send(&format!("board {}", gameconfig.gameboard.to_notation()))?;
+
+  writeln!(output, "You are {host_symbol:?}")?;
+
+  while gameconfig.gameboard.tiles_covered < 9 {
+    writeln!(output)?;
+    gameconfig.write_board(&mut output)?;
+
+    let selected_tile = if gameconfig.gameboard.next_symbol_to_place() == host_symbol {
+      select_empty_tile(&gameconfig.gameboard, &mut local_input, &mut output)?
+    } else {
+      writeln!(output, " -- waiting for {joined_symbol:?} --")?;
+
+      joined_selection(&gameconfig.gameboard, &mut connection)?
+    };
+
+    let symbol = gameconfig.gameboard.next_symbol_to_place();
+
+    gameconfig.gameboard.place_tile(&selected_tile, symbol);
+    gameconfig.gameboard.tiles_covered += 1;
+
+    connection.send(&format!("board {}", gameconfig.gameboard.to_notation()))?;
+
+    if gameconfig.check_if_win() {
+      gameconfig.end_gamestate = gameconfig.completed_row_result();
+
+      break;
+    }
+  }
+
+  connection.send(&format!("result {:?}", gameconfig.end_gamestate))?;
+
+  writeln!(output, "{:?}", gameconfig.end_gamestate)?;
+  gameconfig.write_board(&mut output)?;
+
+  Ok(gameconfig.end_gamestate)
+}
+
+/// Plays the game as the joining player, following whatever the host sends.
+/// Only the theme and colors of gameconfig are used, the board comes from the host.
+pub fn join_session<R: BufRead, W: Write>(
+  stream: TcpStream,
+  mut gameconfig: GameConfig,
+  mut local_input: R,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
+  let mut connection = Connection::new(stream)?;
+
+  loop {
+    let message = connection.receive()?;
+    let (command, argument) = message.split_once(' ').unwrap_or((&message, ""));
+
+    match command {
+      "welcome" => writeln!(output, "You are {argument}")?,
+      "board" => {
+        gameconfig.gameboard = BoardConfig::from_notation(argument)?;
+
+        writeln!(output)?;
+        gameconfig.write_board(&mut output)?;
+      }
+      "your_turn" => {
+        let selected_tile =
+          select_empty_tile(&gameconfig.gameboard, &mut local_input, &mut output)?;
+
+        connection.send(&format!("move {}", row_column_notation(&selected_tile)))?;
+      }
+      "illegal" => writeln!(output, "{argument}")?,
+      "result" => {
+        writeln!(output, "{argument}")?;
+
+        return parse_game_state(argument);
+      }
+      _ => return Err(Box::from(format!("unknown message from host '{message}'"))),
+    }
+  }
+}
+
+/// Asks the joined player for a move until they send one that can be placed.
+fn joined_selection(
+  gameboard: &BoardConfig,
+  connection: &mut Connection,
+) -> Result<Coordinates, Box<dyn Error>> {
+  loop {
+    connection.send("your_turn")?;
+
+    let message = connection.receive()?;
+
+    let selection = match message.split_once(' ') {
+      Some(("move", selection)) => check_selection(gameboard, selection),
+      _ => Err(Box::from(format!("expected a move but got '{message}'"))),
+    };
+
+    match selection {
+      Ok(coords) => return Ok(coords),
+      Err(error) => connection.send(&format!("illegal {error}"))?,
+    }
+  }
+}
+
+fn check_selection(
+  gameboard: &BoardConfig,
+  player_input: &str,
+) -> Result<Coordinates, Box<dyn Error>> {
+  let coords = parse_tile_selection(player_input)?;
+
+  if coords.0 >= GRID_SIZE || coords.1 >= GRID_SIZE {
+    return Err(Box::from("incorrect input"));
+  }
+
+  if gameboard.get_board_state(&coords) != &BoardStates::Empty {
+    return Err(Box::from(format!(
+      "{} is already taken",
+      row_column_notation(&coords)
+    )));
+  }
+
+  Ok(coords)
+}
+
+fn parse_game_state(game_state: &str) -> Result<GameState, Box<dyn Error>> {
+  match game_state {
+    "XWon" => Ok(GameState::XWon),
+    "OWon" => Ok(GameState::OWon),
+    "Draw" => Ok(GameState::Draw),
+    _ => Err(Box::from(format!("unknown result '{game_state}'"))),
+  }
+}
+
+struct Connection {
+  reader: BufReader<TcpStream>,
+  writer: TcpStream,
+}
+
+impl Connection {
+  fn new(stream: TcpStream) -> io::Result<Self> {
+    Ok(Connection {
+      reader: BufReader::new(stream.try_clone()?),
+      writer: stream,
+    })
+  }
+
+  fn send(&mut self, message: &str) -> Result<(), Box<dyn Error>> {
+    writeln!(self.writer, "{message}")
+      .and_then(|_| self.writer.flush())
+      .map_err(|_| Box::from(DISCONNECTED_ERROR))
+  }
+
+  /// A closed or broken connection counts as the other player disconnecting.
+  fn receive(&mut self) -> Result<String, Box<dyn Error>> {
+    let mut message = String::new();
+
+    match self.reader.read_line(&mut message) {
+      Ok(0) | Err(_) => Err(Box::from(DISCONNECTED_ERROR)),
+      Ok(_) => Ok(message.trim().to_string()),
+    }
+  }
+}
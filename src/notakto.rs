@@ -0,0 +1,533 @@
+//! Notakto, started with `notakto [number of boards]`.
+//!
+//! Both sides place X on any of several boards. A board with three in a row is
+//! dead, and whoever kills the last live board loses.
+//!
+//! The bot plays perfectly using the misère quotient from Plambeck and Whitehead's
+//! "The Secrets of Notakto". Every board has a value in the 18 element monoid
+//!
+//! ```text
+//! Q = < a, b, c, d | a² = 1, b³ = b, b²c = c, c³ = ac², b²d = d, cd = ad, d² = c² >
+//! ```
+//!
+//! and a position made of several boards is lost for whoever places next when the
+//! product of its board values is one of a, b², bc or c². So the bot looks for the
+//! move that leaves one of those.
+
+use crate::bot::Difficulty;
+use crate::coordinate_methods::*;
+use crate::gameboard::*;
+use crate::gameplay::*;
+use crate::solver::empty_tiles;
+use rand::prelude::*;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, BufRead, Write};
+
+pub const DEFAULT_BOARD_COUNT: usize = 3;
+pub const MAX_BOARD_COUNT: usize = 9;
+
+/// The board then the tile on it.
+pub type NotaktoMove = (usize, Coordinates);
+
+/// The value of every live board, up to rotating and reflecting it.
+/// Boards with no row of three yet have one of only eight values.
+const BOARD_VALUES: [(&str, &str); 46] = [
+  (".........", "c"),
+  ("....x....", "c2"),
+  (".x.......", "1"),
+  ("x........", "1"),
+  ("...x.x...", "a"),
+  ("..x...x..", "a"),
+  ("..xx.....", "b"),
+  (".x..x....", "b"),
+  (".x.x.....", "a"),
+  ("x...x....", "b"),
+  ("x.x......", "b"),
+  ("xx.......", "d"),
+  ("..xxx....", "a"),
+  (".x...xx..", "1"),
+  (".x.x.x...", "b"),
+  (".x.xx....", "ab"),
+  (".xx...x..", "ad"),
+  (".xxx.....", "ad"),
+  ("x....xx..", "a"),
+  ("x..x.x...", "ad"),
+  ("x.x...x..", "ab"),
+  ("x.x.x....", "a"),
+  ("x.xx.....", "a"),
+  ("xx..x....", "ab"),
+  ("xx.x.....", "b"),
+  ("..xx.xx..", "a"),
+  (".x..xxx..", "b"),
+  (".x.x.x.x.", "a"),
+  (".x.x.xx..", "ab"),
+  (".xx..xx..", "a"),
+  (".xxx..x..", "ab"),
+  (".xxxx....", "b"),
+  ("x...xxx..", "b"),
+  ("x.x...x.x", "a"),
+  ("x.x..xx..", "b"),
+  ("x.xx.x...", "b"),
+  ("x.xxx....", "b"),
+  ("xx...xx..", "b"),
+  ("xx.x.x...", "a"),
+  ("xx.xx....", "a"),
+  (".xxx.xx..", "b"),
+  ("x.x..xxx.", "a"),
+  ("x.xx.x.x.", "a"),
+  ("xx..xxx..", "a"),
+  ("xx.x.x.x.", "b"),
+  (".xxx.xxx.", "a"),
+];
+
+/// An element of the misère quotient written as a^a b^b c^c d^d, always kept reduced.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct QuotientValue {
+  pub a: u8,
+  pub b: u8,
+  pub c: u8,
+  pub d: u8,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct NotaktoGame {
+  pub boards: Vec<BoardConfig>,
+  pub last_move: Option<NotaktoMove>,
+}
+
+impl QuotientValue {
+  pub const IDENTITY: QuotientValue = QuotientValue {
+    a: 0,
+    b: 0,
+    c: 0,
+    d: 0,
+  };
+
+  /// Written the way BOARD_VALUES is, e.g. "ab2c" or "1".
+  pub fn parse(value: &str) -> Result<Self, Box<dyn Error>> {
+    let mut quotient_value = QuotientValue::IDENTITY;
+    let mut letters = value.chars().peekable();
+
+    if value == "1" {
+      return Ok(quotient_value);
+    }
+
+    while let Some(letter) = letters.next() {
+      let power = match letters.peek().and_then(|power| power.to_digit(10)) {
+        Some(power) => {
+          letters.next();
+          power as u8
+        }
+        None => 1,
+      };
+
+      match letter {
+        'a' => quotient_value.a += power,
+        'b' => quotient_value.b += power,
+        'c' => quotient_value.c += power,
+        'd' => quotient_value.d += power,
+        _ => return Err(Box::from(format!("unknown quotient value '{value}'"))),
+      }
+    }
+
+    Ok(quotient_value.reduce())
+  }
+
+  pub fn multiply(self, other: QuotientValue) -> Self {
+    QuotientValue {
+      a: self.a + other.a,
+      b: self.b + other.b,
+      c: self.c + other.c,
+      d: self.d + other.d,
+    }
+    .reduce()
+  }
+
+  /// Whether whoever places next loses, with perfect play from both sides.
+  pub fn is_losing_to_move(self) -> bool {
+    ["a", "b2", "bc", "c2"]
+      .into_iter()
+      .any(|value| QuotientValue::parse(value).ok() == Some(self))
+  }
+
+  /// Applies the relations until none of them fit.
+  fn reduce(mut self) -> Self {
+    loop {
+      if self.a >= 2 {
+        self.a -= 2;
+      } else if self.b >= 3 || (self.b >= 2 && (self.c >= 1 || self.d >= 1)) {
+        self.b -= 2;
+      } else if self.c >= 3 || (self.c >= 1 && self.d >= 1) {
+        // c³ = ac² and cd = ad
+        self.c -= 1;
+        self.a += 1;
+      } else if self.d >= 2 {
+        self.d -= 2;
+        self.c += 2;
+      } else {
+        return self;
+      }
+    }
+  }
+}
+
+impl Display for QuotientValue {
+  fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+    if *self == QuotientValue::IDENTITY {
+      return write!(formatter, "1");
+    }
+
+    for (letter, power) in [('a', self.a), ('b', self.b), ('c', self.c), ('d', self.d)] {
+      match power {
+        0 => (),
+        1 => write!(formatter, "{letter}")?,
+        _ => write!(formatter, "{letter}{power}")?,
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl NotaktoGame {
+  pub fn new(board_count: usize) -> Self {
+    NotaktoGame {
+      boards: vec![BoardConfig::new(); board_count],
+      last_move: None,
+    }
+  }
+
+  pub fn is_board_dead(&self, board: usize) -> bool {
+    self.boards[board].winning_row().is_some()
+  }
+
+  pub fn live_boards(&self) -> Vec<usize> {
+    (0..self.boards.len())
+      .filter(|board| !self.is_board_dead(*board))
+      .collect()
+  }
+
+  /// Once every board is dead, whoever placed last has lost.
+  pub fn is_over(&self) -> bool {
+    self.live_boards().is_empty()
+  }
+
+  pub fn legal_moves(&self) -> Vec<NotaktoMove> {
+    self
+      .live_boards()
+      .into_iter()
+      .flat_map(|board| {
+        empty_tiles(&self.boards[board])
+          .into_iter()
+          .map(move |coords| (board, coords))
+      })
+      .collect()
+  }
+
+  pub fn place(&mut self, notakto_move: &NotaktoMove) -> Result<(), Box<dyn Error>> {
+    let (board, coords) = *notakto_move;
+
+    if board >= self.boards.len() {
+      return Err(Box::from(format!(
+        "there are only {} boards",
+        self.boards.len()
+      )));
+    }
+
+    if coords.0 >= GRID_SIZE || coords.1 >= GRID_SIZE {
+      return Err(Box::from("incorrect input"));
+    }
+
+    if self.is_board_dead(board) {
+      return Err(Box::from(format!("board {} is dead", board + 1)));
+    }
+
+    if self.boards[board].get_board_state(&coords) != &BoardStates::Empty {
+      return Err(Box::from(format!(
+        "{} is already taken",
+        row_column_notation(&coords)
+      )));
+    }
+
+    self.boards[board].place_tile(&coords, BoardStates::X);
+    self.boards[board].tiles_covered += 1;
+    self.last_move = Some(*notakto_move);
+
+    Ok(())
+  }
+
+  /// The product of every board's value.
+  pub fn value(&self) -> QuotientValue {
+    self
+      .boards
+      .iter()
+      .map(board_value)
+      .fold(QuotientValue::IDENTITY, QuotientValue::multiply)
+  }
+}
+
+/// Dead boards can't be played on so they're the identity.
+pub fn board_value(gameboard: &BoardConfig) -> QuotientValue {
+  if gameboard.winning_row().is_some() {
+    return QuotientValue::IDENTITY;
+  }
+
+  let symmetries = board_symmetries(&gameboard.to_notation());
+
+  BOARD_VALUES
+    .iter()
+    .find(|(notation, _)| symmetries.iter().any(|symmetry| symmetry == notation))
+    .and_then(|(_, value)| QuotientValue::parse(value).ok())
+    .expect("every live board is in BOARD_VALUES")
+}
+
+/// The notation of every rotation and reflection of the board.
+fn board_symmetries(notation: &str) -> Vec<String> {
+  let mut tiles: Vec<char> = notation.chars().collect();
+  let mut symmetries = vec![];
+
+  for _ in 0..4 {
+    tiles = transform_tiles(&tiles, |(row, column)| (column, GRID_SIZE - 1 - row));
+
+    symmetries.push(tiles.iter().collect());
+    symmetries.push(
+      transform_tiles(&tiles, |(row, column)| (row, GRID_SIZE - 1 - column))
+        .iter()
+        .collect(),
+    );
+  }
+
+  symmetries
+}
+
+fn transform_tiles(tiles: &[char], transform: impl Fn(Coordinates) -> Coordinates) -> Vec<char> {
+  let mut transformed = vec!['.'; tiles.len()];
+
+  for (index, tile) in tiles.iter().enumerate() {
+    let (row, column) = transform((index / GRID_SIZE, index % GRID_SIZE));
+
+    transformed[row * GRID_SIZE + column] = *tile;
+  }
+
+  transformed
+}
+
+/// A move leaving a position that's lost for the other side, if there is one.
+pub fn winning_move(notakto_game: &NotaktoGame) -> Option<NotaktoMove> {
+  notakto_game.legal_moves().into_iter().find(|notakto_move| {
+    let mut next_game = notakto_game.clone();
+
+    next_game.place(notakto_move).is_ok()
+      && !next_game.is_over()
+      && next_game.value().is_losing_to_move()
+  })
+}
+
+/// Easy places randomly but never kills the last board when it doesn't have to,
+/// Medium only plays perfectly once one board is left and Hard always does.
+pub fn bot_move(notakto_game: &NotaktoGame, difficulty: Difficulty) -> Option<NotaktoMove> {
+  let plays_perfectly = match difficulty {
+    Difficulty::Easy => false,
+    Difficulty::Medium => notakto_game.live_boards().len() == 1,
+    Difficulty::Hard => true,
+  };
+
+  if plays_perfectly {
+    if let Some(notakto_move) = winning_move(notakto_game) {
+      return Some(notakto_move);
+    }
+  }
+
+  let legal_moves = notakto_game.legal_moves();
+
+  let safe_moves: Vec<NotaktoMove> = legal_moves
+    .iter()
+    .copied()
+    .filter(|notakto_move| {
+      let mut next_game = notakto_game.clone();
+
+      next_game.place(notakto_move).is_ok() && !next_game.is_over()
+    })
+    .collect();
+
+  let mut rng = thread_rng();
+
+  safe_moves
+    .choose(&mut rng)
+    .or_else(|| legal_moves.choose(&mut rng))
+    .copied()
+}
+
+/// "2 b2" or "2 5", or just the tile when only one board is live.
+pub fn parse_notakto_move(
+  player_input: &str,
+  notakto_game: &NotaktoGame,
+) -> Result<NotaktoMove, Box<dyn Error>> {
+  let selections: Vec<&str> = player_input.split_whitespace().collect();
+  let live_boards = notakto_game.live_boards();
+
+  match selections.as_slice() {
+    [board, tile] => {
+      let board = board
+        .parse::<usize>()
+        .ok()
+        .filter(|board| *board != 0)
+        .ok_or("boards are numbered from 1")?;
+
+      Ok((board - 1, parse_tile_selection(tile)?))
+    }
+    [tile] if live_boards.len() == 1 => Ok((live_boards[0], parse_tile_selection(tile)?)),
+    [_] => Err(Box::from(
+      "more than one board is live, give the board then the tile e.g. '2 b2'",
+    )),
+    _ => Err(Box::from("incorrect input")),
+  }
+}
+
+/// ```text
+///  board 1        board 2 dead
+///     1  2  3        1  2  3
+///  a  X  .  .     a  X  X  X
+///  b  . [X] .     b  .  .  .
+///  c  .  .  .     c  .  .  .
+/// ```
+pub fn render_notakto(notakto_game: &NotaktoGame) -> String {
+  let board_lines: Vec<Vec<String>> = notakto_game
+    .boards
+    .iter()
+    .enumerate()
+    .map(|(board, gameboard)| {
+      let title = if notakto_game.is_board_dead(board) {
+        format!(" board {} dead", board + 1)
+      } else {
+        format!(" board {}", board + 1)
+      };
+
+      let mut lines = vec![title, "    1  2  3".to_string()];
+
+      for row in 0..GRID_SIZE {
+        let tiles: String = (0..GRID_SIZE)
+          .map(|column| {
+            let symbol = match gameboard.get_board_state(&(row, column)) {
+              BoardStates::Empty => '.',
+              _ => 'X',
+            };
+
+            if notakto_game.last_move == Some((board, (row, column))) {
+              format!("[{symbol}]")
+            } else {
+              format!(" {symbol} ")
+            }
+          })
+          .collect();
+
+        lines.push(format!(" {} {tiles}", (b'a' + row as u8) as char));
+      }
+
+      lines
+    })
+    .collect();
+
+  (0..board_lines[0].len())
+    .map(|line| {
+      board_lines
+        .iter()
+        .map(|lines| format!("{:<15}", lines[line]))
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+        + "\n"
+    })
+    .collect()
+}
+
+pub fn notakto(
+  gameconfig: GameConfig,
+  gamemode_arguments: &[String],
+) -> Result<(), Box<dyn Error>> {
+  let board_count = match gamemode_arguments.first() {
+    Some(board_count) => match board_count.parse::<usize>() {
+      Ok(board_count) if (1..=MAX_BOARD_COUNT).contains(&board_count) => board_count,
+      _ => {
+        return Err(Box::from(format!(
+          "notakto needs 1 to {MAX_BOARD_COUNT} boards, got '{board_count}'"
+        )))
+      }
+    },
+    None => DEFAULT_BOARD_COUNT,
+  };
+
+  notakto_session(gameconfig, board_count, io::stdin().lock(), io::stdout())?;
+
+  Ok(())
+}
+
+/// Plays against the bot, returning true if the player won.
+/// The player places first when gameconfig.player_turn is set.
+pub fn notakto_session<R: BufRead, W: Write>(
+  mut gameconfig: GameConfig,
+  board_count: usize,
+  mut input: R,
+  mut output: W,
+) -> Result<bool, Box<dyn Error>> {
+  let mut notakto_game = NotaktoGame::new(board_count);
+
+  writeln!(
+    output,
+    "Both sides place X, whoever kills the last board loses."
+  )?;
+
+  while !notakto_game.is_over() {
+    write!(output, "\n{}", render_notakto(&notakto_game))?;
+
+    if gameconfig.player_turn {
+      writeln!(output, "Select a board then a tile, e.g. '2 b2' or '2 5'")?;
+
+      let mut player_input = String::new();
+
+      if input.read_line(&mut player_input)? == 0 {
+        return Err(input_ended_error());
+      }
+
+      let placed = parse_notakto_move(&player_input, &notakto_game)
+        .and_then(|notakto_move| notakto_game.place(&notakto_move));
+
+      if let Err(error) = placed {
+        writeln!(output, "{error}")?;
+
+        continue;
+      }
+    } else {
+      let notakto_move =
+        bot_move(&notakto_game, gameconfig.bot.difficulty).ok_or("the bot couldn't find a move")?;
+
+      notakto_game.place(&notakto_move)?;
+
+      writeln!(
+        output,
+        " -- bot placed {} {} --",
+        notakto_move.0 + 1,
+        row_column_notation(&notakto_move.1)
+      )?;
+    }
+
+    gameconfig.player_turn = !gameconfig.player_turn;
+  }
+
+  // The turn has already passed to whoever didn't kill the last board.
+  let player_won = gameconfig.player_turn;
+
+  write!(output, "\n{}", render_notakto(&notakto_game))?;
+  writeln!(
+    output,
+    "{}",
+    if player_won {
+      format!("{} won", gameconfig.player_name)
+    } else {
+      "The bot won".to_string()
+    }
+  )?;
+
+  Ok(player_won)
+}
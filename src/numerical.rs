@@ -0,0 +1,453 @@
+//! Numerical tic-tac-toe, started with the 'numerical' gamemode.
+//!
+//! One side places the odd numbers 1 to 9 and the other the even ones, each
+//! number only once. Whoever fills a row that adds up to 15 wins. Odd places
+//! first, which is the player when gameconfig.player_turn is set. Odd counts
+//! as X in the result.
+//!
+//! Moves are written as the number then the tile, e.g. "7 b2" or "7 at 5".
+//!
+//! Hard searches to the end of the game, which the search in search.rs would
+//! take far too long to do, so best_move keeps the score of every position it
+//! has seen like the solver does.
+
+use crate::coordinate_methods::*;
+use crate::gameboard::ALL_ROWS;
+use crate::gameplay::*;
+use crate::mcts::{Game, Outcome};
+use crate::search::{search_depth, Searchable};
+use crate::session::{play_session, two_player_seats, SessionGame};
+use crate::solver::{DRAW_SCORE, WIN_SCORE};
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+pub const TARGET_SUM: u8 = 15;
+pub const HIGHEST_NUMBER: u8 = 9;
+
+/// The tiles read row by row, in the order each rotation and reflection reads them.
+const SYMMETRIES: [[usize; GRID_SIZE * GRID_SIZE]; 8] = [
+  [0, 1, 2, 3, 4, 5, 6, 7, 8],
+  [6, 3, 0, 7, 4, 1, 8, 5, 2],
+  [8, 7, 6, 5, 4, 3, 2, 1, 0],
+  [2, 5, 8, 1, 4, 7, 0, 3, 6],
+  [2, 1, 0, 5, 4, 3, 8, 7, 6],
+  [6, 7, 8, 3, 4, 5, 0, 1, 2],
+  [0, 3, 6, 1, 4, 7, 2, 5, 8],
+  [8, 5, 2, 7, 4, 1, 6, 3, 0],
+];
+
+/// The number then the tile it's placed on.
+pub type NumericalMove = (u8, Coordinates);
+
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct NumericalGame {
+  pub tiles: [[Option<u8>; GRID_SIZE]; GRID_SIZE],
+  pub odd_to_move: bool,
+  pub last_move: Option<NumericalMove>,
+}
+
+impl NumericalGame {
+  pub fn new() -> Self {
+    NumericalGame {
+      odd_to_move: true,
+      ..NumericalGame::default()
+    }
+  }
+
+  pub fn number_at(&self, coords: &Coordinates) -> Option<u8> {
+    self.tiles[coords.0][coords.1]
+  }
+
+  /// The numbers of that side still to be placed, smallest first.
+  pub fn remaining_numbers(&self, odd: bool) -> Vec<u8> {
+    (1..=HIGHEST_NUMBER)
+      .filter(|number| (number % 2 == 1) == odd)
+      .filter(|number| {
+        !self
+          .tiles
+          .iter()
+          .flatten()
+          .any(|tile| tile == &Some(*number))
+      })
+      .collect()
+  }
+
+  /// A full row adding up to TARGET_SUM.
+  pub fn winning_row(&self) -> Option<[Coordinates; GRID_SIZE]> {
+    ALL_ROWS.into_iter().find(|row| {
+      row
+        .iter()
+        .map(|coords| self.number_at(coords))
+        .collect::<Option<Vec<u8>>>()
+        .is_some_and(|numbers| numbers.iter().sum::<u8>() == TARGET_SUM)
+    })
+  }
+
+  /// None while the game can still be played.
+  pub fn result(&self) -> Option<GameState> {
+    if self.winning_row().is_some() {
+      // The turn has already passed on from whoever filled the row.
+      Some(if self.odd_to_move {
+        GameState::OWon
+      } else {
+        GameState::XWon
+      })
+    } else if self.tiles.iter().flatten().all(Option::is_some) {
+      Some(GameState::Draw)
+    } else {
+      None
+    }
+  }
+
+  pub fn legal_moves(&self) -> Vec<NumericalMove> {
+    if self.result().is_some() {
+      return vec![];
+    }
+
+    let numbers = self.remaining_numbers(self.odd_to_move);
+
+    (0..GRID_SIZE)
+      .flat_map(|row| (0..GRID_SIZE).map(move |column| (row, column)))
+      .filter(|coords| self.number_at(coords).is_none())
+      .flat_map(|coords| numbers.iter().map(move |number| (*number, coords)))
+      .collect()
+  }
+
+  /// Places the number for whichever side is to move, erroring if it isn't allowed.
+  pub fn play(&mut self, numerical_move: &NumericalMove) -> Result<(), Box<dyn Error>> {
+    let (number, coords) = *numerical_move;
+
+    if self.result().is_some() {
+      return Err(Box::from("the game is over"));
+    }
+
+    if !(1..=HIGHEST_NUMBER).contains(&number) {
+      return Err(Box::from(format!("numbers go from 1 to {HIGHEST_NUMBER}")));
+    }
+
+    if (number % 2 == 1) != self.odd_to_move {
+      return Err(Box::from(format!(
+        "{number} isn't yours, you place the {} numbers",
+        if self.odd_to_move { "odd" } else { "even" }
+      )));
+    }
+
+    if !self.remaining_numbers(self.odd_to_move).contains(&number) {
+      return Err(Box::from(format!("{number} has already been placed")));
+    }
+
+    if coords.0 >= GRID_SIZE || coords.1 >= GRID_SIZE {
+      return Err(Box::from("incorrect input"));
+    }
+
+    if self.number_at(&coords).is_some() {
+      return Err(Box::from(format!(
+        "{} is already taken",
+        row_column_notation(&coords)
+      )));
+    }
+
+    self.tiles[coords.0][coords.1] = Some(number);
+    self.odd_to_move = !self.odd_to_move;
+    self.last_move = Some(*numerical_move);
+
+    Ok(())
+  }
+
+  /// A move filling a row up to TARGET_SUM for whoever is to move.
+  pub fn winning_move(&self) -> Option<NumericalMove> {
+    self.completing_moves(self.odd_to_move).first().copied()
+  }
+
+  /// Every move that side could fill a row up to TARGET_SUM with, if it were their turn.
+  pub fn completing_moves(&self, odd: bool) -> Vec<NumericalMove> {
+    let numbers = self.remaining_numbers(odd);
+
+    ALL_ROWS
+      .into_iter()
+      .filter_map(|row| {
+        let empty: Vec<&Coordinates> = row
+          .iter()
+          .filter(|coords| self.number_at(coords).is_none())
+          .collect();
+        let sum: u8 = row.iter().filter_map(|coords| self.number_at(coords)).sum();
+
+        match empty.as_slice() {
+          [coords] => TARGET_SUM
+            .checked_sub(sum)
+            .filter(|needed| numbers.contains(needed))
+            .map(|needed| (needed, **coords)),
+          _ => None,
+        }
+      })
+      .collect()
+  }
+
+  /// Every tile packed four bits each, for keeping scores by position.
+  /// Rotated and reflected boards score the same, so they share the smallest key.
+  fn key(&self) -> u64 {
+    let tiles: Vec<u8> = self
+      .tiles
+      .iter()
+      .flatten()
+      .map(|tile| tile.unwrap_or(0))
+      .collect();
+
+    SYMMETRIES
+      .iter()
+      .map(|order| {
+        order
+          .iter()
+          .fold(0, |key, index| key << 4 | tiles[*index] as u64)
+      })
+      .min()
+      .unwrap_or_default()
+  }
+}
+
+/// "7 b2", "7 at b2" or "7 5".
+pub fn parse_numerical_move(player_input: &str) -> Result<NumericalMove, Box<dyn Error>> {
+  let selections: Vec<&str> = player_input
+    .split_whitespace()
+    .filter(|selection| !selection.eq_ignore_ascii_case("at"))
+    .collect();
+
+  match selections.as_slice() {
+    [number, tile] => {
+      let number = number
+        .parse::<u8>()
+        .map_err(|_| format!("'{number}' isn't a number"))?;
+
+      Ok((number, parse_tile_selection(tile)?))
+    }
+    _ => Err(Box::from("give a number then a tile, e.g. '7 b2'")),
+  }
+}
+
+/// The best move for whoever is to move looking depth moves ahead, the first one found on a tie.
+pub fn best_move(numerical_game: &NumericalGame, depth: u32) -> Option<NumericalMove> {
+  let mut scores = HashMap::new();
+  let mut best = None;
+
+  for numerical_move in numerical_game.legal_moves() {
+    let mut next_game = numerical_game.clone();
+    next_game.play(&numerical_move).ok()?;
+
+    let score = -negamax(&next_game, depth.saturating_sub(1), &mut scores);
+
+    if best.is_none_or(|(_, best_score)| score > best_score) {
+      best = Some((numerical_move, score));
+    }
+
+    if score == WIN_SCORE {
+      break;
+    }
+  }
+
+  best.map(|(numerical_move, _)| numerical_move)
+}
+
+/// Scores are kept by position and depth, depth being part of it since a
+/// shallow search can miss what a deeper one finds.
+fn negamax(numerical_game: &NumericalGame, depth: u32, scores: &mut HashMap<(u64, u32), i8>) -> i8 {
+  // Only whoever just moved can have filled a row.
+  if numerical_game.winning_row().is_some() {
+    return -WIN_SCORE;
+  }
+
+  if depth == 0 {
+    return DRAW_SCORE;
+  }
+
+  if numerical_game.winning_move().is_some() {
+    return WIN_SCORE;
+  }
+
+  let key = (numerical_game.key(), depth);
+
+  if let Some(score) = scores.get(&key) {
+    return *score;
+  }
+
+  // Wherever the other side could fill a row next has to be taken now.
+  let mut forced_tiles: Vec<Coordinates> = numerical_game
+    .completing_moves(!numerical_game.odd_to_move)
+    .into_iter()
+    .map(|(_, coords)| coords)
+    .collect();
+  forced_tiles.sort();
+  forced_tiles.dedup();
+
+  if forced_tiles.len() > 1 && depth > 1 {
+    return -WIN_SCORE;
+  }
+
+  let mut best_score = None;
+
+  for numerical_move in numerical_game.legal_moves() {
+    if forced_tiles
+      .first()
+      .is_some_and(|coords| coords != &numerical_move.1)
+    {
+      continue;
+    }
+
+    let mut next_game = numerical_game.clone();
+    next_game.play(&numerical_move).ok();
+
+    let score = -negamax(&next_game, depth - 1, scores);
+
+    best_score = best_score.max(Some(score));
+
+    if score == WIN_SCORE {
+      break;
+    }
+  }
+
+  let score = best_score.unwrap_or(DRAW_SCORE);
+  scores.insert(key, score);
+
+  score
+}
+
+/// ```text
+///      1   2   3
+///  a   7 | . | .
+///     ---+---+---
+///  b   . | 4 | .
+///     ---+---+---
+///  c   . | . | .
+///  Odd left: 1 3 5 9 | Even left: 2 6 8
+/// ```
+pub fn render_numerical(numerical_game: &NumericalGame) -> String {
+  let mut rendered = String::from("     1   2   3\n");
+
+  for row in 0..GRID_SIZE {
+    if row != 0 {
+      rendered += "    ---+---+---\n";
+    }
+
+    let tiles: Vec<String> = (0..GRID_SIZE)
+      .map(|column| match numerical_game.number_at(&(row, column)) {
+        Some(number) => number.to_string(),
+        None => ".".to_string(),
+      })
+      .collect();
+
+    rendered += &format!(" {}   {}\n", (b'a' + row as u8) as char, tiles.join(" | "));
+  }
+
+  let numbers_left = |odd: bool| {
+    numerical_game
+      .remaining_numbers(odd)
+      .iter()
+      .map(u8::to_string)
+      .collect::<Vec<String>>()
+      .join(" ")
+  };
+
+  rendered
+    + &format!(
+      " Odd left: {} | Even left: {}\n",
+      numbers_left(true),
+      numbers_left(false)
+    )
+}
+
+impl Game for NumericalGame {
+  type Move = NumericalMove;
+
+  /// Odd is 0.
+  fn current_player(&self) -> usize {
+    if self.odd_to_move {
+      0
+    } else {
+      1
+    }
+  }
+
+  fn legal_moves(&self) -> Vec<NumericalMove> {
+    NumericalGame::legal_moves(self)
+  }
+
+  fn apply(&mut self, numerical_move: &NumericalMove) {
+    self
+      .play(numerical_move)
+      .expect("only legal moves are played");
+  }
+
+  fn outcome(&self) -> Option<Outcome> {
+    self.result().map(Outcome::from)
+  }
+}
+
+/// Only for SEARCH_DEPTHS, best_move doesn't go through search.rs. Hard searches to the end.
+impl Searchable for NumericalGame {
+  const SEARCH_DEPTHS: [u32; 3] = [1, 2, (GRID_SIZE * GRID_SIZE) as u32];
+
+  /// Nothing but a filled row is worth anything.
+  fn evaluate(&self, _player: usize) -> i32 {
+    DRAW_SCORE as i32
+  }
+}
+
+impl SessionGame for NumericalGame {
+  type Result = GameState;
+
+  fn render(&self) -> String {
+    render_numerical(self)
+  }
+
+  fn session_result(&self) -> Option<GameState> {
+    self.result()
+  }
+
+  fn prompt(&self) -> String {
+    "select a number then a tile, e.g. '7 b2' or '7 5'".to_string()
+  }
+
+  fn play_input(&mut self, player_input: &str) -> Result<(), Box<dyn Error>> {
+    self.play(&parse_numerical_move(player_input)?)
+  }
+
+  fn describe_move(&self, numerical_move: &NumericalMove) -> String {
+    format!(
+      "placed {} on {}",
+      numerical_move.0,
+      row_column_notation(&numerical_move.1)
+    )
+  }
+}
+
+pub fn numerical(gameconfig: GameConfig) -> Result<(), Box<dyn Error>> {
+  numerical_session(gameconfig, io::stdin().lock(), io::stdout())?;
+
+  Ok(())
+}
+
+/// The player places the odd numbers, and so places first, when gameconfig.player_turn is set.
+pub fn numerical_session<R: BufRead, W: Write>(
+  gameconfig: GameConfig,
+  input: R,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
+  let player_is_odd = gameconfig.player_turn;
+  let depth = search_depth::<NumericalGame>(gameconfig.bot.difficulty);
+
+  writeln!(
+    output,
+    "{} {} | Bot {} | A row adding up to {TARGET_SUM} wins",
+    gameconfig.player_name,
+    if player_is_odd { "odd" } else { "even" },
+    if player_is_odd { "even" } else { "odd" }
+  )?;
+
+  play_session(
+    NumericalGame::new(),
+    &two_player_seats(&gameconfig.player_name, if player_is_odd { 0 } else { 1 }),
+    |numerical_game| best_move(numerical_game, depth),
+    input,
+    output,
+  )
+}
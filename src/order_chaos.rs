@@ -0,0 +1,369 @@
+//! Order and chaos, started with `order_chaos [order|chaos]` to pick your side.
+//!
+//! Played on a 6x6 board where both sides place X or O, whichever they like.
+//! Order wins with five in a row of either symbol and chaos wins by filling
+//! the board without one. Order places first, and you play order by default.
+//!
+//! The sides want such different things that the bot judges positions
+//! differently for each. As order it looks for lines that can still be made,
+//! as chaos for lines it has ruined by mixing symbols into them.
+//!
+//! Moves are written as the symbol then the tile, e.g. "o c4".
+
+use crate::coordinate_methods::Coordinates;
+use crate::gameboard::BoardStates;
+use crate::gameplay::*;
+use crate::grid_board::*;
+use crate::mcts::{Game, Outcome};
+use crate::search::{best_move, line_score, search_depth, total_evaluation, Searchable};
+use crate::session::{play_session, two_player_seats, SessionGame};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+pub const BOARD_SIZE: usize = 6;
+pub const WIN_LENGTH: usize = 5;
+
+/// What chaos gets for each line with both symbols in, which can never be made.
+pub const DEAD_LINE_SCORE: i32 = 50;
+
+const ORDER_CHAOS_USAGE: &str =
+  "expected 'order_chaos', 'order_chaos order' or 'order_chaos chaos'";
+
+/// The symbol to place then where.
+pub type OrderChaosMove = (BoardStates, Coordinates);
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Role {
+  Order,
+  Chaos,
+}
+
+impl Role {
+  pub fn opponent(&self) -> Role {
+    match self {
+      Role::Order => Role::Chaos,
+      Role::Chaos => Role::Order,
+    }
+  }
+
+  /// Which player the role is as mcts::Game numbers them, order being 0.
+  pub fn player(&self) -> usize {
+    match self {
+      Role::Order => 0,
+      Role::Chaos => 1,
+    }
+  }
+
+  pub fn of_player(player: usize) -> Role {
+    match player {
+      0 => Role::Order,
+      _ => Role::Chaos,
+    }
+  }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum OrderChaosResult {
+  /// Five in a row was made.
+  OrderWon,
+  /// The board filled without five in a row.
+  ChaosWon,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct OrderChaosGame {
+  pub grid_board: GridBoard,
+  pub next_role: Role,
+  lines: Vec<Vec<Coordinates>>,
+}
+
+impl OrderChaosGame {
+  pub fn new() -> Self {
+    let grid_board = GridBoard::new(BOARD_SIZE, BOARD_SIZE, WIN_LENGTH).unwrap();
+
+    OrderChaosGame {
+      lines: grid_board.lines(),
+      grid_board,
+      next_role: Role::Order,
+    }
+  }
+
+  /// None while the game can still be played.
+  pub fn result(&self) -> Option<OrderChaosResult> {
+    let made_line = self
+      .lines
+      .iter()
+      .any(|line| matches!(self.line_counts(line), (WIN_LENGTH, 0) | (0, WIN_LENGTH)));
+
+    if made_line {
+      Some(OrderChaosResult::OrderWon)
+    } else if self.grid_board.all_tiles_covered() {
+      Some(OrderChaosResult::ChaosWon)
+    } else {
+      None
+    }
+  }
+
+  /// Places the move for next_role then passes the turn on, erroring if the tile isn't free.
+  pub fn place(&mut self, order_chaos_move: &OrderChaosMove) -> Result<(), Box<dyn Error>> {
+    let (symbol, coords) = *order_chaos_move;
+
+    if self.result().is_some() {
+      return Err(Box::from("the game is over"));
+    }
+
+    if !symbol.is_symbol() {
+      return Err(Box::from("pick a symbol to place, x or o"));
+    }
+
+    if self.grid_board.get_board_state(&coords) != &BoardStates::Empty {
+      return Err(Box::from(format!(
+        "{} is already taken",
+        row_column_notation(&coords)
+      )));
+    }
+
+    self.grid_board.place_tile(&coords, symbol);
+    self.next_role = self.next_role.opponent();
+
+    Ok(())
+  }
+
+  /// How many X and O are on the line.
+  fn line_counts(&self, line: &[Coordinates]) -> (usize, usize) {
+    line.iter().fold((0, 0), |(x, o), coords| {
+      match self.grid_board.get_board_state(coords) {
+        BoardStates::X => (x + 1, o),
+        BoardStates::O => (x, o + 1),
+        _ => (x, o),
+      }
+    })
+  }
+
+  /// Lines with only one kind of symbol in, or none, which order could still make.
+  pub fn live_lines(&self) -> Vec<&Vec<Coordinates>> {
+    self
+      .lines
+      .iter()
+      .filter(|line| {
+        let (x, o) = self.line_counts(line);
+
+        x == 0 || o == 0
+      })
+      .collect()
+  }
+
+  /// Both symbols on every empty tile a live line goes through. Once there
+  /// are none where a move goes doesn't matter, so only one is given.
+  pub fn candidate_moves(&self) -> Vec<OrderChaosMove> {
+    let live_lines = self.live_lines();
+
+    let mut tiles: Vec<Coordinates> = self
+      .grid_board
+      .empty_tiles()
+      .into_iter()
+      .filter(|coords| live_lines.iter().any(|line| line.contains(coords)))
+      .collect();
+
+    if tiles.is_empty() {
+      tiles = self.grid_board.empty_tiles().into_iter().take(1).collect();
+    }
+
+    tiles
+      .into_iter()
+      .flat_map(|coords| [(BoardStates::X, coords), (BoardStates::O, coords)])
+      .collect()
+  }
+
+  /// Every line that can still be made, worth more the closer it is to done.
+  pub fn evaluate_order(&self) -> i32 {
+    total_evaluation(self.lines.iter().map(|line| match self.line_counts(line) {
+      (0, count) | (count, 0) => line_score(count),
+      _ => 0,
+    }))
+  }
+
+  /// Every ruined line, minus any that are getting close to done.
+  pub fn evaluate_chaos(&self) -> i32 {
+    total_evaluation(self.lines.iter().map(|line| match self.line_counts(line) {
+      (0, count) | (count, 0) if count >= 3 => -line_score(count),
+      (0, _) | (_, 0) => 0,
+      _ => DEAD_LINE_SCORE as i64,
+    }))
+  }
+
+  pub fn evaluate(&self, role: Role) -> i32 {
+    match role {
+      Role::Order => self.evaluate_order(),
+      Role::Chaos => self.evaluate_chaos(),
+    }
+  }
+}
+
+impl Default for OrderChaosGame {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// "o c4" or "c4 o", checked against the board's size.
+pub fn parse_order_chaos_move(
+  player_input: &str,
+  grid_board: &GridBoard,
+) -> Result<OrderChaosMove, Box<dyn Error>> {
+  let selections: Vec<String> = player_input
+    .split_whitespace()
+    .map(str::to_lowercase)
+    .collect();
+
+  let parse_symbol = |symbol: &str| match symbol {
+    "x" => Some(BoardStates::X),
+    "o" => Some(BoardStates::O),
+    _ => None,
+  };
+
+  match selections.as_slice() {
+    [first, second] => {
+      if let Some(symbol) = parse_symbol(first) {
+        Ok((symbol, parse_grid_tile(second, grid_board)?))
+      } else if let Some(symbol) = parse_symbol(second) {
+        Ok((symbol, parse_grid_tile(first, grid_board)?))
+      } else {
+        Err(Box::from("pick a symbol to place, x or o"))
+      }
+    }
+    [_] => Err(Box::from(
+      "give the symbol to place as well as the tile, e.g. 'o c4'",
+    )),
+    _ => Err(Box::from("incorrect input")),
+  }
+}
+
+impl Game for OrderChaosGame {
+  type Move = OrderChaosMove;
+
+  fn current_player(&self) -> usize {
+    self.next_role.player()
+  }
+
+  fn legal_moves(&self) -> Vec<OrderChaosMove> {
+    if self.outcome().is_some() {
+      return vec![];
+    }
+
+    self
+      .grid_board
+      .empty_tiles()
+      .into_iter()
+      .flat_map(|coords| [(BoardStates::X, coords), (BoardStates::O, coords)])
+      .collect()
+  }
+
+  fn apply(&mut self, order_chaos_move: &OrderChaosMove) {
+    self
+      .place(order_chaos_move)
+      .expect("only legal moves are placed");
+  }
+
+  /// With every line ruined chaos has won, even if the board isn't full yet.
+  fn outcome(&self) -> Option<Outcome> {
+    match self.result() {
+      Some(OrderChaosResult::OrderWon) => Some(Outcome::Won(Role::Order.player())),
+      Some(OrderChaosResult::ChaosWon) => Some(Outcome::Won(Role::Chaos.player())),
+      None if self.live_lines().is_empty() => Some(Outcome::Won(Role::Chaos.player())),
+      None => None,
+    }
+  }
+}
+
+/// Each role judges positions its own way, see evaluate_order and evaluate_chaos.
+impl Searchable for OrderChaosGame {
+  const SEARCH_DEPTHS: [u32; 3] = [1, 2, 3];
+
+  fn evaluate(&self, player: usize) -> i32 {
+    OrderChaosGame::evaluate(self, Role::of_player(player))
+  }
+
+  fn search_moves(&self) -> Vec<OrderChaosMove> {
+    self.candidate_moves()
+  }
+}
+
+impl SessionGame for OrderChaosGame {
+  type Result = OrderChaosResult;
+
+  fn render(&self) -> String {
+    render_grid(&self.grid_board)
+  }
+
+  fn session_result(&self) -> Option<OrderChaosResult> {
+    self.result()
+  }
+
+  fn prompt(&self) -> String {
+    "select a symbol then a tile, e.g. 'o c4'".to_string()
+  }
+
+  fn play_input(&mut self, player_input: &str) -> Result<(), Box<dyn Error>> {
+    let order_chaos_move = parse_order_chaos_move(player_input, &self.grid_board)?;
+
+    self.place(&order_chaos_move)
+  }
+
+  fn describe_move(&self, order_chaos_move: &OrderChaosMove) -> String {
+    format!(
+      "placed {:?} on {}",
+      order_chaos_move.0,
+      row_column_notation(&order_chaos_move.1)
+    )
+  }
+}
+
+pub fn order_chaos(
+  gameconfig: GameConfig,
+  gamemode_arguments: &[String],
+) -> Result<(), Box<dyn Error>> {
+  let role = match gamemode_arguments {
+    [] => Role::Order,
+    [role] if role.eq_ignore_ascii_case("order") => Role::Order,
+    [role] if role.eq_ignore_ascii_case("chaos") => Role::Chaos,
+    _ => return Err(Box::from(ORDER_CHAOS_USAGE)),
+  };
+
+  order_chaos_session(
+    gameconfig,
+    OrderChaosGame::new(),
+    role,
+    io::stdin().lock(),
+    io::stdout(),
+  )?;
+
+  Ok(())
+}
+
+/// The player places as role and the bot as the other side.
+pub fn order_chaos_session<R: BufRead, W: Write>(
+  gameconfig: GameConfig,
+  order_chaos_game: OrderChaosGame,
+  role: Role,
+  input: R,
+  mut output: W,
+) -> Result<OrderChaosResult, Box<dyn Error>> {
+  let depth = search_depth::<OrderChaosGame>(gameconfig.bot.difficulty);
+
+  writeln!(
+    output,
+    "{} {:?} | Bot {:?} | Place X or O, {WIN_LENGTH} in a row wins for order, a full board for chaos",
+    gameconfig.player_name,
+    role,
+    role.opponent()
+  )?;
+
+  play_session(
+    order_chaos_game,
+    &two_player_seats(&gameconfig.player_name, role.player()),
+    |order_chaos_game| best_move(order_chaos_game, depth),
+    input,
+    output,
+  )
+}
@@ -0,0 +1,579 @@
+//! Quantum tic-tac-toe, started with the 'quantum' gamemode.
+//!
+//! Each move places a spooky mark in two tiles at once, written with the
+//! move's number as x₁ or o₂. The marks link their two tiles, and when a new
+//! mark links two tiles that were already linked it has closed a cycle. The
+//! other side then picks which of its two tiles it collapses into, which
+//! forces every mark linked to it into one tile too. Collapsed marks are
+//! classical and can't move again.
+//!
+//! Three classical marks in a row wins. If a collapse finishes rows for both
+//! sides, the row whose newest mark is oldest wins. Once only one tile isn't
+//! classical the last move is a classical mark there.
+//!
+//! Spooky marks are written as their two tiles, "a1 b2" or "1 5", and
+//! collapses and classical marks as one tile.
+//!
+//! Which marks end up where is too hard to judge by hand, so past Easy the bot
+//! uses the Monte Carlo tree search in mcts.rs.
+
+use crate::bot::Difficulty;
+use crate::coordinate_methods::*;
+use crate::gameboard::{BoardStates, ALL_ROWS};
+use crate::gameplay::*;
+use crate::mcts::{self, player_of, Game, MctsBudget, Outcome};
+use crate::session::{play_session, two_player_seats, SessionGame};
+use crate::solver::opposing_symbol;
+use rand::prelude::*;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+const SUBSCRIPTS: [char; 10] = ['₀', '₁', '₂', '₃', '₄', '₅', '₆', '₇', '₈', '₉'];
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct SpookyMark {
+  pub symbol: BoardStates,
+  pub move_number: u8,
+  pub tiles: [Coordinates; 2],
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum QuantumMove {
+  Spooky(Coordinates, Coordinates),
+  /// Where the mark that closed a cycle collapses into.
+  Collapse(Coordinates),
+  /// Only allowed on the last tile that isn't classical.
+  Classical(Coordinates),
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct QuantumGame {
+  /// Marks still between two tiles.
+  pub spooky_marks: Vec<SpookyMark>,
+  /// The symbol and move number of the mark each tile collapsed to.
+  pub classical_marks: [[Option<(BoardStates, u8)>; GRID_SIZE]; GRID_SIZE],
+  pub next_symbol: BoardStates,
+  pub move_number: u8,
+  /// The mark that closed a cycle, next_symbol picks where it collapses before placing.
+  pub pending_collapse: Option<SpookyMark>,
+}
+
+impl QuantumGame {
+  pub fn new() -> Self {
+    QuantumGame {
+      spooky_marks: vec![],
+      classical_marks: [[None; GRID_SIZE]; GRID_SIZE],
+      next_symbol: BoardStates::X,
+      move_number: 0,
+      pending_collapse: None,
+    }
+  }
+
+  pub fn classical_at(&self, coords: &Coordinates) -> Option<(BoardStates, u8)> {
+    self.classical_marks[coords.0][coords.1]
+  }
+
+  /// Tiles that aren't classical yet.
+  pub fn free_tiles(&self) -> Vec<Coordinates> {
+    all_coordinates()
+      .into_iter()
+      .filter(|coords| self.classical_at(coords).is_none())
+      .collect()
+  }
+
+  /// Whether spooky marks already link the two tiles, so a mark between them closes a cycle.
+  pub fn is_linked(&self, from: &Coordinates, to: &Coordinates) -> bool {
+    let mut visited = vec![*from];
+    let mut to_visit = VecDeque::from([*from]);
+
+    while let Some(coords) = to_visit.pop_front() {
+      if coords == *to {
+        return true;
+      }
+
+      for spooky_mark in &self.spooky_marks {
+        let next = match spooky_mark.tiles {
+          [first, second] if first == coords => second,
+          [first, second] if second == coords => first,
+          _ => continue,
+        };
+
+        if !visited.contains(&next) {
+          visited.push(next);
+          to_visit.push_back(next);
+        }
+      }
+    }
+
+    false
+  }
+
+  /// Every classical row with the symbol in it and its newest move number.
+  pub fn completed_rows(&self) -> Vec<(BoardStates, u8)> {
+    ALL_ROWS
+      .iter()
+      .filter_map(|row| {
+        let marks = row
+          .iter()
+          .map(|coords| self.classical_at(coords))
+          .collect::<Option<Vec<(BoardStates, u8)>>>()?;
+
+        let symbol = marks[0].0;
+
+        marks
+          .iter()
+          .all(|(mark_symbol, _)| *mark_symbol == symbol)
+          .then(|| {
+            (
+              symbol,
+              marks.iter().map(|(_, number)| *number).max().unwrap_or(0),
+            )
+          })
+      })
+      .collect()
+  }
+
+  /// None while the game can still be played.
+  pub fn result(&self) -> Option<GameState> {
+    let first_row = self
+      .completed_rows()
+      .into_iter()
+      .min_by_key(|(_, newest_move)| *newest_move);
+
+    match first_row {
+      Some((BoardStates::X, _)) => Some(GameState::XWon),
+      Some((BoardStates::O, _)) => Some(GameState::OWon),
+      _ if self.free_tiles().is_empty() => Some(GameState::Draw),
+      _ => None,
+    }
+  }
+
+  pub fn legal_moves(&self) -> Vec<QuantumMove> {
+    if self.result().is_some() {
+      return vec![];
+    }
+
+    if let Some(spooky_mark) = self.pending_collapse {
+      return spooky_mark.tiles.map(QuantumMove::Collapse).to_vec();
+    }
+
+    let free_tiles = self.free_tiles();
+
+    if let [last_tile] = free_tiles.as_slice() {
+      return vec![QuantumMove::Classical(*last_tile)];
+    }
+
+    free_tiles
+      .iter()
+      .enumerate()
+      .flat_map(|(index, first)| {
+        free_tiles[index + 1..]
+          .iter()
+          .map(|second| QuantumMove::Spooky(*first, *second))
+      })
+      .collect()
+  }
+
+  /// Plays the move for next_symbol, erroring if it isn't allowed.
+  pub fn play(&mut self, quantum_move: &QuantumMove) -> Result<(), Box<dyn Error>> {
+    if self.result().is_some() {
+      return Err(Box::from("the game is over"));
+    }
+
+    match (*quantum_move, self.pending_collapse) {
+      (QuantumMove::Collapse(coords), Some(spooky_mark)) => {
+        if !spooky_mark.tiles.contains(&coords) {
+          return Err(Box::from(format!(
+            "{} can only collapse into {}",
+            mark_notation(&spooky_mark),
+            tiles_notation(&spooky_mark.tiles)
+          )));
+        }
+
+        self.collapse(&spooky_mark, &coords);
+        self.pending_collapse = None;
+
+        Ok(())
+      }
+      (_, Some(spooky_mark)) => Err(Box::from(format!(
+        "{} closed a cycle, pick which of {} it collapses into first",
+        mark_notation(&spooky_mark),
+        tiles_notation(&spooky_mark.tiles)
+      ))),
+      (QuantumMove::Collapse(_), None) => Err(Box::from("there's nothing to collapse")),
+      (QuantumMove::Spooky(first, second), None) => self.place_spooky_mark([first, second]),
+      (QuantumMove::Classical(coords), None) => self.place_classical_mark(&coords),
+    }
+  }
+
+  fn place_spooky_mark(&mut self, tiles: [Coordinates; 2]) -> Result<(), Box<dyn Error>> {
+    for coords in &tiles {
+      self.check_free(coords)?;
+    }
+
+    if tiles[0] == tiles[1] {
+      return Err(Box::from("a spooky mark needs two different tiles"));
+    }
+
+    if let [last_tile] = self.free_tiles().as_slice() {
+      return Err(Box::from(format!(
+        "only {} is left, place a classical mark there",
+        row_column_notation(last_tile)
+      )));
+    }
+
+    self.move_number += 1;
+
+    let spooky_mark = SpookyMark {
+      symbol: self.next_symbol,
+      move_number: self.move_number,
+      tiles,
+    };
+
+    if self.is_linked(&tiles[0], &tiles[1]) {
+      self.pending_collapse = Some(spooky_mark);
+    }
+
+    self.spooky_marks.push(spooky_mark);
+    self.next_symbol = opposing_symbol(self.next_symbol);
+
+    Ok(())
+  }
+
+  fn place_classical_mark(&mut self, coords: &Coordinates) -> Result<(), Box<dyn Error>> {
+    self.check_free(coords)?;
+
+    if self.free_tiles().len() > 1 {
+      return Err(Box::from(
+        "classical marks only go on the last tile left, place a spooky mark on two tiles",
+      ));
+    }
+
+    self.move_number += 1;
+    self.classical_marks[coords.0][coords.1] = Some((self.next_symbol, self.move_number));
+    self.next_symbol = opposing_symbol(self.next_symbol);
+
+    Ok(())
+  }
+
+  /// Sends the mark into coords, every other mark on coords into its other tile, and so on.
+  fn collapse(&mut self, spooky_mark: &SpookyMark, coords: &Coordinates) {
+    let mut to_collapse = VecDeque::from([(spooky_mark.move_number, *coords)]);
+
+    while let Some((move_number, coords)) = to_collapse.pop_front() {
+      let Some(index) = self
+        .spooky_marks
+        .iter()
+        .position(|spooky_mark| spooky_mark.move_number == move_number)
+      else {
+        continue;
+      };
+
+      let spooky_mark = self.spooky_marks.remove(index);
+
+      if self.classical_at(&coords).is_some() {
+        continue;
+      }
+
+      self.classical_marks[coords.0][coords.1] = Some((spooky_mark.symbol, move_number));
+
+      for other_mark in &self.spooky_marks {
+        match other_mark.tiles {
+          [first, second] if first == coords => {
+            to_collapse.push_back((other_mark.move_number, second))
+          }
+          [first, second] if second == coords => {
+            to_collapse.push_back((other_mark.move_number, first))
+          }
+          _ => (),
+        }
+      }
+    }
+  }
+
+  fn check_free(&self, coords: &Coordinates) -> Result<(), Box<dyn Error>> {
+    if coords.0 >= GRID_SIZE || coords.1 >= GRID_SIZE {
+      return Err(Box::from("incorrect input"));
+    }
+
+    if self.classical_at(coords).is_some() {
+      return Err(Box::from(format!(
+        "{} is already classical",
+        row_column_notation(coords)
+      )));
+    }
+
+    Ok(())
+  }
+}
+
+impl Default for QuantumGame {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// x₃ for spooky marks, with `classical` X₃.
+pub fn symbol_notation(symbol: BoardStates, move_number: u8, classical: bool) -> String {
+  let letter = match (symbol, classical) {
+    (BoardStates::X, true) => "X",
+    (BoardStates::X, false) => "x",
+    (BoardStates::O, true) => "O",
+    (BoardStates::O, false) => "o",
+    (BoardStates::Empty | BoardStates::Blocked, _) => "",
+  };
+
+  let subscript: String = move_number
+    .to_string()
+    .chars()
+    .filter_map(|digit| digit.to_digit(10))
+    .map(|digit| SUBSCRIPTS[digit as usize])
+    .collect();
+
+  format!("{letter}{subscript}")
+}
+
+pub fn mark_notation(spooky_mark: &SpookyMark) -> String {
+  symbol_notation(spooky_mark.symbol, spooky_mark.move_number, false)
+}
+
+fn tiles_notation(tiles: &[Coordinates; 2]) -> String {
+  format!(
+    "{} or {}",
+    row_column_notation(&tiles[0]),
+    row_column_notation(&tiles[1])
+  )
+}
+
+/// Two tiles are a spooky mark, one tile is a collapse or a classical mark
+/// depending on what the game is waiting on.
+pub fn parse_quantum_move(
+  player_input: &str,
+  quantum_game: &QuantumGame,
+) -> Result<QuantumMove, Box<dyn Error>> {
+  let selections: Vec<&str> = player_input.split_whitespace().collect();
+
+  match selections.as_slice() {
+    [first, second] => Ok(QuantumMove::Spooky(
+      parse_tile_selection(first)?,
+      parse_tile_selection(second)?,
+    )),
+    [tile] if quantum_game.pending_collapse.is_some() => {
+      Ok(QuantumMove::Collapse(parse_tile_selection(tile)?))
+    }
+    [tile] if quantum_game.free_tiles().len() == 1 => {
+      Ok(QuantumMove::Classical(parse_tile_selection(tile)?))
+    }
+    [_] => Err(Box::from("a spooky mark needs two tiles, e.g. 'a1 b2'")),
+    _ => Err(Box::from("incorrect input")),
+  }
+}
+
+pub fn quantum_move_notation(quantum_move: &QuantumMove) -> String {
+  match quantum_move {
+    QuantumMove::Spooky(first, second) => {
+      format!(
+        "{} {}",
+        row_column_notation(first),
+        row_column_notation(second)
+      )
+    }
+    QuantumMove::Collapse(coords) | QuantumMove::Classical(coords) => row_column_notation(coords),
+  }
+}
+
+/// ```text
+///       1        2        3
+///  a  X₁     | x₂ o₃  |
+///    --------+--------+--------
+///  b  x₂     |        | o₃
+///    --------+--------+--------
+///  c         |        |
+/// ```
+pub fn render_quantum(quantum_game: &QuantumGame) -> String {
+  let tile_text = |coords: &Coordinates| match quantum_game.classical_at(coords) {
+    Some((symbol, move_number)) => symbol_notation(symbol, move_number, true),
+    None => quantum_game
+      .spooky_marks
+      .iter()
+      .filter(|spooky_mark| spooky_mark.tiles.contains(coords))
+      .map(mark_notation)
+      .collect::<Vec<String>>()
+      .join(" "),
+  };
+
+  let texts: Vec<Vec<String>> = (0..GRID_SIZE)
+    .map(|row| {
+      (0..GRID_SIZE)
+        .map(|column| tile_text(&(row, column)))
+        .collect()
+    })
+    .collect();
+
+  // Each tile is a space, its marks, and at least one more space.
+  let width = texts
+    .iter()
+    .flatten()
+    .map(|text| text.chars().count() + 2)
+    .max()
+    .unwrap_or(0)
+    .max(8);
+
+  let pad = |text: &str| format!(" {text}{}", " ".repeat(width - 1 - text.chars().count()));
+
+  let header: Vec<String> = (1..=GRID_SIZE)
+    .map(|column| format!("{column:^width$}"))
+    .collect();
+
+  let mut rendered = format!("   {}\n", header.join(" ").trim_end());
+  let separator = vec!["-".repeat(width); GRID_SIZE].join("+");
+
+  for (row, texts) in texts.iter().enumerate() {
+    if row != 0 {
+      rendered += &format!("   {separator}\n");
+    }
+
+    let tiles: Vec<String> = texts.iter().map(|text| pad(text)).collect();
+
+    rendered += &format!(
+      " {} {}\n",
+      (b'a' + row as u8) as char,
+      tiles.join("|").trim_end()
+    );
+  }
+
+  if let Some(spooky_mark) = quantum_game.pending_collapse {
+    rendered += &format!(
+      " {} closed a cycle, {:?} picks whether it collapses into {}\n",
+      mark_notation(&spooky_mark),
+      quantum_game.next_symbol,
+      tiles_notation(&spooky_mark.tiles)
+    );
+  }
+
+  rendered
+}
+
+/// How long the tree search goes on for, None for Easy which places randomly.
+pub fn mcts_budget(difficulty: Difficulty) -> Option<MctsBudget> {
+  match difficulty {
+    Difficulty::Easy => None,
+    Difficulty::Medium => Some(MctsBudget::Iterations(1_000)),
+    Difficulty::Hard => Some(MctsBudget::Iterations(5_000)),
+  }
+}
+
+/// The move the tree search likes best for next_symbol, a random one on Easy.
+pub fn best_move(quantum_game: &QuantumGame, difficulty: Difficulty) -> Option<QuantumMove> {
+  match mcts_budget(difficulty) {
+    Some(budget) => mcts::best_move(quantum_game, budget, &mut thread_rng()),
+    None => quantum_game
+      .legal_moves()
+      .choose(&mut thread_rng())
+      .copied(),
+  }
+}
+
+/// A collapse doesn't pass the turn, so whoever picks it places next as well.
+impl Game for QuantumGame {
+  type Move = QuantumMove;
+
+  fn current_player(&self) -> usize {
+    player_of(self.next_symbol)
+  }
+
+  fn legal_moves(&self) -> Vec<QuantumMove> {
+    QuantumGame::legal_moves(self)
+  }
+
+  fn apply(&mut self, quantum_move: &QuantumMove) {
+    self
+      .play(quantum_move)
+      .expect("only legal moves are played");
+  }
+
+  fn outcome(&self) -> Option<Outcome> {
+    self.result().map(Outcome::from)
+  }
+}
+
+impl SessionGame for QuantumGame {
+  type Result = GameState;
+
+  fn render(&self) -> String {
+    render_quantum(self)
+  }
+
+  fn session_result(&self) -> Option<GameState> {
+    self.result()
+  }
+
+  fn prompt(&self) -> String {
+    match (self.pending_collapse, self.free_tiles().as_slice()) {
+      (Some(spooky_mark), _) => format!(
+        "select where {} collapses, {}",
+        mark_notation(&spooky_mark),
+        tiles_notation(&spooky_mark.tiles)
+      ),
+      (None, [last_tile]) => format!(
+        "only {} is left, select it for a classical mark",
+        row_column_notation(last_tile)
+      ),
+      _ => "select two tiles for a spooky mark, e.g. 'a1 b2' or '1 5'".to_string(),
+    }
+  }
+
+  fn play_input(&mut self, player_input: &str) -> Result<(), Box<dyn Error>> {
+    let quantum_move = parse_quantum_move(player_input, self)?;
+
+    self.play(&quantum_move)
+  }
+
+  fn describe_move(&self, quantum_move: &QuantumMove) -> String {
+    let description = match (quantum_move, self.pending_collapse) {
+      (QuantumMove::Collapse(_), Some(spooky_mark)) => {
+        format!("collapsed {} into", mark_notation(&spooky_mark))
+      }
+      _ => format!(
+        "placed {}{} on",
+        symbol_notation(self.next_symbol, self.move_number + 1, false),
+        if matches!(quantum_move, QuantumMove::Classical(_)) {
+          " classically"
+        } else {
+          ""
+        }
+      ),
+    };
+
+    format!("{description} {}", quantum_move_notation(quantum_move))
+  }
+}
+
+pub fn quantum(gameconfig: GameConfig) -> Result<(), Box<dyn Error>> {
+  quantum_session(gameconfig, io::stdin().lock(), io::stdout())?;
+
+  Ok(())
+}
+
+pub fn quantum_session<R: BufRead, W: Write>(
+  gameconfig: GameConfig,
+  input: R,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
+  let difficulty = gameconfig.bot.difficulty;
+
+  writeln!(
+    output,
+    "{} {:?} | Bot {:?} | Spooky marks go on two tiles until a cycle collapses them",
+    gameconfig.player_name, gameconfig.player_symbol, gameconfig.bot.bot_symbol
+  )?;
+
+  play_session(
+    QuantumGame::new(),
+    &two_player_seats(&gameconfig.player_name, player_of(gameconfig.player_symbol)),
+    |quantum_game| best_move(quantum_game, difficulty),
+    input,
+    output,
+  )
+}
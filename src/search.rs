@@ -0,0 +1,167 @@
+//! Depth limited alpha-beta search, for the modes too big to solve outright.
+//!
+//! Scores are always for the player the search is picking a move for. On their
+//! turns the best move for them is taken and on everyone else's the worst one,
+//! which with more than two players is paranoid search.
+//!
+//! Anything implementing mcts::Game can be searched once it can score a
+//! position, see Searchable.
+
+use crate::bot::{Difficulty, SearchBudget};
+use crate::mcts::{Game, Outcome};
+use std::time::Instant;
+
+/// A win, plus how many moves the search still had left so sooner wins score higher.
+pub const WIN_SCORE: i32 = 1_000_000;
+/// Evaluations never go past this either way, so on any board they stay well
+/// under WIN_SCORE and no position looks as good as a win.
+pub const MAX_EVALUATION: i32 = 500_000;
+
+pub trait Searchable: Game {
+  /// How many moves ahead the search looks on Easy, Medium and Hard.
+  const SEARCH_DEPTHS: [u32; 3] = [1, 3, 5];
+
+  /// How good the position looks for player, within MAX_EVALUATION either way.
+  fn evaluate(&self, player: usize) -> i32;
+
+  /// The moves worth searching, best looking first. Every legal move unless
+  /// the game narrows them down.
+  fn search_moves(&self) -> Vec<Self::Move> {
+    self.legal_moves()
+  }
+}
+
+pub fn search_depth<G: Searchable>(difficulty: Difficulty) -> u32 {
+  match difficulty {
+    Difficulty::Easy => G::SEARCH_DEPTHS[0],
+    Difficulty::Medium => G::SEARCH_DEPTHS[1],
+    Difficulty::Hard => G::SEARCH_DEPTHS[2],
+  }
+}
+
+/// What a line with count of one side's pieces and none of the other's is worth.
+pub fn line_score(count: usize) -> i64 {
+  4_i64.saturating_pow(count as u32)
+}
+
+/// Adds up line scores without overflowing, however big the board is.
+pub fn total_evaluation(scores: impl Iterator<Item = i64>) -> i32 {
+  scores
+    .fold(0_i64, i64::saturating_add)
+    .clamp(-MAX_EVALUATION as i64, MAX_EVALUATION as i64) as i32
+}
+
+/// The best move for whoever is to move looking depth moves ahead, the first one found on a tie.
+pub fn best_move<G: Searchable>(game: &G, depth: u32) -> Option<G::Move> {
+  search_root(game, &game.search_moves(), depth.max(1), None)
+}
+
+/// The best of root_moves within budget. It searches a move deeper each time
+/// round until it reaches budget.max_depth or runs out of time, keeping the
+/// best move from the deepest search it finished. The first search is always finished.
+pub fn best_move_within<G: Searchable>(
+  game: &G,
+  root_moves: &[G::Move],
+  budget: SearchBudget,
+) -> Option<G::Move> {
+  let stop_at = Instant::now() + budget.time_limit;
+  let mut best = root_moves.first().copied();
+
+  if root_moves.len() <= 1 {
+    return best;
+  }
+
+  for depth in 1..=budget.max_depth.max(1) {
+    let deadline = (depth > 1).then_some(stop_at);
+
+    match search_root(game, root_moves, depth, deadline) {
+      Some(game_move) => best = Some(game_move),
+      None => break,
+    }
+
+    if Instant::now() >= stop_at {
+      break;
+    }
+  }
+
+  best
+}
+
+/// None if the deadline passed before every move was searched.
+fn search_root<G: Searchable>(
+  game: &G,
+  root_moves: &[G::Move],
+  depth: u32,
+  deadline: Option<Instant>,
+) -> Option<G::Move> {
+  let player = game.current_player();
+  let mut best = None;
+  let mut alpha = -WIN_SCORE * 2;
+
+  for game_move in root_moves {
+    let mut next_game = game.clone();
+    next_game.apply(game_move);
+
+    let score = alpha_beta(
+      &next_game,
+      player,
+      depth - 1,
+      alpha,
+      WIN_SCORE * 2,
+      deadline,
+    )?;
+
+    if best.is_none() || score > alpha {
+      alpha = score;
+      best = Some(*game_move);
+    }
+  }
+
+  best
+}
+
+/// Scored for player, None once the deadline has passed.
+fn alpha_beta<G: Searchable>(
+  game: &G,
+  player: usize,
+  depth: u32,
+  mut alpha: i32,
+  mut beta: i32,
+  deadline: Option<Instant>,
+) -> Option<i32> {
+  match game.outcome() {
+    Some(Outcome::Won(winner)) if winner == player => return Some(WIN_SCORE + depth as i32),
+    Some(Outcome::Won(_)) => return Some(-(WIN_SCORE + depth as i32)),
+    Some(Outcome::Draw) => return Some(0),
+    None => (),
+  }
+
+  if depth == 0 {
+    return Some(game.evaluate(player));
+  }
+
+  if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+    return None;
+  }
+
+  let maximizing = game.current_player() == player;
+
+  for game_move in game.search_moves() {
+    let mut next_game = game.clone();
+    next_game.apply(&game_move);
+
+    let score = alpha_beta(&next_game, player, depth - 1, alpha, beta, deadline)?;
+
+    if maximizing {
+      alpha = alpha.max(score);
+    } else {
+      beta = beta.min(score);
+    }
+
+    if alpha >= beta {
+      break;
+    }
+  }
+
+  Some(if maximizing { alpha } else { beta })
+}
@@ -0,0 +1,110 @@
+//! The loop every mode played at a prompt goes through. The board is shown
+//! before each turn, a person's move is read from input and a bot's is picked
+//! by whatever search the mode uses, until the game has a result.
+
+use crate::gameplay::input_ended_error;
+use crate::mcts::Game;
+use std::error::Error;
+use std::fmt::Debug;
+use std::io::{BufRead, Write};
+
+/// Who plays one of the players, in the order mcts::Game numbers them.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Seat {
+  pub name: String,
+  pub is_person: bool,
+}
+
+pub trait SessionGame: Game {
+  type Result: Debug;
+
+  /// The board as shown before every turn.
+  fn render(&self) -> String;
+
+  /// None while the game can still be played.
+  fn session_result(&self) -> Option<Self::Result>;
+
+  /// What a person is asked for on their turn, e.g. "select a tile, e.g. 'c4'".
+  fn prompt(&self) -> String;
+
+  /// Plays what a person typed, erroring without changing anything if it can't be played.
+  fn play_input(&mut self, player_input: &str) -> Result<(), Box<dyn Error>>;
+
+  /// How a bot's move is shown, e.g. "placed on c4".
+  fn describe_move(&self, game_move: &Self::Move) -> String;
+
+  /// What's written once the game is over.
+  fn announce(&self, result: &Self::Result, _seats: &[Seat]) -> String {
+    format!("{result:?}")
+  }
+}
+
+/// A person against the bot, person_seat being 0 if they play first.
+pub fn two_player_seats(player_name: &str, person_seat: usize) -> Vec<Seat> {
+  (0..2)
+    .map(|seat| Seat {
+      name: if seat == person_seat {
+        player_name.to_string()
+      } else {
+        "Bot".to_string()
+      },
+      is_person: seat == person_seat,
+    })
+    .collect()
+}
+
+/// Plays game out, asking for moves by name when more than one person is playing.
+pub fn play_session<G: SessionGame, R: BufRead, W: Write>(
+  mut game: G,
+  seats: &[Seat],
+  mut bot_move: impl FnMut(&G) -> Option<G::Move>,
+  mut input: R,
+  mut output: W,
+) -> Result<G::Result, Box<dyn Error>> {
+  let people = seats.iter().filter(|seat| seat.is_person).count();
+
+  loop {
+    write!(output, "\n{}", game.render())?;
+
+    if let Some(result) = game.session_result() {
+      writeln!(output, "{}", game.announce(&result, seats))?;
+
+      return Ok(result);
+    }
+
+    let seat = &seats[game.current_player()];
+
+    if seat.is_person {
+      if people > 1 {
+        writeln!(output, "{}, {}", seat.name, game.prompt())?;
+      } else {
+        writeln!(output, "{}", capitalized(&game.prompt()))?;
+      }
+
+      let mut player_input = String::new();
+
+      if input.read_line(&mut player_input)? == 0 {
+        return Err(input_ended_error());
+      }
+
+      if let Err(error) = game.play_input(&player_input) {
+        writeln!(output, "{error}")?;
+      }
+    } else {
+      let game_move = bot_move(&game).ok_or("the bot couldn't find a move")?;
+
+      writeln!(output, " -- bot {} --", game.describe_move(&game_move))?;
+
+      game.apply(&game_move);
+    }
+  }
+}
+
+fn capitalized(text: &str) -> String {
+  let mut characters = text.chars();
+
+  match characters.next() {
+    Some(first) => first.to_uppercase().chain(characters).collect(),
+    None => String::new(),
+  }
+}
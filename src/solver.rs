@@ -0,0 +1,209 @@
+//! Searches every possible game from a position, rather than following
+//! a strategy like the bot does.
+//!
+//! The misère functions play the reverse game, where completing a row loses.
+//! The wild functions play the game where either symbol can be placed and
+//! completing any row wins.
+
+use crate::coordinate_methods::*;
+use crate::gameboard::*;
+use std::collections::HashMap;
+
+/// The symbol to place then where.
+pub type WildMove = (BoardStates, Coordinates);
+
+pub const WIN_SCORE: i8 = 1;
+pub const DRAW_SCORE: i8 = 0;
+pub const LOSS_SCORE: i8 = -1;
+
+/// Scores every empty tile for whoever places next, assuming both sides play perfectly.
+/// A win is WIN_SCORE, a draw DRAW_SCORE and a loss LOSS_SCORE.
+pub fn score_moves(gameboard: &BoardConfig) -> Vec<(Coordinates, i8)> {
+  score_moves_with_rules(gameboard, false)
+}
+
+/// score_moves for the game where completing a row loses.
+pub fn misere_score_moves(gameboard: &BoardConfig) -> Vec<(Coordinates, i8)> {
+  score_moves_with_rules(gameboard, true)
+}
+
+/// The highest scoring empty tile, the first one found when there's a tie.
+pub fn best_move(gameboard: &BoardConfig) -> Option<Coordinates> {
+  highest_scoring(score_moves(gameboard))
+}
+
+pub fn misere_best_move(gameboard: &BoardConfig) -> Option<Coordinates> {
+  highest_scoring(misere_score_moves(gameboard))
+}
+
+fn score_moves_with_rules(gameboard: &BoardConfig, misere: bool) -> Vec<(Coordinates, i8)> {
+  let mut gameboard = gameboard.clone();
+  let symbol = gameboard.next_symbol_to_place();
+
+  if gameboard.winning_row().is_some() {
+    return vec![];
+  }
+
+  empty_tiles(&gameboard)
+    .into_iter()
+    .map(|coords| {
+      let score = score_after_placing(&mut gameboard, &coords, symbol, misere);
+
+      (coords, score)
+    })
+    .collect()
+}
+
+fn highest_scoring(scored_moves: Vec<(Coordinates, i8)>) -> Option<Coordinates> {
+  scored_moves
+    .into_iter()
+    .rev()
+    .max_by_key(|(_, score)| *score)
+    .map(|(coords, _)| coords)
+}
+
+/// Scores every symbol on every empty tile for whoever places next.
+pub fn wild_score_moves(gameboard: &BoardConfig) -> Vec<(WildMove, i8)> {
+  let mut gameboard = gameboard.clone();
+  let mut scores = HashMap::new();
+
+  if gameboard.winning_row().is_some() {
+    return vec![];
+  }
+
+  wild_moves(&gameboard)
+    .into_iter()
+    .map(|wild_move| {
+      let score = wild_score_after_placing(&mut gameboard, &wild_move, &mut scores);
+
+      (wild_move, score)
+    })
+    .collect()
+}
+
+/// The highest scoring wild move, the first one found when there's a tie.
+pub fn wild_best_move(gameboard: &BoardConfig) -> Option<WildMove> {
+  wild_score_moves(gameboard)
+    .into_iter()
+    .rev()
+    .max_by_key(|(_, score)| *score)
+    .map(|(wild_move, _)| wild_move)
+}
+
+pub fn wild_moves(gameboard: &BoardConfig) -> Vec<WildMove> {
+  empty_tiles(gameboard)
+    .into_iter()
+    .flat_map(|coords| [(BoardStates::X, coords), (BoardStates::O, coords)])
+    .collect()
+}
+
+pub fn empty_tiles(gameboard: &BoardConfig) -> Vec<Coordinates> {
+  all_coordinates()
+    .into_iter()
+    .filter(|coords| gameboard.get_board_state(coords) == &BoardStates::Empty)
+    .collect()
+}
+
+fn score_after_placing(
+  gameboard: &mut BoardConfig,
+  coords: &Coordinates,
+  symbol: BoardStates,
+  misere: bool,
+) -> i8 {
+  let last_modified_tile = gameboard.last_modified_tile;
+
+  gameboard.place_tile(coords, symbol);
+  gameboard.tiles_covered += 1;
+
+  let score = if gameboard.check_if_win() {
+    if misere {
+      LOSS_SCORE
+    } else {
+      WIN_SCORE
+    }
+  } else {
+    -negamax(gameboard, opposing_symbol(symbol), misere)
+  };
+
+  gameboard.place_tile(coords, BoardStates::Empty);
+  gameboard.tiles_covered -= 1;
+  gameboard.last_modified_tile = last_modified_tile;
+
+  score
+}
+
+fn negamax(gameboard: &mut BoardConfig, symbol: BoardStates, misere: bool) -> i8 {
+  let mut best_score = None;
+
+  for coords in empty_tiles(gameboard) {
+    let score = score_after_placing(gameboard, &coords, symbol, misere);
+
+    if score == WIN_SCORE {
+      return WIN_SCORE;
+    }
+
+    best_score = best_score.max(Some(score));
+  }
+
+  best_score.unwrap_or(DRAW_SCORE)
+}
+
+fn wild_score_after_placing(
+  gameboard: &mut BoardConfig,
+  wild_move: &WildMove,
+  scores: &mut HashMap<String, i8>,
+) -> i8 {
+  let (symbol, coords) = *wild_move;
+  let last_modified_tile = gameboard.last_modified_tile;
+
+  gameboard.place_tile(&coords, symbol);
+  gameboard.tiles_covered += 1;
+
+  let score = if gameboard.check_if_win() {
+    WIN_SCORE
+  } else {
+    -wild_negamax(gameboard, scores)
+  };
+
+  gameboard.place_tile(&coords, BoardStates::Empty);
+  gameboard.tiles_covered -= 1;
+  gameboard.last_modified_tile = last_modified_tile;
+
+  score
+}
+
+/// Both sides have the same moves in wild, so a board scores the same
+/// whoever is placing and the scores can be kept by board.
+fn wild_negamax(gameboard: &mut BoardConfig, scores: &mut HashMap<String, i8>) -> i8 {
+  let notation = gameboard.to_notation();
+
+  if let Some(score) = scores.get(&notation) {
+    return *score;
+  }
+
+  let mut best_score = None;
+
+  for wild_move in wild_moves(gameboard) {
+    let score = wild_score_after_placing(gameboard, &wild_move, scores);
+
+    best_score = best_score.max(Some(score));
+
+    if score == WIN_SCORE {
+      break;
+    }
+  }
+
+  let score = best_score.unwrap_or(DRAW_SCORE);
+  scores.insert(notation, score);
+
+  score
+}
+
+pub fn opposing_symbol(symbol: BoardStates) -> BoardStates {
+  match symbol {
+    BoardStates::X => BoardStates::O,
+    BoardStates::O => BoardStates::X,
+    BoardStates::Empty => BoardStates::Empty,
+    BoardStates::Blocked => BoardStates::Blocked,
+  }
+}
@@ -0,0 +1,155 @@
+//! Toroidal mode, started with `torus [size [win length]]`.
+//!
+//! Played on a square board whose edges wrap around, so a row carries on
+//! from the last column to the first and diagonals get broken across the
+//! corners. With no corners or edges every tile is as good as any other to
+//! start with. The default is a 4x4 torus with four in a row.
+//!
+//! Tiles are written like on the flat boards, e.g. "c4".
+
+use crate::coordinate_methods::Coordinates;
+use crate::gameboard::BoardStates;
+use crate::gameplay::*;
+use crate::grid_board::*;
+use crate::mcts::player_of;
+use crate::search::{best_move, search_depth};
+use crate::session::{play_session, two_player_seats};
+use crate::solver::opposing_symbol;
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+pub const DEFAULT_TORUS_SIZE: usize = 4;
+pub const DEFAULT_WIN_LENGTH: usize = 4;
+
+const TORUS_USAGE: &str = "expected 'torus', 'torus <size>' or 'torus <size> <win length>'";
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct TorusGame {
+  pub grid_board: GridBoard,
+  pub next_symbol: BoardStates,
+}
+
+impl TorusGame {
+  pub fn new(size: usize, win_length: usize) -> Result<Self, Box<dyn Error>> {
+    Ok(TorusGame {
+      grid_board: GridBoard::toroidal(size, size, win_length)?,
+      next_symbol: BoardStates::X,
+    })
+  }
+
+  /// None while the game can still be played.
+  pub fn result(&self) -> Option<GameState> {
+    self.grid_board.result()
+  }
+
+  /// Places next_symbol on coords, erroring if it can't go there.
+  pub fn place(&mut self, coords: &Coordinates) -> Result<(), Box<dyn Error>> {
+    if self.result().is_some() {
+      return Err(Box::from("the game is over"));
+    }
+
+    if self.grid_board.get_board_state(coords) != &BoardStates::Empty {
+      return Err(Box::from(format!(
+        "{} is already taken",
+        row_column_notation(coords)
+      )));
+    }
+
+    self.grid_board.place_tile(coords, self.next_symbol);
+    self.next_symbol = opposing_symbol(self.next_symbol);
+
+    Ok(())
+  }
+
+  /// Empty tiles next to a placed one, counting across the wrapped edges.
+  /// Anywhere else is only worth searching on an empty board.
+  pub fn candidate_tiles(&self) -> Vec<Coordinates> {
+    let empty_tiles = self.grid_board.empty_tiles();
+
+    let near_pieces: Vec<Coordinates> = empty_tiles
+      .iter()
+      .filter(|coords| {
+        self
+          .grid_board
+          .neighbors(coords)
+          .iter()
+          .any(|next| self.grid_board.get_board_state(next) != &BoardStates::Empty)
+      })
+      .copied()
+      .collect();
+
+    if near_pieces.is_empty() {
+      empty_tiles
+    } else {
+      near_pieces
+    }
+  }
+}
+
+impl GridGame for TorusGame {
+  const SEARCH_DEPTHS: [u32; 3] = [1, 2, 4];
+
+  fn grid_board(&self) -> &GridBoard {
+    &self.grid_board
+  }
+
+  fn next_symbol(&self) -> BoardStates {
+    self.next_symbol
+  }
+
+  fn place(&mut self, coords: &Coordinates) -> Result<(), Box<dyn Error>> {
+    TorusGame::place(self, coords)
+  }
+
+  fn search_tiles(&self) -> Vec<Coordinates> {
+    self.candidate_tiles()
+  }
+}
+
+pub fn torus(gameconfig: GameConfig, gamemode_arguments: &[String]) -> Result<(), Box<dyn Error>> {
+  let sizes = gamemode_arguments
+    .iter()
+    .map(|size| {
+      size
+        .parse::<usize>()
+        .map_err(|_| format!("torus sizes have to be whole numbers, got '{size}'"))
+    })
+    .collect::<Result<Vec<usize>, String>>()?;
+
+  let torus_game = match sizes.as_slice() {
+    [] => TorusGame::new(DEFAULT_TORUS_SIZE, DEFAULT_WIN_LENGTH)?,
+    [size] => TorusGame::new(*size, DEFAULT_WIN_LENGTH.min(*size))?,
+    [size, win_length] => TorusGame::new(*size, *win_length)?,
+    _ => return Err(Box::from(TORUS_USAGE)),
+  };
+
+  torus_session(gameconfig, torus_game, io::stdin().lock(), io::stdout())?;
+
+  Ok(())
+}
+
+pub fn torus_session<R: BufRead, W: Write>(
+  gameconfig: GameConfig,
+  torus_game: TorusGame,
+  input: R,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
+  let depth = search_depth::<TorusGame>(gameconfig.bot.difficulty);
+
+  writeln!(
+    output,
+    "{} {:?} | Bot {:?} | {} in a row wins, wrapping around the edges",
+    gameconfig.player_name,
+    gameconfig.player_symbol,
+    gameconfig.bot.bot_symbol,
+    torus_game.grid_board.win_length
+  )?;
+
+  play_session(
+    torus_game,
+    &two_player_seats(&gameconfig.player_name, player_of(gameconfig.player_symbol)),
+    |torus_game| best_move(torus_game, depth),
+    input,
+    output,
+  )
+}
@@ -0,0 +1,318 @@
+//! A full screen game against the bot, started with the 'tui' gamemode.
+//!
+//! The arrow keys move the cursor around the board and enter places a tile.
+//! The screen is redrawn in place after every key press, with the board drawn
+//! in the chosen theme.
+//!
+//! ```text
+//!      1   2   3      Moves
+//!  a  [X]|   |        1. X a1
+//!     ---+---+---     2. O b2
+//!  b     | O |
+//!     ---+---+---
+//!  c     |   |
+//!
+//!  You X | Bot O | Your turn | Wins 0  Losses 0  Draws 1
+//!  arrows move, enter places, n starts a new game, q quits
+//! ```
+
+use crate::coordinate_methods::*;
+use crate::gameboard::*;
+use crate::gameplay::*;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use std::error::Error;
+use std::io::{self, Write};
+
+const SIDE_PANEL_COLUMN: usize = 20;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum TuiKey {
+  Up,
+  Down,
+  Left,
+  Right,
+  Select,
+  NewGame,
+  Quit,
+}
+
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Scoreboard {
+  pub wins: u32,
+  pub losses: u32,
+  pub draws: u32,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct TuiState {
+  pub gameconfig: GameConfig,
+  pub cursor: Coordinates,
+  pub move_history: Vec<(BoardStates, Coordinates)>,
+  pub scoreboard: Scoreboard,
+  pub message: String,
+  pub quit: bool,
+}
+
+impl TuiState {
+  pub fn new(gameconfig: GameConfig) -> Self {
+    let mut tui_state = TuiState {
+      gameconfig,
+      cursor: (1, 1),
+      move_history: vec![],
+      scoreboard: Scoreboard::default(),
+      message: String::new(),
+      quit: false,
+    };
+
+    tui_state.start_game();
+
+    tui_state
+  }
+
+  pub fn handle_key(&mut self, key: TuiKey) {
+    match key {
+      TuiKey::Up => self.cursor.0 = self.cursor.0.saturating_sub(1),
+      TuiKey::Down => self.cursor.0 = (self.cursor.0 + 1).min(GRID_SIZE - 1),
+      TuiKey::Left => self.cursor.1 = self.cursor.1.saturating_sub(1),
+      TuiKey::Right => self.cursor.1 = (self.cursor.1 + 1).min(GRID_SIZE - 1),
+      TuiKey::Select => self.place_at_cursor(),
+      TuiKey::NewGame => {
+        let scoreboard = self.scoreboard.clone();
+
+        let gameconfig = GameConfig::from_config(&self.gameconfig.config_file).unwrap_or_default();
+
+        *self = TuiState::new(gameconfig);
+        self.scoreboard = scoreboard;
+      }
+      TuiKey::Quit => self.quit = true,
+    }
+  }
+
+  pub fn is_game_over(&self) -> bool {
+    self.gameconfig.current_result().is_some()
+  }
+
+  /// Every line of the screen, the board on the left and the moves on the right.
+  pub fn screen_lines(&self) -> Vec<String> {
+    // The cursor is drawn the way the theme draws the last placed tile,
+    // the moves on the right already show what was placed last.
+    let mut shown_board = self.gameconfig.gameboard.clone();
+    shown_board.last_modified_tile = Some(self.cursor);
+
+    let board = self
+      .gameconfig
+      .theme
+      .renderer(self.gameconfig.colors)
+      .render(&shown_board);
+    let board_lines: Vec<&str> = board.lines().collect();
+
+    let mut panel_lines = vec!["Moves".to_string()];
+
+    panel_lines.extend(
+      self
+        .move_history
+        .iter()
+        .enumerate()
+        .map(|(index, (symbol, coords))| {
+          format!("{}. {symbol:?} {}", index + 1, row_column_notation(coords))
+        }),
+    );
+
+    let line_count = board_lines.len().max(panel_lines.len());
+
+    let mut screen_lines: Vec<String> = (0..line_count)
+      .map(|index| {
+        let board_line = board_lines.get(index).copied().unwrap_or("");
+        let panel_line = panel_lines.get(index).map_or("", String::as_str);
+        let padding = SIDE_PANEL_COLUMN.saturating_sub(display_width(board_line));
+
+        format!("{board_line}{}{panel_line}", " ".repeat(padding))
+          .trim_end()
+          .to_string()
+      })
+      .collect();
+
+    screen_lines.push(String::new());
+    screen_lines.push(format!(
+      " {} {:?} | Bot {:?} | {} | Wins {}  Losses {}  Draws {}",
+      self.gameconfig.player_name,
+      self.gameconfig.player_symbol,
+      self.gameconfig.bot.bot_symbol,
+      self.message,
+      self.scoreboard.wins,
+      self.scoreboard.losses,
+      self.scoreboard.draws,
+    ));
+    screen_lines.push(" arrows move, enter places, n starts a new game, q quits".to_string());
+
+    if self.gameconfig.misere {
+      screen_lines.push(MISERE_REMINDER.replacen(" |", "", 1));
+    }
+
+    screen_lines
+  }
+
+  fn start_game(&mut self) {
+    if self.gameconfig.bot.bot_symbol == BoardStates::X {
+      self.bot_move();
+    }
+
+    self.message = "Your turn".to_string();
+  }
+
+  fn place_at_cursor(&mut self) {
+    if self.is_game_over() {
+      return;
+    }
+
+    if self.gameconfig.gameboard.get_board_state(&self.cursor) != &BoardStates::Empty {
+      self.message = format!("{} is taken", row_column_notation(&self.cursor));
+
+      return;
+    }
+
+    let player_symbol = self.gameconfig.player_symbol;
+
+    self
+      .gameconfig
+      .gameboard
+      .place_tile(&self.cursor, player_symbol);
+    self.gameconfig.gameboard.tiles_covered += 1;
+    self.move_history.push((player_symbol, self.cursor));
+    self.message = "Your turn".to_string();
+
+    if !self.is_game_over() {
+      self.bot_move();
+    }
+
+    self.record_result();
+  }
+
+  fn bot_move(&mut self) {
+    let coords = bot_place_tile(&mut self.gameconfig);
+
+    self
+      .move_history
+      .push((self.gameconfig.bot.bot_symbol, coords));
+  }
+
+  fn record_result(&mut self) {
+    let Some(end_gamestate) = self.gameconfig.current_result() else {
+      return;
+    };
+
+    let player_won = matches!(
+      (&end_gamestate, self.gameconfig.player_symbol),
+      (GameState::XWon, BoardStates::X) | (GameState::OWon, BoardStates::O)
+    );
+
+    self.message = if end_gamestate == GameState::Draw {
+      self.scoreboard.draws += 1;
+      "Draw".to_string()
+    } else if player_won {
+      self.scoreboard.wins += 1;
+      "You won".to_string()
+    } else {
+      self.scoreboard.losses += 1;
+      "The bot won".to_string()
+    };
+
+    self.gameconfig.end_gamestate = end_gamestate;
+  }
+}
+
+pub fn run_tui(gameconfig: GameConfig) -> Result<(), Box<dyn Error>> {
+  let _terminal_guard = TerminalGuard::enter()?;
+
+  tui_loop(&mut io::stdout(), TuiState::new(gameconfig))
+}
+
+/// Puts the terminal back when dropped, so an error or a panic
+/// doesn't leave it in raw mode on the alternate screen.
+struct TerminalGuard;
+
+impl TerminalGuard {
+  fn enter() -> io::Result<Self> {
+    terminal::enable_raw_mode()?;
+
+    let terminal_guard = TerminalGuard;
+
+    execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+
+    Ok(terminal_guard)
+  }
+}
+
+impl Drop for TerminalGuard {
+  fn drop(&mut self) {
+    let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+  }
+}
+
+fn tui_loop<W: Write>(output: &mut W, mut tui_state: TuiState) -> Result<(), Box<dyn Error>> {
+  while !tui_state.quit {
+    draw_screen(output, &tui_state)?;
+
+    if let Event::Key(key_event) = event::read()? {
+      if let Some(key) = key_from_event(key_event) {
+        tui_state.handle_key(key);
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Raw mode doesn't move back to the start of the line on a new line,
+/// so every line is moved to directly.
+fn draw_screen<W: Write>(output: &mut W, tui_state: &TuiState) -> io::Result<()> {
+  queue!(output, Clear(ClearType::All))?;
+
+  for (line_index, line) in tui_state.screen_lines().into_iter().enumerate() {
+    queue!(output, MoveTo(0, line_index as u16), Print(line))?;
+  }
+
+  output.flush()
+}
+
+/// How many columns a line takes up on screen, skipping color codes and
+/// counting the emoji theme's symbols as two columns.
+fn display_width(line: &str) -> usize {
+  let mut width = 0;
+  let mut in_escape_code = false;
+
+  for character in line.chars() {
+    match character {
+      '\x1b' => in_escape_code = true,
+      'm' if in_escape_code => in_escape_code = false,
+      _ if in_escape_code => {}
+      '\u{2600}'.. => width += 2,
+      _ => width += 1,
+    }
+  }
+
+  width
+}
+
+pub fn key_from_event(key_event: KeyEvent) -> Option<TuiKey> {
+  if key_event.kind != KeyEventKind::Press {
+    return None;
+  }
+
+  match key_event.code {
+    KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => Some(TuiKey::Quit),
+    KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('w') => Some(TuiKey::Up),
+    KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('s') => Some(TuiKey::Down),
+    KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('a') => Some(TuiKey::Left),
+    KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('d') => Some(TuiKey::Right),
+    KeyCode::Enter | KeyCode::Char(' ') => Some(TuiKey::Select),
+    KeyCode::Char('n') => Some(TuiKey::NewGame),
+    KeyCode::Char('q') | KeyCode::Esc => Some(TuiKey::Quit),
+    _ => None,
+  }
+}
@@ -0,0 +1,444 @@
+//! Ultimate tic-tac-toe, started with the 'ultimate' gamemode.
+//!
+//! The board is 3x3 sub-boards. The cell you place in picks the sub-board the
+//! other side has to place in next, and winning a sub-board places your symbol
+//! on the meta-board. Three won sub-boards in a row wins the game.
+//! If the picked sub-board is already won or full the next move can go anywhere.
+//!
+//! Moves are written as the sub-board then the cell, e.g. "b2 a1" or "5 1".
+//! When the sub-board is already decided the cell on its own is enough.
+//!
+//! The bot in bot.rs only knows the 3x3 game, so this goes through the depth
+//! limited search in search.rs.
+
+use crate::board_render::symbol_text;
+use crate::coordinate_methods::*;
+use crate::gameboard::*;
+use crate::gameplay::*;
+use crate::mcts::{player_of, symbol_of, Game, Outcome};
+use crate::search::{best_move, search_depth, Searchable};
+use crate::session::{play_session, two_player_seats, SessionGame};
+use crate::solver::{empty_tiles, opposing_symbol};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+/// The sub-board then the cell inside it.
+pub type UltimateMove = (Coordinates, Coordinates);
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct UltimateBoard {
+  pub sub_boards: [[BoardConfig; GRID_SIZE]; GRID_SIZE],
+  /// A sub-board's tile is the symbol that won it, drawn sub-boards stay empty.
+  pub meta_board: BoardConfig,
+  /// None when the next move can go in any open sub-board.
+  pub next_sub_board: Option<Coordinates>,
+  pub next_symbol: BoardStates,
+  pub last_move: Option<UltimateMove>,
+}
+
+impl UltimateBoard {
+  pub fn new() -> Self {
+    UltimateBoard {
+      sub_boards: std::array::from_fn(|_| std::array::from_fn(|_| BoardConfig::new())),
+      meta_board: BoardConfig::new(),
+      next_sub_board: None,
+      next_symbol: BoardStates::X,
+      last_move: None,
+    }
+  }
+
+  pub fn sub_board(&self, coords: &Coordinates) -> &BoardConfig {
+    &self.sub_boards[coords.0][coords.1]
+  }
+
+  /// A sub-board is open until it's won or full.
+  pub fn is_sub_board_open(&self, coords: &Coordinates) -> bool {
+    let sub_board = self.sub_board(coords);
+
+    sub_board.winning_row().is_none() && !sub_board.all_tiles_covered()
+  }
+
+  pub fn winner(&self) -> Option<BoardStates> {
+    self
+      .meta_board
+      .winning_row()
+      .map(|row| *self.meta_board.get_board_state(&row[0]))
+  }
+
+  /// None while the game can still be played.
+  pub fn result(&self) -> Option<GameState> {
+    match self.winner() {
+      Some(BoardStates::X) => Some(GameState::XWon),
+      Some(BoardStates::O) => Some(GameState::OWon),
+      _ if self.legal_moves().is_empty() => Some(GameState::Draw),
+      _ => None,
+    }
+  }
+
+  pub fn legal_moves(&self) -> Vec<UltimateMove> {
+    if self.winner().is_some() {
+      return vec![];
+    }
+
+    let sub_boards = match self.next_sub_board {
+      Some(coords) => vec![coords],
+      None => all_coordinates()
+        .into_iter()
+        .filter(|coords| self.is_sub_board_open(coords))
+        .collect(),
+    };
+
+    sub_boards
+      .into_iter()
+      .flat_map(|sub_board| {
+        empty_tiles(self.sub_board(&sub_board))
+          .into_iter()
+          .map(move |cell| (sub_board, cell))
+      })
+      .collect()
+  }
+
+  /// Places next_symbol, erroring if the move isn't allowed.
+  pub fn place(&mut self, ultimate_move: &UltimateMove) -> Result<(), Box<dyn Error>> {
+    let (sub_board_coords, cell) = *ultimate_move;
+
+    if self.result().is_some() {
+      return Err(Box::from("the game is over"));
+    }
+
+    if [sub_board_coords, cell]
+      .iter()
+      .any(|coords| coords.0 >= GRID_SIZE || coords.1 >= GRID_SIZE)
+    {
+      return Err(Box::from("incorrect input"));
+    }
+
+    if let Some(next_sub_board) = self.next_sub_board {
+      if next_sub_board != sub_board_coords {
+        return Err(Box::from(format!(
+          "this move has to be in sub-board {}",
+          row_column_notation(&next_sub_board)
+        )));
+      }
+    } else if !self.is_sub_board_open(&sub_board_coords) {
+      return Err(Box::from(format!(
+        "sub-board {} is already finished",
+        row_column_notation(&sub_board_coords)
+      )));
+    }
+
+    let symbol = self.next_symbol;
+    let sub_board = &mut self.sub_boards[sub_board_coords.0][sub_board_coords.1];
+
+    if sub_board.get_board_state(&cell) != &BoardStates::Empty {
+      return Err(Box::from(format!(
+        "{} is already taken",
+        row_column_notation(&cell)
+      )));
+    }
+
+    sub_board.place_tile(&cell, symbol);
+    sub_board.tiles_covered += 1;
+
+    if sub_board.check_if_win() {
+      self.meta_board.place_tile(&sub_board_coords, symbol);
+      self.meta_board.tiles_covered += 1;
+    }
+
+    self.next_sub_board = Some(cell).filter(|cell| self.is_sub_board_open(cell));
+    self.next_symbol = opposing_symbol(symbol);
+    self.last_move = Some(*ultimate_move);
+
+    Ok(())
+  }
+}
+
+impl Default for UltimateBoard {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// "b2 a1" or "5 1", or just the cell when the sub-board is already decided.
+pub fn parse_ultimate_move(
+  player_input: &str,
+  next_sub_board: Option<Coordinates>,
+) -> Result<UltimateMove, Box<dyn Error>> {
+  let selections: Vec<&str> = player_input.split_whitespace().collect();
+
+  match (selections.as_slice(), next_sub_board) {
+    ([sub_board, cell], _) => Ok((
+      parse_tile_selection(sub_board)?,
+      parse_tile_selection(cell)?,
+    )),
+    ([cell], Some(next_sub_board)) => Ok((next_sub_board, parse_tile_selection(cell)?)),
+    ([_], None) => Err(Box::from(
+      "any sub-board can be played, give the sub-board then the cell e.g. 'b2 a1'",
+    )),
+    _ => Err(Box::from("incorrect input")),
+  }
+}
+
+/// ```text
+///          1       2       3
+///      +-------+-------+-------+
+///      | X . . |       # . . . #
+///   a  | . O . |   X   # . . . #
+///      | . . . |       # .[O]. #
+///      +-------+-------+#######+
+/// ```
+///
+/// Won sub-boards only show the winner, the last move is wrapped in [ ]
+/// and the sub-board the next move has to be in is bordered with #.
+/// Until the game is over the line under it says where the next move can go.
+pub fn render_ultimate(ultimate_board: &UltimateBoard) -> String {
+  let is_next = |coords: Coordinates| ultimate_board.next_sub_board == Some(coords);
+  let mut lines = vec!["         1       2       3".to_string()];
+
+  for meta_row in 0..=GRID_SIZE {
+    // The line above meta_row, also bordering the row before it.
+    let borders_next = |meta_column: usize| {
+      (meta_row < GRID_SIZE && is_next((meta_row, meta_column)))
+        || (meta_row > 0 && is_next((meta_row - 1, meta_column)))
+    };
+
+    lines.push(format!(
+      "     +{}+",
+      (0..GRID_SIZE)
+        .map(|meta_column| if borders_next(meta_column) {
+          "#######"
+        } else {
+          "-------"
+        })
+        .collect::<Vec<&str>>()
+        .join("+")
+    ));
+
+    if meta_row == GRID_SIZE {
+      break;
+    }
+
+    for cell_row in 0..GRID_SIZE {
+      let label = if cell_row == 1 {
+        (b'a' + meta_row as u8) as char
+      } else {
+        ' '
+      };
+
+      let mut line = format!("  {label}  ");
+
+      for meta_column in 0..=GRID_SIZE {
+        let next_on_either_side = (meta_column < GRID_SIZE && is_next((meta_row, meta_column)))
+          || (meta_column > 0 && is_next((meta_row, meta_column - 1)));
+
+        line.push(if next_on_either_side { '#' } else { '|' });
+
+        if meta_column < GRID_SIZE {
+          line.push_str(&render_sub_board_row(
+            ultimate_board,
+            &(meta_row, meta_column),
+            cell_row,
+          ));
+        }
+      }
+
+      lines.push(line);
+    }
+  }
+
+  if ultimate_board.result().is_none() {
+    lines.push(match ultimate_board.next_sub_board {
+      Some(next_sub_board) => format!(
+        "{:?} places in {}",
+        ultimate_board.next_symbol,
+        row_column_notation(&next_sub_board)
+      ),
+      None => format!("{:?} places anywhere", ultimate_board.next_symbol),
+    });
+  }
+
+  lines.join("\n") + "\n"
+}
+
+/// One row of a sub-board, always 7 characters wide.
+fn render_sub_board_row(
+  ultimate_board: &UltimateBoard,
+  sub_board_coords: &Coordinates,
+  cell_row: usize,
+) -> String {
+  let winner = ultimate_board.meta_board.get_board_state(sub_board_coords);
+
+  if winner != &BoardStates::Empty {
+    return if cell_row == 1 {
+      format!("   {}   ", symbol_text(winner))
+    } else {
+      " ".repeat(7)
+    };
+  }
+
+  let sub_board = ultimate_board.sub_board(sub_board_coords);
+  let mut row: Vec<char> = " . . . ".chars().collect();
+
+  for cell_column in 0..GRID_SIZE {
+    let cell = (cell_row, cell_column);
+    let position = cell_column * 2 + 1;
+
+    if let symbol @ (BoardStates::X | BoardStates::O) = sub_board.get_board_state(&cell) {
+      row[position] = symbol_text(symbol).chars().next().unwrap();
+    }
+
+    if ultimate_board.last_move == Some((*sub_board_coords, cell)) {
+      row[position - 1] = '[';
+      row[position + 1] = ']';
+    }
+  }
+
+  row.into_iter().collect()
+}
+
+/// Won sub-boards and rows close to being finished, for symbol minus for the other side.
+fn evaluate(ultimate_board: &UltimateBoard, symbol: BoardStates) -> i32 {
+  let opponent = opposing_symbol(symbol);
+  let open_meta = |coords: &Coordinates| {
+    ultimate_board.meta_board.get_board_state(coords) == &BoardStates::Empty
+      && ultimate_board.is_sub_board_open(coords)
+  };
+
+  let mut score = 0;
+
+  for coords in all_coordinates() {
+    let position_bonus = match ultimate_board.meta_board.get_board_position(&coords) {
+      BoardPositions::Center => 4,
+      BoardPositions::Corner => 2,
+      BoardPositions::Edge => 0,
+    };
+
+    match ultimate_board.meta_board.get_board_state(&coords) {
+      state if *state == symbol => score += 20 + position_bonus,
+      state if *state == opponent => score -= 20 + position_bonus,
+      _ if ultimate_board.is_sub_board_open(&coords) => {
+        score += row_threats(ultimate_board.sub_board(&coords), symbol, |cell| {
+          ultimate_board.sub_board(&coords).get_board_state(cell) == &BoardStates::Empty
+        });
+      }
+      _ => (),
+    }
+  }
+
+  score + 10 * row_threats(&ultimate_board.meta_board, symbol, open_meta)
+}
+
+/// Rows with two of one symbol and an open third tile, symbol's minus the other side's.
+fn row_threats(
+  gameboard: &BoardConfig,
+  symbol: BoardStates,
+  is_open: impl Fn(&Coordinates) -> bool,
+) -> i32 {
+  let opponent = opposing_symbol(symbol);
+
+  ALL_ROWS
+    .iter()
+    .map(|row| {
+      let count_of = |state: BoardStates| {
+        row
+          .iter()
+          .filter(|coords| gameboard.get_board_state(coords) == &state)
+          .count()
+      };
+      let open_tiles = row.iter().filter(|coords| is_open(coords)).count();
+
+      match (count_of(symbol), count_of(opponent), open_tiles) {
+        (2, 0, 1) => 1,
+        (0, 2, 1) => -1,
+        _ => 0,
+      }
+    })
+    .sum()
+}
+
+impl Game for UltimateBoard {
+  type Move = UltimateMove;
+
+  fn current_player(&self) -> usize {
+    player_of(self.next_symbol)
+  }
+
+  fn legal_moves(&self) -> Vec<UltimateMove> {
+    UltimateBoard::legal_moves(self)
+  }
+
+  fn apply(&mut self, ultimate_move: &UltimateMove) {
+    self
+      .place(ultimate_move)
+      .expect("only legal moves are placed");
+  }
+
+  fn outcome(&self) -> Option<Outcome> {
+    self.result().map(Outcome::from)
+  }
+}
+
+impl Searchable for UltimateBoard {
+  fn evaluate(&self, player: usize) -> i32 {
+    evaluate(self, symbol_of(player))
+  }
+}
+
+impl SessionGame for UltimateBoard {
+  type Result = GameState;
+
+  fn render(&self) -> String {
+    render_ultimate(self)
+  }
+
+  fn session_result(&self) -> Option<GameState> {
+    self.result()
+  }
+
+  fn prompt(&self) -> String {
+    "select a sub-board then a cell, e.g. 'b2 a1' or '5 1'".to_string()
+  }
+
+  fn play_input(&mut self, player_input: &str) -> Result<(), Box<dyn Error>> {
+    let ultimate_move = parse_ultimate_move(player_input, self.next_sub_board)?;
+
+    self.place(&ultimate_move)
+  }
+
+  fn describe_move(&self, ultimate_move: &UltimateMove) -> String {
+    format!(
+      "placed {} {}",
+      row_column_notation(&ultimate_move.0),
+      row_column_notation(&ultimate_move.1)
+    )
+  }
+}
+
+pub fn ultimate(gameconfig: GameConfig) -> Result<(), Box<dyn Error>> {
+  ultimate_session(gameconfig, io::stdin().lock(), io::stdout())?;
+
+  Ok(())
+}
+
+/// Plays against the search, reading moves from input and writing everything to output.
+pub fn ultimate_session<R: BufRead, W: Write>(
+  gameconfig: GameConfig,
+  input: R,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
+  let depth = search_depth::<UltimateBoard>(gameconfig.bot.difficulty);
+
+  writeln!(
+    output,
+    "{} {:?} | Bot {:?}",
+    gameconfig.player_name, gameconfig.player_symbol, gameconfig.bot.bot_symbol
+  )?;
+
+  play_session(
+    UltimateBoard::new(),
+    &two_player_seats(&gameconfig.player_name, player_of(gameconfig.player_symbol)),
+    |ultimate_board| best_move(ultimate_board, depth),
+    input,
+    output,
+  )
+}
@@ -0,0 +1,190 @@
+//! Wild tic-tac-toe, started with the 'wild' gamemode.
+//!
+//! Nobody owns a symbol. Each turn whoever is placing picks X or O, and
+//! whoever completes a row of either wins it. So player_symbol and bot_symbol
+//! aren't used here, only player_turn to decide who places first. The result
+//! is XWon when whoever placed first won and OWon when the other side did.
+//!
+//! Moves are written as the symbol then the tile, e.g. "o b2" or "x 5".
+
+use crate::board_render::Theme;
+use crate::gameboard::*;
+use crate::gameplay::*;
+use crate::mcts::{Game, Outcome};
+use crate::session::{play_session, two_player_seats, SessionGame};
+use crate::solver::{wild_moves, WildMove};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct WildGame {
+  pub gameboard: BoardConfig,
+  /// 0 for whoever placed first.
+  pub next_player: usize,
+  pub theme: Theme,
+  pub colors: bool,
+}
+
+impl WildGame {
+  /// Picks up from the board, theme and colors of gameconfig.
+  pub fn new(gameconfig: &GameConfig) -> Self {
+    WildGame {
+      gameboard: gameconfig.gameboard.clone(),
+      next_player: 0,
+      theme: gameconfig.theme,
+      colors: gameconfig.colors,
+    }
+  }
+
+  /// None while the game can still be played.
+  pub fn result(&self) -> Option<GameState> {
+    match self.outcome()? {
+      Outcome::Won(0) => Some(GameState::XWon),
+      Outcome::Won(_) => Some(GameState::OWon),
+      Outcome::Draw => Some(GameState::Draw),
+    }
+  }
+}
+
+/// "o b2" or "x 5", the symbol can also come after the tile.
+pub fn parse_wild_move(player_input: &str) -> Result<WildMove, Box<dyn Error>> {
+  let selections: Vec<String> = player_input
+    .split_whitespace()
+    .map(str::to_lowercase)
+    .collect();
+
+  let parse_symbol = |symbol: &str| match symbol {
+    "x" => Some(BoardStates::X),
+    "o" => Some(BoardStates::O),
+    _ => None,
+  };
+
+  match selections.as_slice() {
+    [first, second] => {
+      if let Some(symbol) = parse_symbol(first) {
+        Ok((symbol, parse_tile_selection(second)?))
+      } else if let Some(symbol) = parse_symbol(second) {
+        Ok((symbol, parse_tile_selection(first)?))
+      } else {
+        Err(Box::from("pick a symbol to place, x or o"))
+      }
+    }
+    [_] => Err(Box::from(
+      "give the symbol to place as well as the tile, e.g. 'o b2'",
+    )),
+    _ => Err(Box::from("incorrect input")),
+  }
+}
+
+/// Places the move on the board, erroring if the tile isn't free.
+pub fn place_wild_move(
+  gameboard: &mut BoardConfig,
+  wild_move: &WildMove,
+) -> Result<(), Box<dyn Error>> {
+  let (symbol, coords) = *wild_move;
+
+  if gameboard.get_board_state(&coords) != &BoardStates::Empty {
+    return Err(Box::from(format!(
+      "{} is already taken",
+      row_column_notation(&coords)
+    )));
+  }
+
+  gameboard.place_tile(&coords, symbol);
+  gameboard.tiles_covered += 1;
+
+  Ok(())
+}
+
+impl Game for WildGame {
+  type Move = WildMove;
+
+  fn current_player(&self) -> usize {
+    self.next_player
+  }
+
+  fn legal_moves(&self) -> Vec<WildMove> {
+    if self.outcome().is_some() {
+      return vec![];
+    }
+
+    wild_moves(&self.gameboard)
+  }
+
+  fn apply(&mut self, wild_move: &WildMove) {
+    place_wild_move(&mut self.gameboard, wild_move).expect("only legal moves are placed");
+    self.next_player = 1 - self.next_player;
+  }
+
+  /// The turn has already passed on from whoever completed the row.
+  fn outcome(&self) -> Option<Outcome> {
+    if self.gameboard.winning_row().is_some() {
+      Some(Outcome::Won(1 - self.next_player))
+    } else if self.gameboard.all_tiles_covered() {
+      Some(Outcome::Draw)
+    } else {
+      None
+    }
+  }
+}
+
+impl SessionGame for WildGame {
+  type Result = GameState;
+
+  fn render(&self) -> String {
+    self.theme.renderer(self.colors).render(&self.gameboard)
+  }
+
+  fn session_result(&self) -> Option<GameState> {
+    self.result()
+  }
+
+  fn prompt(&self) -> String {
+    "select a symbol then a tile, e.g. 'o b2' or 'x 5'".to_string()
+  }
+
+  fn play_input(&mut self, player_input: &str) -> Result<(), Box<dyn Error>> {
+    let wild_move = parse_wild_move(player_input)?;
+
+    place_wild_move(&mut self.gameboard, &wild_move)?;
+    self.next_player = 1 - self.next_player;
+
+    Ok(())
+  }
+
+  fn describe_move(&self, wild_move: &WildMove) -> String {
+    format!(
+      "placed {:?} on {}",
+      wild_move.0,
+      row_column_notation(&wild_move.1)
+    )
+  }
+}
+
+pub fn wild(gameconfig: GameConfig) -> Result<(), Box<dyn Error>> {
+  wild_session(gameconfig, io::stdin().lock(), io::stdout())?;
+
+  Ok(())
+}
+
+pub fn wild_session<R: BufRead, W: Write>(
+  gameconfig: GameConfig,
+  input: R,
+  mut output: W,
+) -> Result<GameState, Box<dyn Error>> {
+  writeln!(
+    output,
+    "{} | Bot | Place X or O, any three in a row wins",
+    gameconfig.player_name
+  )?;
+
+  let person_seat = if gameconfig.player_turn { 0 } else { 1 };
+
+  play_session(
+    WildGame::new(&gameconfig),
+    &two_player_seats(&gameconfig.player_name, person_seat),
+    |wild_game| gameconfig.bot.choose_wild_move(&wild_game.gameboard),
+    input,
+    output,
+  )
+}
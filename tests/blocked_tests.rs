@@ -0,0 +1,203 @@
+use std::io::Cursor;
+use std::time::{Duration, Instant};
+use tictactoe_with_ai::blocked::*;
+use tictactoe_with_ai::bot::{Difficulty, SearchBudget};
+use tictactoe_with_ai::gameboard::BoardStates;
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::grid_board::*;
+
+const DIAMOND: &str = "
+  #.#
+  ...
+  #.#
+";
+
+fn place_all(blocked_game: &mut BlockedGame, tiles: &[&str]) {
+  for tile in tiles {
+    let coords = parse_grid_tile(tile, &blocked_game.grid_board).unwrap();
+
+    blocked_game.place(&coords).unwrap();
+  }
+}
+
+#[cfg(test)]
+mod layouts {
+  use super::*;
+
+  #[test]
+  fn layouts_are_read_row_by_row() {
+    let grid_board = parse_layout(DIAMOND, 3).unwrap();
+
+    assert_eq!((grid_board.width, grid_board.height), (3, 3));
+    assert_eq!(grid_board.get_board_state(&(0, 0)), &BoardStates::Blocked);
+    assert_eq!(grid_board.get_board_state(&(0, 1)), &BoardStates::Empty);
+    assert_eq!(grid_board.last_modified_tile, None);
+
+    // Just the middle row and column.
+    assert_eq!(grid_board.lines().len(), 2);
+  }
+
+  #[test]
+  fn bad_layouts_are_errors() {
+    assert_eq!(
+      parse_layout("...\n..\n", 3).unwrap_err().to_string(),
+      "row 2 of the layout is 2 tiles long, the first is 3"
+    );
+    assert_eq!(
+      parse_layout("..x\n", 3).unwrap_err().to_string(),
+      "unknown tile 'x' in the layout"
+    );
+    assert!(parse_layout("", 3).is_err());
+  }
+
+  #[test]
+  fn random_layouts_block_the_asked_for_tiles() {
+    let grid_board = random_layout(4, 3, 5).unwrap();
+
+    let blocked_tiles = grid_board
+      .tiles
+      .iter()
+      .filter(|tile| **tile == BoardStates::Blocked)
+      .count();
+
+    assert_eq!(blocked_tiles, 5);
+    assert!(!grid_board.lines().is_empty());
+    assert!(random_layout(3, 3, 9).is_err());
+  }
+}
+
+#[cfg(test)]
+mod rules {
+  use super::*;
+
+  #[test]
+  fn blocked_tiles_cant_be_placed_on() {
+    let mut blocked_game = BlockedGame::new(parse_layout(DIAMOND, 3).unwrap());
+
+    assert_eq!(
+      blocked_game.place(&(0, 0)).unwrap_err().to_string(),
+      "a1 is blocked"
+    );
+  }
+
+  #[test]
+  fn game_ends_once_the_open_tiles_are_filled() {
+    let mut blocked_game = BlockedGame::new(parse_layout(DIAMOND, 3).unwrap());
+
+    place_all(&mut blocked_game, &["b2", "a2", "b1", "b3", "c2"]);
+
+    assert_eq!(blocked_game.result(), Some(GameState::Draw));
+  }
+}
+
+#[cfg(test)]
+mod bot {
+  use super::*;
+
+  #[test]
+  fn bot_blocks_the_only_open_line() {
+    let mut blocked_game = BlockedGame::new(parse_layout(DIAMOND, 3).unwrap());
+
+    place_all(&mut blocked_game, &["b1", "a2", "b2"]);
+
+    assert_eq!(
+      best_move(&blocked_game, search_budget(Difficulty::Medium)),
+      Some((1, 2))
+    );
+  }
+
+  #[test]
+  fn bot_takes_the_crossing_tile() {
+    let blocked_game = BlockedGame::new(parse_layout(DIAMOND, 3).unwrap());
+
+    // b2 is on both lines, everything else on one.
+    assert_eq!(blocked_game.candidate_tiles()[0], (1, 1));
+    assert_eq!(
+      best_move(&blocked_game, search_budget(Difficulty::Easy)),
+      Some((1, 1))
+    );
+  }
+
+  #[test]
+  fn only_tiles_next_to_pieces_are_searched() {
+    let mut blocked_game = BlockedGame::new(random_layout(8, 4, 0).unwrap());
+
+    place_all(&mut blocked_game, &["d4"]);
+
+    let candidates = blocked_game.candidate_tiles();
+
+    assert_eq!(candidates.len(), 8);
+    assert!(candidates
+      .iter()
+      .all(|coords| coords.0.abs_diff(3) <= 1 && coords.1.abs_diff(3) <= 1));
+    assert!(candidates.windows(2).all(|pair| {
+      blocked_game.lines_through(&pair[0]) >= blocked_game.lines_through(&pair[1])
+    }));
+  }
+
+  #[test]
+  fn big_boards_are_searched_within_the_time_limit() {
+    let mut blocked_game = BlockedGame::new(random_layout(MAX_GRID_SIZE, 5, 0).unwrap());
+
+    place_all(&mut blocked_game, &["m13", "n14"]);
+
+    let started = Instant::now();
+    let coords = best_move(
+      &blocked_game,
+      SearchBudget {
+        max_depth: 20,
+        time_limit: Duration::from_millis(200),
+      },
+    )
+    .unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(2));
+    assert_eq!(
+      blocked_game.grid_board.get_board_state(&coords),
+      &BoardStates::Empty
+    );
+  }
+}
+
+#[cfg(test)]
+mod interface {
+  use super::*;
+
+  #[test]
+  fn session_plays_until_the_game_ends() {
+    let mut gameconfig = GameConfig {
+      player_symbol: BoardStates::X,
+      ..GameConfig::default()
+    };
+
+    gameconfig.bot.difficulty = Difficulty::Easy;
+
+    let layout = "
+      #..#
+      ....
+      ....
+      #..#
+    ";
+
+    let tiles: Vec<String> = ["a", "b", "c", "d"]
+      .into_iter()
+      .flat_map(|row| (1..=4).map(move |column| format!("{row}{column}\n")))
+      .collect();
+
+    let mut output = vec![];
+    let end_gamestate = blocked_session(
+      gameconfig,
+      BlockedGame::new(parse_layout(layout, 3).unwrap()),
+      Cursor::new(tiles.concat()),
+      &mut output,
+    )
+    .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("a1 is blocked"));
+    assert!(output.contains(" a  #  .  .  #"));
+    assert!(output.contains(" -- bot placed on "));
+    assert!(output.ends_with(&format!("{end_gamestate:?}\n")));
+  }
+}
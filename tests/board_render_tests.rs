@@ -0,0 +1,162 @@
+use tictactoe_with_ai::board_render::*;
+use tictactoe_with_ai::gameboard::*;
+
+#[test]
+fn plain_board_has_labels_and_grid() {
+  let mut gameboard = BoardConfig::new();
+  let expected_render = "     1   2   3
+ a   X | O |
+    ---+---+---
+ b     |[X]|
+    ---+---+---
+ c     |   |
+";
+
+  gameboard.place_tile(&(0, 0), BoardStates::X);
+  gameboard.place_tile(&(0, 1), BoardStates::O);
+  gameboard.place_tile(&(1, 1), BoardStates::X);
+
+  assert_eq!(render_board(&gameboard, false), expected_render);
+}
+
+#[test]
+fn plain_winning_row_is_marked() {
+  let expected_render = "     1   2   3
+ a  *X*| O | O
+    ---+---+---
+ b     |*X*|
+    ---+---+---
+ c     |   |*X*
+";
+
+  let mut gameboard = BoardConfig::from_notation("xoo.x....").unwrap();
+  gameboard.place_tile(&(2, 2), BoardStates::X);
+
+  assert_eq!(render_board(&gameboard, false), expected_render);
+}
+
+#[test]
+fn colored_board_uses_ansi_codes() {
+  let mut gameboard = BoardConfig::new();
+
+  gameboard.place_tile(&(0, 0), BoardStates::X);
+  gameboard.place_tile(&(1, 1), BoardStates::O);
+
+  let plain_render = render_board(&gameboard, false);
+  let colored_render = render_board(&gameboard, true);
+
+  assert!(!plain_render.contains('\x1b'));
+  assert!(plain_render.is_ascii());
+  assert!(colored_render.contains('\x1b'));
+  assert!(colored_render.contains(" X "));
+  assert!(colored_render.contains(" O "));
+}
+
+#[cfg(test)]
+mod themes {
+  use super::*;
+
+  fn example_board() -> BoardConfig {
+    //X|O|-
+    //-|X|-
+    //-|-|-
+    let mut gameboard = BoardConfig::from_notation("xo.......").unwrap();
+    gameboard.place_tile(&(1, 1), BoardStates::X);
+
+    gameboard
+  }
+
+  #[test]
+  fn themes_are_parsed() {
+    assert_eq!("Box".parse::<Theme>().unwrap(), Theme::Box);
+    assert_eq!("numbered".parse::<Theme>().unwrap(), Theme::Numbered);
+    assert!("neon".parse::<Theme>().is_err());
+
+    for name in Theme::NAMES {
+      assert!(name.parse::<Theme>().is_ok(), "{name}");
+    }
+  }
+
+  #[test]
+  fn box_theme() {
+    let expected_render = "     1   2   3
+   ┌───┬───┬───┐
+ a │ X │ O │   │
+   ├───┼───┼───┤
+ b │   │[X]│   │
+   ├───┼───┼───┤
+ c │   │   │   │
+   └───┴───┴───┘
+";
+
+    assert_eq!(
+      Theme::Box.renderer(false).render(&example_board()),
+      expected_render
+    );
+  }
+
+  #[test]
+  fn emoji_theme() {
+    let expected_render = "    1   2   3
+ a  ❌  ⭕  ⬜
+ b  ⬜ [❌] ⬜
+ c  ⬜  ⬜  ⬜
+";
+
+    assert_eq!(
+      Theme::Emoji.renderer(false).render(&example_board()),
+      expected_render
+    );
+  }
+
+  #[test]
+  fn compact_theme() {
+    assert_eq!(
+      Theme::Compact.renderer(false).render(&example_board()),
+      "xo./.[x]./...\n"
+    );
+  }
+
+  #[test]
+  fn numbered_theme() {
+    assert_eq!(
+      Theme::Numbered.renderer(false).render(&example_board()),
+      "X|O|3\n4|[X]|6\n7|8|9\n"
+    );
+  }
+
+  #[test]
+  fn renderers_write_to_any_output() {
+    let mut output = Vec::new();
+
+    Theme::Compact
+      .renderer(false)
+      .write_board(&example_board(), &mut output)
+      .unwrap();
+
+    assert_eq!(output, b"xo./.[x]./...\n");
+  }
+
+  #[test]
+  fn every_theme_marks_the_winning_row() {
+    let mut gameboard = BoardConfig::from_notation("xoo.x....").unwrap();
+    gameboard.place_tile(&(2, 2), BoardStates::X);
+
+    assert_eq!(
+      Theme::Compact.renderer(false).render(&gameboard),
+      "*x*oo/.*x*./..*x*\n"
+    );
+    assert_eq!(
+      Theme::Numbered.renderer(false).render(&gameboard),
+      "*X*|O|O\n4|*X*|6\n7|8|*X*\n"
+    );
+    assert!(Theme::Emoji
+      .renderer(false)
+      .render(&gameboard)
+      .contains(" a *❌* ⭕  ⭕"));
+
+    for theme in [Theme::Emoji, Theme::Compact, Theme::Numbered] {
+      assert!(theme.renderer(true).render(&gameboard).contains('\x1b'));
+    }
+  }
+}
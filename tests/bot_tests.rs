@@ -239,7 +239,7 @@ mod auto_play_logic {
     let expected_error = Err("No possible tile to place on".to_string());
 
     for row in &mut gameboard.tiles {
-      for mut tile in row {
+      for tile in row {
         tile.board_state = BOT_BOARD_SYMBOL;
       }
     }
@@ -306,6 +306,68 @@ mod choose_coordinates_logic {
   }
 }
 
+#[cfg(test)]
+mod difficulty_logic {
+  use super::*;
+
+  fn bot_with_difficulty(difficulty: Difficulty) -> Bot {
+    let mut bot = Bot::new();
+    bot.bot_symbol = BOT_BOARD_SYMBOL;
+    bot.difficulty = difficulty;
+
+    bot
+  }
+
+  #[test]
+  fn medium_wins_before_blocking() {
+    let mut bot = bot_with_difficulty(Difficulty::Medium);
+    let gameboard = BoardConfig::from_notation("xx.oo....").unwrap();
+    let expected_chosen_placement = Ok((0, 2));
+
+    //X|X|-
+    //O|O|-
+    //-|-|-
+    bot.choose_coordinates(&gameboard);
+
+    assert_eq!(bot.most_recent_chosen_coords, expected_chosen_placement);
+  }
+
+  #[test]
+  fn medium_blocks() {
+    let mut bot = bot_with_difficulty(Difficulty::Medium);
+    let gameboard = BoardConfig::from_notation("x...oo..x").unwrap();
+    let expected_chosen_placement = Ok((1, 0));
+
+    //X|-|-
+    //-|O|O
+    //-|-|X
+    bot.choose_coordinates(&gameboard);
+
+    assert_eq!(bot.most_recent_chosen_coords, expected_chosen_placement);
+  }
+
+  #[test]
+  fn easy_places_on_the_only_empty_tile() {
+    let mut bot = bot_with_difficulty(Difficulty::Easy);
+    let gameboard = BoardConfig::from_notation("xoxxoooxx").unwrap();
+    let mut almost_full_gameboard = gameboard.clone();
+    almost_full_gameboard.place_tile(&(2, 2), BoardStates::Empty);
+
+    bot.choose_coordinates(&almost_full_gameboard);
+    assert_eq!(bot.most_recent_chosen_coords, Ok((2, 2)));
+
+    bot.choose_coordinates(&gameboard);
+    assert!(bot.most_recent_chosen_coords.is_err());
+  }
+
+  #[test]
+  fn difficulties_are_parsed() {
+    assert_eq!("Easy".parse::<Difficulty>().unwrap(), Difficulty::Easy);
+    assert_eq!("hard".parse::<Difficulty>().unwrap(), Difficulty::Hard);
+    assert!("impossible".parse::<Difficulty>().is_err());
+  }
+}
+
 #[cfg(test)]
 mod known_bugs {
   use super::*;
@@ -326,8 +388,7 @@ mod known_bugs {
     //
 
     for _x in 0..25 {
-      let mut gameconfig = GameConfig::new()
-        .unwrap_or_else(|error| panic!("An error has occured while grabbing config: '{error}'"));
+      let mut gameconfig = GameConfig::new();
 
       gameconfig.player_symbol = PLAYER_BOARD_SYMBOL;
       gameconfig.bot.bot_symbol = BOT_BOARD_SYMBOL;
@@ -377,3 +438,43 @@ mod known_bugs {
     assert_eq!(bot_edge_placement_count, expected_bot_edge_placements);
   }
 }
+
+#[cfg(test)]
+mod wild_logic {
+  use super::*;
+
+  #[test]
+  fn medium_completes_a_row_of_either_symbol() {
+    let mut bot = Bot::new();
+    bot.difficulty = Difficulty::Medium;
+
+    //O|O|-
+    //-|X|-
+    //-|-|-
+    let mut gameboard = BoardConfig::new();
+    gameboard.place_tile(&(0, 0), BoardStates::O);
+    gameboard.place_tile(&(0, 1), BoardStates::O);
+    gameboard.place_tile(&(1, 1), BoardStates::X);
+
+    assert_eq!(
+      bot.choose_wild_move(&gameboard),
+      Some((BoardStates::O, (0, 2)))
+    );
+  }
+
+  #[test]
+  fn medium_never_leaves_a_row_to_finish() {
+    let mut bot = Bot::new();
+    bot.difficulty = Difficulty::Medium;
+
+    let mut gameboard = BoardConfig::new();
+    gameboard.place_tile(&(1, 1), BoardStates::X);
+
+    for _ in 0..20 {
+      let (symbol, _) = bot.choose_wild_move(&gameboard).unwrap();
+
+      // Another X anywhere makes two in a row with the third tile open.
+      assert_eq!(symbol, BoardStates::O);
+    }
+  }
+}
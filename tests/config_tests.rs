@@ -0,0 +1,124 @@
+use std::time::Duration;
+use tictactoe_with_ai::board_render::Theme;
+use tictactoe_with_ai::bot::Difficulty;
+use tictactoe_with_ai::config::*;
+use tictactoe_with_ai::gameboard::BoardStates;
+use tictactoe_with_ai::gameplay::*;
+
+#[test]
+fn every_setting_is_read() {
+  let expected_config = ConfigFile {
+    mode: Some("tui".to_string()),
+    symbol: Some(BoardStates::O),
+    starts: Some(Starts::Bot),
+    difficulty: Some(Difficulty::Medium),
+    bot_play_delay: Some(Duration::from_millis(250)),
+    theme: Some(Theme::Emoji),
+    player_name: Some("Sam".to_string()),
+    misere: Some(true),
+  };
+
+  let config_file = ConfigFile::parse(
+    r#"
+mode = "tui"
+symbol = "o"
+starts = "bot"
+difficulty = "medium"
+bot_play_delay_ms = 250
+theme = "emoji"
+player_name = "Sam"
+misere = true
+"#,
+  )
+  .unwrap();
+
+  assert_eq!(config_file, expected_config);
+}
+
+#[test]
+fn empty_config_is_default() {
+  assert_eq!(ConfigFile::parse("").unwrap(), ConfigFile::default());
+}
+
+#[test]
+fn bad_settings_are_errors() {
+  let error_for = |contents: &str| ConfigFile::parse(contents).unwrap_err().to_string();
+
+  assert!(error_for("theme = \"neon\"").contains("unknown theme 'neon'"));
+  assert!(error_for("theme = true").contains("unknown theme 'true'"));
+  assert!(error_for("theme = 1.5").contains("theme must be a string, a number or true/false"));
+  assert!(error_for("misere = \"maybe\"").contains("misere must be true or false"));
+  assert!(
+    error_for("colour = \"red\"").starts_with("unknown setting 'colour', expected one of: mode")
+  );
+  assert!(error_for("mode = \"chess\"").contains("unknown mode 'chess'"));
+  assert!(error_for("symbol = \"z\"").contains("expected x or o"));
+  assert!(error_for("difficulty = \"impossible\"").contains("unknown difficulty"));
+  assert!(error_for("bot_play_delay_ms = -5").contains("from 0 to 60000"));
+  assert!(error_for("player_name = \"  \"").contains("can't be empty"));
+  assert!(error_for("symbol = \"o\"\nstarts = \"player\"").contains("symbol and starts disagree"));
+}
+
+#[test]
+fn missing_files_are_only_fine_by_default() {
+  let path = std::env::temp_dir().join("tictactoe_missing_config.toml");
+
+  assert!(ConfigFile::load(&path)
+    .unwrap_err()
+    .to_string()
+    .starts_with("failed to read"));
+}
+
+#[test]
+fn flags_override_the_file() {
+  let mut config_file = ConfigFile::parse("theme = \"box\"\ndifficulty = \"easy\"").unwrap();
+  let mut arguments: Vec<String> = ["bot_play", "--theme", "compact", "--x", "engine"]
+    .map(String::from)
+    .to_vec();
+
+  config_file.apply_flags(&mut arguments).unwrap();
+
+  assert_eq!(config_file.theme, Some(Theme::Compact));
+  assert_eq!(config_file.difficulty, Some(Difficulty::Easy));
+  assert_eq!(arguments, ["bot_play", "--x", "engine"]);
+
+  let mut arguments = vec!["--bot-play-delay-ms".to_string(), "soon".to_string()];
+
+  assert!(config_file
+    .apply_flags(&mut arguments)
+    .unwrap_err()
+    .to_string()
+    .starts_with("--bot-play-delay-ms: "));
+}
+
+#[test]
+fn flags_are_taken_out_of_the_arguments() {
+  let mut arguments: Vec<String> = ["bot_play", "--theme", "box", "--x", "engine"]
+    .map(String::from)
+    .to_vec();
+
+  let theme = take_flag(&mut arguments, "--theme").unwrap();
+  let missing_flag = take_flag(&mut arguments, "--config").unwrap();
+
+  assert_eq!(theme, Some("box".to_string()));
+  assert_eq!(missing_flag, None);
+  assert_eq!(arguments, ["bot_play", "--x", "engine"]);
+  assert!(take_flag(&mut vec!["--theme".to_string()], "--theme").is_err());
+}
+
+#[test]
+fn gameconfig_follows_the_config() {
+  let config_file = ConfigFile::parse(
+    "starts = \"bot\"\ndifficulty = \"easy\"\nbot_play_delay_ms = 0\nplayer_name = \"Sam\"",
+  )
+  .unwrap();
+
+  let gameconfig = GameConfig::from_config(&config_file).unwrap();
+
+  assert!(!gameconfig.player_turn);
+  assert_eq!(gameconfig.player_symbol, BoardStates::O);
+  assert_eq!(gameconfig.bot.bot_symbol, BoardStates::X);
+  assert_eq!(gameconfig.bot.difficulty, Difficulty::Easy);
+  assert_eq!(gameconfig.bot_play_delay, Duration::ZERO);
+  assert_eq!(gameconfig.player_name, "Sam");
+}
@@ -0,0 +1,191 @@
+use std::io::Cursor;
+use tictactoe_with_ai::bot::Difficulty;
+use tictactoe_with_ai::cube::*;
+use tictactoe_with_ai::gameboard::BoardStates;
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::search::*;
+
+fn place_all(cube_game: &mut CubeGame, tiles: &[&str]) {
+  for tile in tiles {
+    cube_game.place(&parse_cube_tile(tile).unwrap()).unwrap();
+  }
+}
+
+#[cfg(test)]
+mod rules {
+  use super::*;
+
+  #[test]
+  fn cubes_have_every_line() {
+    assert_eq!(CubeBoard::new(3).unwrap().lines().len(), 49);
+    assert_eq!(CubeBoard::new(4).unwrap().lines().len(), 76);
+    assert!(CubeBoard::new(5).is_err());
+  }
+
+  #[test]
+  fn corners_and_centers_are_on_the_most_lines() {
+    let cube_board = CubeBoard::new(4).unwrap();
+
+    assert_eq!(cube_board.lines_through(&(0, 0, 0)).count(), 7);
+    assert_eq!(cube_board.lines_through(&(1, 1, 1)).count(), 7);
+    assert_eq!(cube_board.lines_through(&(0, 0, 1)).count(), 4);
+  }
+
+  #[test]
+  fn lines_run_through_the_layers() {
+    let mut cube_game = CubeGame::new(3).unwrap();
+
+    place_all(&mut cube_game, &["a1@1", "a2@1", "b2@2", "a3@1"]);
+
+    assert_eq!(cube_game.result(), None);
+
+    place_all(&mut cube_game, &["c3@3"]);
+
+    assert_eq!(cube_game.result(), Some(GameState::XWon));
+    assert!(cube_game.cube_board.check_if_win());
+  }
+
+  #[test]
+  fn taken_and_missing_tiles_are_errors() {
+    let mut cube_game = CubeGame::new(3).unwrap();
+
+    place_all(&mut cube_game, &["b2@2"]);
+
+    assert_eq!(
+      cube_game.place(&(1, 1, 1)).unwrap_err().to_string(),
+      "b2@2 is already taken"
+    );
+    assert_eq!(
+      cube_game.place(&(3, 0, 0)).unwrap_err().to_string(),
+      "a1@4 is off the 3x3x3 cube"
+    );
+  }
+}
+
+#[cfg(test)]
+mod bot {
+  use super::*;
+
+  #[test]
+  fn bot_takes_a_win() {
+    let mut cube_game = CubeGame::new(4).unwrap();
+
+    place_all(
+      &mut cube_game,
+      &["a1@1", "a1@2", "b2@2", "a2@2", "c3@3", "a3@2"],
+    );
+
+    assert_eq!(best_move(&cube_game, 1), Some((3, 3, 3)));
+  }
+
+  #[test]
+  fn bot_blocks_a_line() {
+    let mut cube_game = CubeGame::new(4).unwrap();
+
+    place_all(&mut cube_game, &["a1@1", "d4@4", "a1@2", "d1@4", "a1@3"]);
+
+    assert_eq!(
+      best_move(&cube_game, search_depth::<CubeGame>(Difficulty::Medium)),
+      Some((3, 0, 0))
+    );
+  }
+
+  #[test]
+  fn bot_sets_up_a_double_threat() {
+    let mut cube_game = CubeGame::new(4).unwrap();
+
+    // X has two pairs crossing at a1@1 that O hasn't touched, taking it leaves two threes.
+    place_all(
+      &mut cube_game,
+      &[
+        "a2@1", "d4@4", "a3@1", "c4@4", "b1@1", "d3@4", "c1@1", "b3@3",
+      ],
+    );
+
+    let coords = best_move(&cube_game, search_depth::<CubeGame>(Difficulty::Medium)).unwrap();
+    cube_game.place(&coords).unwrap();
+
+    assert!(cube_game.cube_board.winning_tiles(BoardStates::X).len() > 1);
+  }
+
+  #[test]
+  fn first_player_wins_the_small_cube() {
+    let mut cube_game = CubeGame::new(3).unwrap();
+
+    while cube_game.result().is_none() {
+      let depth = match cube_game.next_symbol {
+        BoardStates::X => search_depth::<CubeGame>(Difficulty::Medium),
+        _ => search_depth::<CubeGame>(Difficulty::Easy),
+      };
+
+      let coords = best_move(&cube_game, depth).unwrap();
+      cube_game.place(&coords).unwrap();
+    }
+
+    assert_eq!(cube_game.result(), Some(GameState::XWon));
+  }
+}
+
+#[cfg(test)]
+mod interface {
+  use super::*;
+
+  #[test]
+  fn tiles_are_parsed_with_their_layer() {
+    assert_eq!(parse_cube_tile("b2@1\n").unwrap(), (0, 1, 1));
+    assert_eq!(parse_cube_tile("D4@3").unwrap(), (2, 3, 3));
+    assert!(parse_cube_tile("b2").is_err());
+    assert!(parse_cube_tile("b0@1").is_err());
+    assert!(parse_cube_tile("b2@0").is_err());
+    assert_eq!(cube_tile_notation(&(2, 3, 3)), "d4@3");
+  }
+
+  #[test]
+  fn layers_are_rendered_side_by_side() {
+    let mut cube_game = CubeGame::new(3).unwrap();
+
+    place_all(&mut cube_game, &["b2@1", "b2@2"]);
+
+    let rendered = render_cube(&cube_game.cube_board);
+    let lines: Vec<&str> = rendered.lines().collect();
+
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], "  layer 1       layer 2       layer 3");
+    assert_eq!(lines[3], "b  .  X  .    b  . [O] .    b  .  .  .");
+  }
+
+  #[test]
+  fn session_plays_until_the_game_ends() {
+    let mut gameconfig = GameConfig {
+      player_symbol: BoardStates::X,
+      ..GameConfig::default()
+    };
+
+    gameconfig.bot.difficulty = Difficulty::Easy;
+
+    let tiles: Vec<String> = (1..=3)
+      .flat_map(|layer| {
+        ["a", "b", "c"]
+          .into_iter()
+          .flat_map(move |row| (1..=3).map(move |column| format!("{row}{column}@{layer}\n")))
+      })
+      .collect();
+
+    let script = format!("b2\n{}", tiles.concat());
+
+    let mut output = vec![];
+    let end_gamestate = cube_session(
+      gameconfig,
+      CubeGame::new(3).unwrap(),
+      Cursor::new(script),
+      &mut output,
+    )
+    .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("give the tile then its layer, e.g. 'b2@1'"));
+    assert!(output.contains(" -- bot placed on "));
+    assert!(output.ends_with(&format!("{end_gamestate:?}\n")));
+  }
+}
@@ -0,0 +1,140 @@
+use std::io::Cursor;
+use tictactoe_with_ai::engine_protocol::*;
+use tictactoe_with_ai::gameboard::*;
+
+fn run_commands(commands: &str) -> String {
+  let mut output = Vec::new();
+
+  run_engine_protocol(Cursor::new(commands), &mut output).unwrap();
+
+  String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn uci_handshake() {
+  let expected_output = "id name tictactoe_with_ai\nuciok\nreadyok\n";
+
+  let output = run_commands("uci\nisready\nquit\n");
+
+  assert_eq!(output, expected_output);
+}
+
+#[test]
+fn bot_blocks_series_of_two() {
+  let expected_output = "bestmove a3\n";
+
+  //X|X|-
+  //-|O|-
+  //-|-|-
+  let output = run_commands("position startpos moves a1 b2 a2\ngo\n");
+
+  assert_eq!(output, expected_output);
+}
+
+#[test]
+fn bot_blocks_series_of_two_from_notation() {
+  let expected_output = "bestmove c3\n";
+
+  //X|-|O
+  //-|X|-
+  //-|-|-
+  let output = run_commands("position x.o.x.... moves\ngo\n");
+
+  assert_eq!(output, expected_output);
+}
+
+#[test]
+fn taken_tile_is_reported() {
+  let expected_output = "info string tile a1 is already taken\n";
+
+  let output = run_commands("position startpos moves a1 1\n");
+
+  assert_eq!(output, expected_output);
+}
+
+#[test]
+fn blocked_tile_is_reported() {
+  let expected_output = "info string tile b2 is blocked\n";
+
+  let output = run_commands("position ....#.... moves b2\n");
+
+  assert_eq!(output, expected_output);
+}
+
+#[test]
+fn bot_plays_around_blocked_tiles() {
+  let expected_output = "bestmove b3\n";
+
+  //X|X|#
+  //O|O|-
+  //-|-|-
+  // X's row is blocked, so all that's left is stopping O's.
+  let output = run_commands("position xx#oo.... moves\ngo\n");
+
+  assert_eq!(output, expected_output);
+}
+
+#[test]
+fn finished_game_has_no_best_move() {
+  let expected_output = "bestmove none\n";
+
+  //X|X|X
+  //O|O|-
+  //-|-|-
+  let output = run_commands("position xxxoo....\ngo\n");
+
+  assert_eq!(output, expected_output);
+}
+
+#[test]
+fn unknown_commands_dont_end_the_session() {
+  let expected_output = "info string unknown command 'fly'\nreadyok\n";
+
+  let output = run_commands("fly\nisready\n");
+
+  assert_eq!(output, expected_output);
+}
+
+#[test]
+fn bot_against_itself_draws() {
+  let mut session = EngineSession::new();
+
+  while let Some(coords) = session.best_move() {
+    let mut moves = session.moves.clone();
+    moves.push(coords);
+
+    session.set_position(BoardConfig::new(), moves).unwrap();
+  }
+
+  let gameboard = session.current_board();
+
+  assert_eq!(gameboard.winning_row(), None, "{}", gameboard.to_notation());
+  assert_eq!(gameboard.tiles_covered, 9);
+}
+
+#[test]
+fn histories_the_bot_never_chose_still_get_a_move() {
+  // These used to send the bot down paths it couldn't finish.
+  let histories = [
+    "b2 a1 a3 c2",
+    "b2 a1 c1 b3",
+    "b2 a3 a1 c2",
+    "b2 a3 c3 b1",
+    "b2 c1 a1 b3",
+    "b2 c1 c3 a2",
+    "b2 c3 a3 b1",
+    "b2 c3 c1 a2",
+  ];
+
+  for history in histories {
+    let output = run_commands(&format!("position startpos moves {history}\ngo\n"));
+    let best_move = output
+      .strip_prefix("bestmove ")
+      .unwrap_or_else(|| panic!("{history}: {output}"))
+      .trim();
+
+    let replayed = run_commands(&format!("position startpos moves {history} {best_move}\n"));
+
+    assert_eq!(replayed, "", "{history} {best_move}");
+  }
+}
@@ -0,0 +1,132 @@
+use std::io;
+use std::process::Command;
+use std::time::Duration;
+use tictactoe_with_ai::bot::Bot;
+use tictactoe_with_ai::external_engine::*;
+use tictactoe_with_ai::gameboard::*;
+use tictactoe_with_ai::gameplay::*;
+
+fn this_crate_as_engine(engine_symbol: BoardStates) -> ExternalEngine {
+  let mut command = Command::new(env!("CARGO_BIN_EXE_tictactoe_with_ai"));
+  command.arg("engine");
+  // Nothing in this directory, so the engine doesn't pick up the config of whoever runs the tests.
+  command.env(
+    "XDG_CONFIG_HOME",
+    std::env::temp_dir().join("tictactoe_no_config"),
+  );
+
+  ExternalEngine::from_command(command, engine_symbol).unwrap()
+}
+
+/// An engine that finishes the handshake then replies to every 'go' with the given line.
+fn scripted_engine(reply: &str, engine_symbol: BoardStates) -> ExternalEngine {
+  let script = format!(
+    "while read command; do
+      case $command in
+        uci) echo uciok ;;
+        go) {reply} ;;
+      esac
+    done"
+  );
+
+  let mut command = Command::new("sh");
+  command.args(["-c", &script]);
+
+  ExternalEngine::from_command(command, engine_symbol).unwrap()
+}
+
+fn bot_with_symbol(bot_symbol: BoardStates) -> Bot {
+  let mut bot = Bot::new();
+  bot.bot_symbol = bot_symbol;
+
+  bot
+}
+
+#[test]
+fn engine_mode_draws_against_bot() {
+  for engine_symbol in [BoardStates::X, BoardStates::O] {
+    let bot_symbol = if engine_symbol == BoardStates::X {
+      BoardStates::O
+    } else {
+      BoardStates::X
+    };
+
+    let end_gamestate = opponents_play(
+      GameConfig::new(),
+      Opponent::External(this_crate_as_engine(engine_symbol)),
+      Opponent::Bot(bot_with_symbol(bot_symbol)),
+      Duration::ZERO,
+      io::sink(),
+    )
+    .unwrap();
+
+    assert_eq!(end_gamestate, GameState::Draw);
+  }
+}
+
+#[test]
+fn engine_sees_moves_from_the_board() {
+  let mut engine = this_crate_as_engine(BoardStates::O);
+  let mut gameboard = BoardConfig::new();
+  let expected_move = Ok((0, 2));
+
+  //X|X|-
+  //-|O|-
+  //-|-|-
+  gameboard.place_tile(&(0, 0), BoardStates::X);
+  gameboard.place_tile(&(1, 1), BoardStates::O);
+  gameboard.place_tile(&(0, 1), BoardStates::X);
+
+  assert_eq!(engine.request_move(&gameboard), expected_move);
+  assert_eq!(engine.moves, vec![(0, 0), (1, 1), (0, 1), (0, 2)]);
+}
+
+#[test]
+fn illegal_move_forfeits() {
+  let end_gamestate = opponents_play(
+    GameConfig::new(),
+    Opponent::Bot(bot_with_symbol(BoardStates::O)),
+    Opponent::External(scripted_engine("echo bestmove z9", BoardStates::X)),
+    Duration::ZERO,
+    io::sink(),
+  )
+  .unwrap();
+
+  assert_eq!(end_gamestate, GameState::OWon);
+}
+
+#[test]
+fn taken_tile_forfeits() {
+  let mut engine = scripted_engine("echo bestmove b2", BoardStates::O);
+  let mut gameboard = BoardConfig::new();
+
+  gameboard.place_tile(&(1, 1), BoardStates::X);
+
+  assert!(matches!(
+    engine.request_move(&gameboard),
+    Err(Forfeit::IllegalMove(_))
+  ));
+}
+
+#[test]
+fn late_reply_forfeits() {
+  let mut engine = scripted_engine("sleep 1; echo bestmove b2", BoardStates::O);
+  engine.move_timeout = Duration::from_millis(100);
+
+  let end_gamestate = opponents_play(
+    GameConfig::new(),
+    Opponent::Bot(bot_with_symbol(BoardStates::X)),
+    Opponent::External(engine),
+    Duration::ZERO,
+    io::sink(),
+  )
+  .unwrap();
+
+  assert_eq!(end_gamestate, GameState::XWon);
+}
+
+#[test]
+fn engine_without_handshake_fails_to_launch() {
+  assert!(ExternalEngine::launch("true", BoardStates::X).is_err());
+  assert!(ExternalEngine::launch("", BoardStates::X).is_err());
+}
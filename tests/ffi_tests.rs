@@ -0,0 +1,85 @@
+use std::ptr;
+use tictactoe_with_ai::ffi::*;
+
+#[test]
+fn game_is_played_through_the_c_interface() {
+  let game = ttt_game_new(TttSymbol::O as u32);
+  let (mut row, mut column) = (usize::MAX, usize::MAX);
+
+  unsafe {
+    assert_eq!(ttt_game_player_symbol(game), TttSymbol::O);
+    assert_eq!(ttt_game_place_tile(game, 0, 0), TttStatus::NotYourTurn);
+    assert_eq!(
+      ttt_game_bot_move(game, &mut row, &mut column),
+      TttStatus::Ok
+    );
+    assert_eq!(ttt_game_get_tile(game, row, column), TttSymbol::X);
+    assert_eq!(ttt_game_place_tile(game, row, column), TttStatus::TileTaken);
+    assert_eq!(ttt_game_place_tile(game, 3, 0), TttStatus::OutOfRange);
+
+    while ttt_game_outcome(game) == TttOutcome::InProgress {
+      if ttt_game_bot_move(game, ptr::null_mut(), ptr::null_mut()) == TttStatus::Ok {
+        continue;
+      }
+
+      let empty_tile = (0..9)
+        .find(|index| ttt_game_get_tile(game, index / 3, index % 3) == TttSymbol::Empty)
+        .unwrap();
+
+      assert_eq!(
+        ttt_game_place_tile(game, empty_tile / 3, empty_tile % 3),
+        TttStatus::Ok
+      );
+    }
+
+    assert_ne!(ttt_game_outcome(game), TttOutcome::OWon);
+    assert_eq!(ttt_game_place_tile(game, 0, 0), TttStatus::GameOver);
+
+    ttt_game_free(game);
+  }
+}
+
+#[test]
+fn null_games_are_handled() {
+  unsafe {
+    assert_eq!(
+      ttt_game_place_tile(ptr::null_mut(), 0, 0),
+      TttStatus::NullPointer
+    );
+    assert_eq!(
+      ttt_game_bot_move(ptr::null_mut(), ptr::null_mut(), ptr::null_mut()),
+      TttStatus::NullPointer
+    );
+    assert_eq!(ttt_game_get_tile(ptr::null(), 0, 0), TttSymbol::Empty);
+    assert_eq!(ttt_game_outcome(ptr::null()), TttOutcome::InProgress);
+
+    ttt_game_free(ptr::null_mut());
+  }
+}
+
+#[test]
+fn header_declares_every_function() {
+  let header = include_str!("../include/tictactoe_with_ai.h");
+
+  for function in [
+    "ttt_game_new",
+    "ttt_game_free",
+    "ttt_game_place_tile",
+    "ttt_game_bot_move",
+    "ttt_game_get_tile",
+    "ttt_game_outcome",
+    "ttt_game_player_symbol",
+  ] {
+    assert!(header.contains(&format!("{function}(")), "{function}");
+  }
+}
+
+#[test]
+fn checked_in_header_is_up_to_date() {
+  let generated = include_str!(concat!(env!("OUT_DIR"), "/tictactoe_with_ai.h"));
+
+  assert!(
+    include_str!("../include/tictactoe_with_ai.h") == generated,
+    "run 'cbindgen --config cbindgen.toml --output include/tictactoe_with_ai.h'"
+  );
+}
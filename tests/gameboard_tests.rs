@@ -104,3 +104,61 @@ mod two_in_series {
     assert_eq!(series, expected_coordinates);
   }
 }
+
+#[cfg(test)]
+mod notation {
+  use super::*;
+
+  #[test]
+  fn notation_round_trip() {
+    let notation = "x.o.x...o";
+
+    let gameboard = BoardConfig::from_notation(notation).unwrap();
+
+    assert_eq!(gameboard.to_notation(), notation);
+    assert_eq!(gameboard.tiles_covered, 4);
+    assert_eq!(gameboard.get_board_state(&(0, 2)), &BoardStates::O);
+  }
+
+  #[test]
+  fn blocked_tiles_round_trip_and_count_as_covered() {
+    let gameboard = BoardConfig::from_notation("x#o#xoxo#").unwrap();
+
+    assert_eq!(gameboard.to_notation(), "x#o#xoxo#");
+    assert_eq!(gameboard.get_board_state(&(0, 1)), &BoardStates::Blocked);
+    assert_eq!(gameboard.tiles_covered, 6);
+    assert_eq!(gameboard.blocked_tile_count(), 3);
+    assert!(gameboard.all_tiles_covered());
+    assert!(!BoardConfig::from_notation("x#o#xo...")
+      .unwrap()
+      .all_tiles_covered());
+  }
+
+  #[test]
+  fn blocked_rows_never_win() {
+    let gameboard = BoardConfig::from_notation("###x.o...").unwrap();
+
+    assert_eq!(gameboard.winning_row(), None);
+    assert!(gameboard.matching_adjacent_tiles(&(0, 0)).is_empty());
+  }
+
+  #[test]
+  fn unreachable_notation_is_rejected() {
+    assert!(BoardConfig::from_notation("xxx......").is_err());
+    assert!(BoardConfig::from_notation("x.o").is_err());
+    assert!(BoardConfig::from_notation("x.o.z....").is_err());
+  }
+
+  #[test]
+  fn winning_row_is_found() {
+    let expected_row = Some([(0, 2), (1, 1), (2, 0)]);
+
+    //O|O|X
+    //-|X|-
+    //X|-|-
+    let gameboard = BoardConfig::from_notation("oox.x.x..").unwrap();
+
+    assert_eq!(gameboard.winning_row(), expected_row);
+    assert_eq!(gameboard.next_symbol_to_place(), BoardStates::O);
+  }
+}
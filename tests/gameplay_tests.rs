@@ -11,14 +11,26 @@ fn testing_coordinate_conversion() {
   assert_eq!(coordinate_conversion, expected_outcome);
 }
 
+#[test]
+fn unknown_gamemodes_are_errors() {
+  let error = run_gamemode(Some("chess".to_string()), &[], GameConfig::new()).unwrap_err();
+
+  assert_eq!(
+    error.to_string(),
+    format!(
+      "unknown gamemode 'chess', expected one of: {}",
+      GAMEMODES.join(", ")
+    )
+  );
+}
+
 #[cfg(test)]
 mod check_if_win_logic {
   use super::*;
 
   #[test]
   fn side_vaild_win() {
-    let mut gameconfig = GameConfig::new()
-      .unwrap_or_else(|error| panic!("An error has occured while grabbing config: '{error}'"));
+    let mut gameconfig = GameConfig::new();
     let checking_from = (0, 0);
     let expected_outcome = true;
 
@@ -36,8 +48,7 @@ mod check_if_win_logic {
 
   #[test]
   fn in_between_valid_win() {
-    let mut gameconfig = GameConfig::new()
-      .unwrap_or_else(|error| panic!("An error has occured while grabbing config: '{error}'"));
+    let mut gameconfig = GameConfig::new();
     let checking_from = (1, 0);
     let expected_outcome = true;
 
@@ -55,8 +66,7 @@ mod check_if_win_logic {
 
   #[test]
   fn two_in_a_row_possible_overflow() {
-    let mut gameconfig = GameConfig::new()
-      .unwrap_or_else(|error| panic!("An error has occured while grabbing config: '{error}'"));
+    let mut gameconfig = GameConfig::new();
     let checking_from = (1, 0);
     let expected_outcome = false;
 
@@ -71,3 +81,158 @@ mod check_if_win_logic {
     assert_eq!(gameconfig.check_if_win(), expected_outcome);
   }
 }
+
+#[cfg(test)]
+mod scripted_games {
+  use super::*;
+
+  fn gameconfig_with_x_player() -> GameConfig {
+    let mut gameconfig = GameConfig::new();
+
+    gameconfig.player_turn = true;
+    gameconfig.player_symbol = BoardStates::X;
+    gameconfig.bot.bot_symbol = BoardStates::O;
+
+    gameconfig
+  }
+
+  fn play_free_play(script: &str) -> (Result<GameState, String>, String) {
+    let mut transcript = Vec::new();
+
+    let end_gamestate = free_play_session(
+      gameconfig_with_x_player(),
+      script.as_bytes(),
+      &mut transcript,
+    )
+    .map_err(|error| error.to_string());
+
+    (end_gamestate, String::from_utf8(transcript).unwrap())
+  }
+
+  #[test]
+  fn free_play_to_a_win() {
+    //X|X|X
+    //O|O|-
+    //-|-|-
+    let (end_gamestate, transcript) = play_free_play("a1\nb1\na2\nb2\na3\n");
+
+    assert_eq!(end_gamestate, Ok(GameState::XWon));
+    assert_eq!(transcript.matches("Select a tile").count(), 5);
+    assert!(transcript.contains("XWon\n"));
+    assert!(transcript.contains("*X*|*X*|*X*"));
+  }
+
+  #[test]
+  fn free_play_to_a_draw() {
+    //X|O|X
+    //X|O|O
+    //O|X|X
+    let (end_gamestate, transcript) = play_free_play("1\n2\n3\n5\n4\n6\n8\n7\n9\n");
+
+    assert_eq!(end_gamestate, Ok(GameState::Draw));
+    assert!(transcript.ends_with("Draw\n     1   2   3\n a   X | O | X\n    ---+---+---\n b   X | O | O\n    ---+---+---\n c   O | X |[X]\n"));
+  }
+
+  #[test]
+  fn bad_selections_are_asked_again() {
+    let (end_gamestate, transcript) = play_free_play("z9\nb2\nb2\n0\n");
+
+    assert_eq!(end_gamestate, Err(INPUT_ENDED_ERROR.to_string()));
+    assert_eq!(transcript.matches("incorrect input").count(), 2);
+    assert_eq!(transcript.matches("b2 is already taken").count(), 1);
+    assert_eq!(transcript.matches("Select a tile").count(), 5);
+  }
+
+  #[test]
+  fn running_out_of_input_is_told_apart_by_kind() {
+    let error =
+      free_play_session(gameconfig_with_x_player(), "z9\n".as_bytes(), &mut vec![]).unwrap_err();
+
+    assert!(is_input_ended(error.as_ref()));
+    assert!(!is_input_ended(
+      Box::<dyn std::error::Error>::from(INPUT_ENDED_ERROR).as_ref()
+    ));
+  }
+
+  #[test]
+  fn bot_is_never_beaten_by_a_script() {
+    let script = "1\n2\n3\n4\n5\n6\n7\n8\n9\n".repeat(2);
+    let mut transcript = Vec::new();
+
+    let end_gamestate = player_vs_bot_session(
+      gameconfig_with_x_player(),
+      script.as_bytes(),
+      &mut transcript,
+    )
+    .unwrap();
+    let transcript = String::from_utf8(transcript).unwrap();
+
+    assert_ne!(end_gamestate, GameState::XWon);
+    assert!(transcript.contains(" -- bot turn over --"));
+    assert!(transcript.contains(&format!("{end_gamestate:?}\n")));
+  }
+}
+
+#[cfg(test)]
+mod misere_games {
+  use super::*;
+  use tictactoe_with_ai::config::ConfigFile;
+
+  fn misere_gameconfig(player_symbol: &str) -> GameConfig {
+    let mut config_file = ConfigFile::parse("misere = true").unwrap();
+    config_file.set("symbol", player_symbol).unwrap();
+
+    GameConfig::from_config(&config_file).unwrap()
+  }
+
+  #[test]
+  fn completing_a_row_loses() {
+    let mut transcript = Vec::new();
+
+    //X|X|X
+    //O|O|-
+    //-|-|-
+    let end_gamestate = free_play_session(
+      misere_gameconfig("x"),
+      "a1\nb1\na2\nb2\na3\n".as_bytes(),
+      &mut transcript,
+    )
+    .unwrap();
+    let transcript = String::from_utf8(transcript).unwrap();
+
+    assert_eq!(end_gamestate, GameState::OWon);
+    assert!(transcript.contains("OWon\n -- X completed three in a row, which loses in misère --"));
+  }
+
+  #[test]
+  fn current_result_is_reversed() {
+    let mut gameconfig = misere_gameconfig("o");
+    gameconfig.gameboard = BoardConfig::from_notation("ooxxxx.oo").unwrap();
+    gameconfig.gameboard.last_modified_tile = Some((1, 2));
+
+    assert_eq!(gameconfig.current_result(), Some(GameState::OWon));
+  }
+
+  #[test]
+  fn bot_never_completes_a_row_first() {
+    for player_symbol in ["x", "o"] {
+      let script = "1\n2\n3\n4\n5\n6\n7\n8\n9\n".repeat(2);
+      let mut transcript = Vec::new();
+      let gameconfig = misere_gameconfig(player_symbol);
+      let bot_symbol = gameconfig.bot.bot_symbol;
+
+      let end_gamestate =
+        player_vs_bot_session(gameconfig, script.as_bytes(), &mut transcript).unwrap();
+
+      let bot_lost = match bot_symbol {
+        BoardStates::X => GameState::OWon,
+        _ => GameState::XWon,
+      };
+
+      assert_ne!(end_gamestate, bot_lost);
+      assert!(String::from_utf8(transcript)
+        .unwrap()
+        .contains(MISERE_REMINDER));
+    }
+  }
+}
@@ -0,0 +1,252 @@
+use std::io::Cursor;
+use std::time::{Duration, Instant};
+use tictactoe_with_ai::bot::{Difficulty, SearchBudget};
+use tictactoe_with_ai::gameboard::BoardStates;
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::gomoku::*;
+use tictactoe_with_ai::grid_board::parse_grid_tile;
+
+fn later() -> Instant {
+  Instant::now() + Duration::from_secs(60)
+}
+
+/// A game with stones put straight down rather than taking turns, X to place next.
+fn game_with(x_tiles: &[&str], o_tiles: &[&str]) -> GomokuGame {
+  let mut gomoku_game = GomokuGame::new();
+
+  for (tiles, symbol) in [(x_tiles, BoardStates::X), (o_tiles, BoardStates::O)] {
+    for tile in tiles {
+      let coords = parse_grid_tile(tile, &gomoku_game.grid_board).unwrap();
+
+      gomoku_game.grid_board.place_tile(&coords, symbol);
+    }
+  }
+
+  gomoku_game
+}
+
+/// The player as X going first, against an easy bot.
+fn gameconfig() -> GameConfig {
+  let mut gameconfig = GameConfig {
+    player_symbol: BoardStates::X,
+    ..GameConfig::default()
+  };
+
+  gameconfig.bot.bot_symbol = BoardStates::O;
+  gameconfig.bot.difficulty = Difficulty::Easy;
+
+  gameconfig
+}
+
+const ACROSS: (isize, isize) = (0, 1);
+
+#[cfg(test)]
+mod threats {
+  use super::*;
+
+  #[test]
+  fn threats_along_a_line() {
+    let threat_on_h8 = |x_tiles: &[&str], o_tiles: &[&str]| {
+      let gomoku_game = game_with(x_tiles, o_tiles);
+
+      threat(&gomoku_game.grid_board, &(7, 7), BoardStates::X, ACROSS)
+    };
+
+    assert_eq!(threat_on_h8(&["h6", "h7"], &[]), Threat::OpenThree);
+    assert_eq!(threat_on_h8(&["h5", "h6", "h7"], &["h4"]), Threat::Four);
+    assert_eq!(threat_on_h8(&["h5", "h6", "h7"], &[]), Threat::OpenFour);
+    assert_eq!(threat_on_h8(&["h4", "h6", "h7"], &[]), Threat::Four);
+    assert_eq!(threat_on_h8(&["h4", "h5", "h6", "h7"], &[]), Threat::Five);
+  }
+
+  #[test]
+  fn a_three_against_a_stone_isnt_open() {
+    let gomoku_game = game_with(&["h6", "h7"], &["h5"]);
+
+    assert_eq!(
+      threat(&gomoku_game.grid_board, &(7, 7), BoardStates::X, ACROSS),
+      Threat::Nothing
+    );
+    assert_eq!(
+      strongest_threat(&gomoku_game.grid_board, &(7, 7), BoardStates::O),
+      Threat::Nothing
+    );
+  }
+
+  #[test]
+  fn winning_tiles_finish_a_five() {
+    let gomoku_game = game_with(&["h4", "h5", "h6", "h7"], &["a1"]);
+
+    assert_eq!(
+      winning_tiles(&gomoku_game.grid_board, BoardStates::X),
+      vec![(7, 2), (7, 7)]
+    );
+    assert!(winning_tiles(&gomoku_game.grid_board, BoardStates::O).is_empty());
+  }
+}
+
+#[cfg(test)]
+mod candidates {
+  use super::*;
+
+  #[test]
+  fn an_empty_board_starts_in_the_middle() {
+    assert_eq!(GomokuGame::new().candidate_tiles(ROOT_WIDTH), vec![(7, 7)]);
+  }
+
+  #[test]
+  fn only_tiles_near_stones_are_searched() {
+    let gomoku_game = game_with(&["h8"], &[]);
+    let nearby_tiles = gomoku_game.nearby_tiles();
+
+    assert_eq!(nearby_tiles.len(), 16);
+    assert!(nearby_tiles
+      .iter()
+      .all(|coords| coords.0.abs_diff(7) <= 2 && coords.1.abs_diff(7) <= 2));
+    assert_eq!(gomoku_game.candidate_tiles(ROOT_WIDTH).len(), ROOT_WIDTH);
+  }
+
+  #[test]
+  fn a_four_has_to_be_blocked() {
+    let gomoku_game = game_with(&["a1", "o15"], &["d4", "e5", "f6", "g7"]);
+
+    assert_eq!(
+      gomoku_game.candidate_tiles(ROOT_WIDTH),
+      vec![(2, 2), (7, 7)]
+    );
+  }
+}
+
+#[cfg(test)]
+mod bot {
+  use super::*;
+
+  fn budget(max_depth: u32) -> SearchBudget {
+    SearchBudget {
+      max_depth,
+      time_limit: Duration::from_secs(5),
+    }
+  }
+
+  #[test]
+  fn bot_takes_a_five_over_blocking() {
+    let gomoku_game = game_with(&["h4", "h5", "h6", "h7"], &["a1", "a2", "a3", "a4"]);
+
+    assert!(matches!(
+      best_move(&gomoku_game, budget(2)),
+      Some((7, 2) | (7, 7))
+    ));
+  }
+
+  #[test]
+  fn bot_blocks_a_four() {
+    let gomoku_game = game_with(&["a1", "o15"], &["d5", "e5", "f5", "g5"]);
+
+    assert!(matches!(
+      best_move(&gomoku_game, budget(2)),
+      Some((2, 4) | (7, 4))
+    ));
+  }
+
+  #[test]
+  fn threat_space_search_finds_a_double_four() {
+    // Placing on h7 makes a four along row h and down column 7 at once.
+    let gomoku_game = game_with(&["h3", "h4", "h5", "d7", "e7", "f7"], &["h2", "c7"]);
+
+    assert_eq!(threat_space_search(&gomoku_game, 1, later()), Some((7, 6)));
+
+    let mut next_game = gomoku_game.clone();
+    next_game.place(&(7, 6)).unwrap();
+
+    assert_eq!(
+      winning_tiles(&next_game.grid_board, BoardStates::X),
+      vec![(6, 6), (7, 5)]
+    );
+  }
+
+  #[test]
+  fn threat_space_search_needs_fours() {
+    let gomoku_game = game_with(&["h3", "h4"], &["a1"]);
+
+    assert_eq!(
+      threat_space_search(&gomoku_game, THREAT_SEARCH_DEPTH, later()),
+      None
+    );
+  }
+
+  #[test]
+  fn threat_space_search_stops_at_the_deadline() {
+    let gomoku_game = game_with(&["h3", "h4", "h5", "d7", "e7", "f7"], &["h2", "c7"]);
+
+    assert_eq!(
+      threat_space_search(&gomoku_game, THREAT_SEARCH_DEPTH, Instant::now()),
+      None
+    );
+  }
+
+  #[test]
+  fn out_of_time_still_gives_a_move() {
+    let gomoku_game = game_with(&["h8", "g7"], &["h7"]);
+    let started = Instant::now();
+
+    let coords = best_move(
+      &gomoku_game,
+      SearchBudget {
+        max_depth: 20,
+        time_limit: Duration::ZERO,
+      },
+    )
+    .unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(2));
+    assert_eq!(
+      gomoku_game.grid_board.get_board_state(&coords),
+      &BoardStates::Empty
+    );
+  }
+}
+
+#[cfg(test)]
+mod interface {
+  use super::*;
+
+  #[test]
+  fn session_reports_bad_tiles_then_plays() {
+    let mut output = vec![];
+    let result = gomoku_session(
+      gameconfig(),
+      GomokuGame::new(),
+      search_budget(Difficulty::Easy),
+      Cursor::new("z9\nh8\nh8\n"),
+      &mut output,
+    );
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(result.unwrap_err().to_string(), INPUT_ENDED_ERROR);
+    assert!(output.starts_with("You X | Bot O | 5 in a row wins\n"));
+    assert!(output.contains("z9 is off the 15x15 board"));
+    assert!(output.contains(" -- bot placed on "));
+    assert!(output.contains("h8 is already taken"));
+  }
+
+  #[test]
+  fn bot_wins_a_game_left_open() {
+    let script: String = (1..=15).map(|column| format!("a{column}\n")).collect();
+
+    let mut output = vec![];
+    let end_gamestate = gomoku_session(
+      gameconfig(),
+      GomokuGame::new(),
+      search_budget(Difficulty::Easy),
+      Cursor::new(script),
+      &mut output,
+    )
+    .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(end_gamestate, GameState::OWon);
+    assert!(output.ends_with("OWon\n"));
+  }
+}
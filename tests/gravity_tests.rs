@@ -0,0 +1,170 @@
+use std::io::Cursor;
+use tictactoe_with_ai::bot::Difficulty;
+use tictactoe_with_ai::gameboard::BoardStates;
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::gravity::*;
+use tictactoe_with_ai::search::*;
+
+fn connect_four() -> GravityGame {
+  GravityGame::new(DEFAULT_WIDTH, DEFAULT_HEIGHT, DEFAULT_WIN_LENGTH).unwrap()
+}
+
+fn drop_all(gravity_game: &mut GravityGame, columns: &[usize]) {
+  for column in columns {
+    gravity_game.drop_piece(column - 1).unwrap();
+  }
+}
+
+#[cfg(test)]
+mod rules {
+  use super::*;
+
+  #[test]
+  fn pieces_fall_to_the_lowest_empty_tile() {
+    let mut gravity_game = connect_four();
+
+    assert_eq!(gravity_game.drop_piece(3).unwrap(), (5, 3));
+    assert_eq!(gravity_game.drop_piece(3).unwrap(), (4, 3));
+    assert_eq!(
+      gravity_game.grid_board.get_board_state(&(5, 3)),
+      &BoardStates::X
+    );
+    assert_eq!(
+      gravity_game.grid_board.get_board_state(&(4, 3)),
+      &BoardStates::O
+    );
+  }
+
+  #[test]
+  fn full_and_missing_columns_are_errors() {
+    let mut gravity_game = connect_four();
+
+    drop_all(&mut gravity_game, &[1, 1, 1, 1, 1, 1]);
+
+    assert_eq!(
+      gravity_game.drop_piece(0).unwrap_err().to_string(),
+      "column 1 is full"
+    );
+    assert_eq!(
+      gravity_game.drop_piece(7).unwrap_err().to_string(),
+      "there are only 7 columns"
+    );
+    assert!(!gravity_game.legal_columns().contains(&0));
+  }
+
+  #[test]
+  fn four_in_a_row_wins() {
+    let mut gravity_game = connect_four();
+
+    drop_all(&mut gravity_game, &[1, 1, 2, 2, 3, 3]);
+
+    assert_eq!(gravity_game.result(), None);
+
+    drop_all(&mut gravity_game, &[4]);
+
+    assert_eq!(gravity_game.result(), Some(GameState::XWon));
+    assert_eq!(
+      gravity_game.drop_piece(5).unwrap_err().to_string(),
+      "the game is over"
+    );
+  }
+
+  #[test]
+  fn columns_are_tried_from_the_middle() {
+    assert_eq!(connect_four().legal_columns(), vec![3, 2, 4, 1, 5, 0, 6]);
+  }
+}
+
+#[cfg(test)]
+mod bot {
+  use super::*;
+
+  #[test]
+  fn bot_takes_a_win() {
+    let mut gravity_game = connect_four();
+
+    drop_all(&mut gravity_game, &[1, 1, 2, 2, 3, 3]);
+
+    assert_eq!(best_move(&gravity_game, 1), Some(3));
+  }
+
+  #[test]
+  fn bot_blocks_an_open_three() {
+    let mut gravity_game = connect_four();
+
+    // X has 3 and 4 along the bottom with both ends open, O has to stop it now.
+    drop_all(&mut gravity_game, &[4, 4, 3]);
+
+    let column = best_move(
+      &gravity_game,
+      search_depth::<GravityGame>(Difficulty::Medium),
+    )
+    .unwrap();
+
+    assert!([1, 4].contains(&column), "dropped in {}", column + 1);
+  }
+
+  #[test]
+  fn bot_never_loses_gravity_tic_tac_toe() {
+    for bot_symbol in [BoardStates::X, BoardStates::O] {
+      let mut gravity_game = GravityGame::new(3, 3, 3).unwrap();
+
+      while gravity_game.result().is_none() {
+        let depth = if gravity_game.next_symbol == bot_symbol {
+          search_depth::<GravityGame>(Difficulty::Hard) + 3
+        } else {
+          search_depth::<GravityGame>(Difficulty::Easy)
+        };
+
+        let column = best_move(&gravity_game, depth).unwrap();
+        gravity_game.drop_piece(column).unwrap();
+      }
+
+      let lost = match bot_symbol {
+        BoardStates::X => GameState::OWon,
+        _ => GameState::XWon,
+      };
+
+      assert_ne!(gravity_game.result(), Some(lost));
+    }
+  }
+}
+
+#[cfg(test)]
+mod interface {
+  use super::*;
+
+  #[test]
+  fn columns_are_parsed() {
+    assert_eq!(parse_column("4\n").unwrap(), 3);
+    assert!(parse_column("0").is_err());
+    assert!(parse_column("d").is_err());
+  }
+
+  #[test]
+  fn session_plays_until_the_game_ends() {
+    let gameconfig = GameConfig {
+      player_symbol: BoardStates::X,
+      ..GameConfig::default()
+    };
+
+    // More than enough columns, the full ones only being refused.
+    let script = format!("4\n{}", "1\n2\n3\n".repeat(3));
+
+    let mut output = vec![];
+    let end_gamestate = gravity_session(
+      gameconfig,
+      GravityGame::new(3, 3, 3).unwrap(),
+      Cursor::new(script),
+      &mut output,
+    )
+    .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_ne!(end_gamestate, GameState::XWon);
+    assert!(output.contains("there are only 3 columns"));
+    assert!(output.contains(" -- bot dropped in column "));
+    assert!(output.ends_with(&format!("{end_gamestate:?}\n")));
+  }
+}
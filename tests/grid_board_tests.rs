@@ -0,0 +1,225 @@
+use tictactoe_with_ai::gameboard::BoardStates;
+use tictactoe_with_ai::grid_board::*;
+use tictactoe_with_ai::search::MAX_EVALUATION;
+
+fn place_all(grid_board: &mut GridBoard, tiles: &[&str], symbol: BoardStates) {
+  for tile in tiles {
+    let coords = parse_grid_tile(tile, grid_board).unwrap();
+
+    grid_board.place_tile(&coords, symbol);
+  }
+}
+
+#[cfg(test)]
+mod line_logic {
+  use super::*;
+
+  #[test]
+  fn every_line_is_counted() {
+    // 3x3 has the usual 8, a 7x6 Connect Four board 69.
+    assert_eq!(GridBoard::new(3, 3, 3).unwrap().lines().len(), 8);
+    assert_eq!(GridBoard::new(7, 6, 4).unwrap().lines().len(), 69);
+  }
+
+  #[test]
+  fn lines_win_in_every_direction() {
+    let lines = [
+      ["b2", "b3", "b4", "b5"],
+      ["a7", "b7", "c7", "d7"],
+      ["c3", "d4", "e5", "f6"],
+      ["c4", "d3", "e2", "f1"],
+    ];
+
+    for line in lines {
+      let mut grid_board = GridBoard::new(7, 6, 4).unwrap();
+
+      place_all(&mut grid_board, &line[..3], BoardStates::O);
+
+      assert!(!grid_board.check_if_win());
+      assert_eq!(grid_board.winner(), None);
+
+      // Filling the middle of the line last checks both ways from it.
+      place_all(&mut grid_board, &line[3..], BoardStates::O);
+      place_all(&mut grid_board, &line[1..2], BoardStates::O);
+
+      assert!(grid_board.check_if_win(), "{line:?}");
+      assert_eq!(grid_board.winner(), Some(BoardStates::O));
+    }
+  }
+
+  #[test]
+  fn mixed_lines_dont_win() {
+    let mut grid_board = GridBoard::new(5, 5, 4).unwrap();
+
+    place_all(&mut grid_board, &["a1", "a2", "a4"], BoardStates::X);
+    place_all(&mut grid_board, &["a3"], BoardStates::O);
+
+    assert!(!grid_board.check_if_win());
+    assert_eq!(grid_board.winning_line(), None);
+  }
+
+  #[test]
+  fn tiles_covered_follows_placing_and_clearing() {
+    let mut grid_board = GridBoard::new(2, 2, 2).unwrap();
+
+    place_all(&mut grid_board, &["a1", "a2", "b1", "b2"], BoardStates::X);
+    assert!(grid_board.all_tiles_covered());
+
+    grid_board.place_tile(&(0, 0), BoardStates::Empty);
+    grid_board.place_tile(&(0, 1), BoardStates::O);
+
+    assert_eq!(grid_board.tiles_covered, 3);
+    assert_eq!(grid_board.empty_tiles(), vec![(0, 0)]);
+  }
+}
+
+#[cfg(test)]
+mod evaluation {
+  use super::*;
+
+  #[test]
+  fn open_lines_count_for_whoever_has_them() {
+    let mut grid_board = GridBoard::new(3, 3, 3).unwrap();
+
+    place_all(&mut grid_board, &["b2"], BoardStates::X);
+
+    // b2 is on 4 lines with one X in each.
+    assert_eq!(evaluate(&grid_board, BoardStates::X), 16);
+    assert_eq!(evaluate(&grid_board, BoardStates::O), -16);
+
+    place_all(&mut grid_board, &["a1"], BoardStates::O);
+
+    assert_eq!(evaluate(&grid_board, BoardStates::X), 12 - 8);
+  }
+
+  #[test]
+  fn long_lines_stay_within_the_limit() {
+    let mut grid_board = GridBoard::new(MAX_GRID_SIZE, MAX_GRID_SIZE, MAX_GRID_SIZE).unwrap();
+
+    for row in 0..MAX_GRID_SIZE {
+      for column in 0..MAX_GRID_SIZE - 1 {
+        grid_board.place_tile(&(row, column), BoardStates::X);
+      }
+    }
+
+    assert_eq!(evaluate(&grid_board, BoardStates::X), MAX_EVALUATION);
+    assert_eq!(evaluate(&grid_board, BoardStates::O), -MAX_EVALUATION);
+  }
+}
+
+#[cfg(test)]
+mod interface {
+  use super::*;
+
+  #[test]
+  fn bad_sizes_are_errors() {
+    assert!(GridBoard::new(0, 3, 3).is_err());
+    assert!(GridBoard::new(27, 3, 3).is_err());
+    assert_eq!(
+      GridBoard::new(3, 3, 4).unwrap_err().to_string(),
+      "4 in a row can't be made on a 3x3 board"
+    );
+  }
+
+  #[test]
+  fn tiles_are_parsed_within_the_board() {
+    let grid_board = GridBoard::new(12, 4, 4).unwrap();
+
+    assert_eq!(parse_grid_tile("d12", &grid_board).unwrap(), (3, 11));
+    assert_eq!(parse_grid_tile(" B3\n", &grid_board).unwrap(), (1, 2));
+    assert_eq!(
+      parse_grid_tile("e1", &grid_board).unwrap_err().to_string(),
+      "e1 is off the 12x4 board"
+    );
+    assert!(parse_grid_tile("a0", &grid_board).is_err());
+    assert!(parse_grid_tile("11", &grid_board).is_err());
+  }
+
+  #[test]
+  fn grid_is_rendered_with_the_last_tile_marked() {
+    let mut grid_board = GridBoard::new(4, 3, 3).unwrap();
+
+    place_all(&mut grid_board, &["b2", "c3"], BoardStates::X);
+    place_all(&mut grid_board, &["c2"], BoardStates::O);
+
+    assert_eq!(
+      render_grid(&grid_board),
+      "    1  2  3  4\n\
+      \x20a  .  .  .  .\n\
+      \x20b  .  X  .  .\n\
+      \x20c  . [O] X  .\n"
+    );
+  }
+}
+
+#[cfg(test)]
+mod blocked_tiles {
+  use super::*;
+
+  #[test]
+  fn lines_through_blocked_tiles_are_left_out() {
+    let mut grid_board = GridBoard::new(3, 3, 3).unwrap();
+
+    place_all(&mut grid_board, &["b2"], BoardStates::Blocked);
+
+    // Only the outside rows and columns are left.
+    assert_eq!(grid_board.lines().len(), 4);
+    assert_eq!(grid_board.tiles_covered, 1);
+  }
+
+  #[test]
+  fn blocked_tiles_dont_make_lines() {
+    let mut grid_board = GridBoard::new(3, 3, 3).unwrap();
+
+    place_all(&mut grid_board, &["a1", "a2", "a3"], BoardStates::Blocked);
+
+    assert!(!grid_board.check_if_win());
+    assert_eq!(grid_board.winner(), None);
+    assert_eq!(grid_board.line_length(&(0, 0), (0, 1)), 0);
+  }
+}
+
+#[cfg(test)]
+mod toroidal {
+  use super::*;
+
+  #[test]
+  fn lines_wrap_without_being_counted_twice() {
+    // Each row, column and diagonal all the way around once.
+    assert_eq!(GridBoard::toroidal(4, 4, 4).unwrap().lines().len(), 16);
+    // Every tile starts a line in all four directions.
+    assert_eq!(GridBoard::toroidal(5, 5, 4).unwrap().lines().len(), 100);
+    assert_eq!(
+      GridBoard::toroidal(4, 3, 4).unwrap_err().to_string(),
+      "4 in a row would wrap onto itself on a 4x3 torus"
+    );
+  }
+
+  #[test]
+  fn broken_rows_and_diagonals_win() {
+    let wrapped_lines = [["a4", "a1", "a2"], ["a1", "d1", "c1"], ["a3", "b4", "c1"]];
+
+    for tiles in wrapped_lines {
+      let mut flat_board = GridBoard::new(4, 4, 3).unwrap();
+      let mut grid_board = GridBoard::toroidal(4, 4, 3).unwrap();
+
+      place_all(&mut flat_board, &tiles, BoardStates::O);
+      place_all(&mut grid_board, &tiles, BoardStates::O);
+
+      assert!(!flat_board.check_if_win(), "{tiles:?}");
+      assert!(grid_board.check_if_win(), "{tiles:?}");
+      assert_eq!(grid_board.winner(), Some(BoardStates::O));
+    }
+  }
+
+  #[test]
+  fn corners_have_neighbors_all_around() {
+    let flat_board = GridBoard::new(4, 4, 3).unwrap();
+    let grid_board = GridBoard::toroidal(4, 4, 3).unwrap();
+
+    assert_eq!(flat_board.neighbors(&(0, 0)).len(), 3);
+    assert_eq!(grid_board.neighbors(&(0, 0)).len(), 8);
+    assert!(grid_board.neighbors(&(0, 0)).contains(&(3, 3)));
+    assert_eq!(grid_board.step(&(0, 3), (0, 1), 1), Some((0, 0)));
+  }
+}
@@ -0,0 +1,188 @@
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use tictactoe_with_ai::http_api::*;
+
+fn post(api_state: &mut ApiState, path: &str, body: Value) -> ApiResponse {
+  handle_request(api_state, "POST", path, &body.to_string())
+}
+
+#[test]
+fn move_blocks_series_of_two() {
+  let mut api_state = ApiState::new();
+
+  let response = post(
+    &mut api_state,
+    "/move",
+    json!({ "position": "startpos", "moves": ["a1", "b2", "a2"] }),
+  );
+
+  assert_eq!(response.status, 200);
+  assert_eq!(response.body, json!({ "move": "a3" }));
+}
+
+#[test]
+fn analyze_scores_every_empty_tile() {
+  let mut api_state = ApiState::new();
+
+  //X|X|-
+  //O|O|-
+  //-|-|-
+  let response = post(
+    &mut api_state,
+    "/analyze",
+    json!({ "position": "xx.oo...." }),
+  );
+
+  assert_eq!(response.status, 200);
+  assert_eq!(response.body["next_symbol"], "X");
+  assert_eq!(response.body["scores"].as_object().unwrap().len(), 5);
+  assert_eq!(response.body["scores"]["a3"], 1);
+  assert_eq!(response.body["scores"]["c3"], -1);
+}
+
+#[test]
+fn bad_requests_are_rejected() {
+  let mut api_state = ApiState::new();
+
+  let bad_json = handle_request(&mut api_state, "POST", "/move", "{");
+  let bad_move = post(&mut api_state, "/move", json!({ "moves": ["z9"] }));
+  let unknown_path = post(&mut api_state, "/dance", json!({}));
+  let wrong_method = handle_request(&mut api_state, "GET", "/move", "");
+
+  assert_eq!(bad_json.status, 400);
+  assert_eq!(bad_move.status, 400);
+  assert_eq!(unknown_path.status, 404);
+  assert_eq!(wrong_method.status, 405);
+}
+
+#[test]
+fn stateful_game_is_played_to_the_end() {
+  let mut api_state = ApiState::new();
+
+  let game = post(&mut api_state, "/games", json!({ "player_symbol": "O" })).body;
+  let id = game["id"].as_u64().unwrap();
+  let mut position = game["position"].as_str().unwrap().to_string();
+
+  assert_eq!(game["bot_symbol"], "X");
+  assert_ne!(game["bot_move"], Value::Null);
+
+  while let Some(index) = position.find('.') {
+    let response = post(
+      &mut api_state,
+      &format!("/games/{id}/move"),
+      json!({ "move": (index + 1).to_string() }),
+    );
+
+    assert_eq!(response.status, 200);
+
+    position = response.body["position"].as_str().unwrap().to_string();
+
+    if response.body["result"] != Value::Null {
+      break;
+    }
+  }
+
+  let game = handle_request(&mut api_state, "GET", &format!("/games/{id}"), "").body;
+
+  assert_eq!(game["position"], position.as_str());
+  assert_ne!(game["result"], Value::Null);
+  assert_ne!(game["result"], "OWon");
+
+  let late_move = post(
+    &mut api_state,
+    &format!("/games/{id}/move"),
+    json!({ "move": "a1" }),
+  );
+
+  assert_eq!(late_move.status, 409);
+}
+
+#[test]
+fn histories_the_bot_never_chose_are_answered() {
+  let mut api_state = ApiState::new();
+
+  let response = post(
+    &mut api_state,
+    "/move",
+    json!({ "moves": ["b2", "a1", "a3", "c2"] }),
+  );
+
+  assert_eq!(response.status, 200);
+  assert_ne!(response.body["move"], Value::Null);
+}
+
+#[test]
+fn oldest_games_are_dropped() {
+  let mut api_state = ApiState::new();
+
+  for _ in 0..=MAX_GAMES {
+    post(&mut api_state, "/games", json!({ "player_symbol": "X" }));
+  }
+
+  let first_game = handle_request(&mut api_state, "GET", "/games/1", "");
+  let last_game = handle_request(
+    &mut api_state,
+    "GET",
+    &format!("/games/{}", MAX_GAMES + 1),
+    "",
+  );
+
+  assert_eq!(api_state.games.len(), MAX_GAMES);
+  assert_eq!(first_game.status, 404);
+  assert_eq!(last_game.status, 200);
+}
+
+#[test]
+fn unknown_game_is_not_found() {
+  let mut api_state = ApiState::new();
+
+  let response = handle_request(&mut api_state, "GET", "/games/42", "");
+
+  assert_eq!(response.status, 404);
+}
+
+/// Sends request to a server handling a single connection, returning the raw response.
+fn send_over_http(request: &str) -> String {
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let address = listener.local_addr().unwrap();
+
+  let server = thread::spawn(move || {
+    let mut api_state = ApiState::new();
+    let (stream, _) = listener.accept().unwrap();
+
+    handle_connection(&mut api_state, stream).unwrap();
+  });
+
+  let mut stream = TcpStream::connect(address).unwrap();
+  stream.write_all(request.as_bytes()).unwrap();
+
+  let mut response = String::new();
+  stream.read_to_string(&mut response).unwrap();
+  server.join().unwrap();
+
+  response
+}
+
+#[test]
+fn requests_are_served_over_http() {
+  let body = r#"{"position": "xx.oo...."}"#;
+
+  let response = send_over_http(&format!(
+    "POST /move HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}",
+    body.len()
+  ));
+
+  assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+  assert!(response.ends_with(r#"{"move":"a3"}"#));
+}
+
+#[test]
+fn malformed_content_length_is_a_bad_request() {
+  let response =
+    send_over_http("POST /move HTTP/1.1\r\nHost: localhost\r\nContent-Length: lots\r\n\r\n");
+
+  assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+  assert!(response.contains("Content-Length has to be a whole number"));
+}
@@ -0,0 +1,161 @@
+use rand::prelude::*;
+use std::time::Duration;
+use tictactoe_with_ai::gameboard::*;
+use tictactoe_with_ai::mcts::*;
+use tictactoe_with_ai::solver;
+
+fn rng() -> StdRng {
+  StdRng::seed_from_u64(3)
+}
+
+#[cfg(test)]
+mod board_config {
+  use super::*;
+
+  #[test]
+  fn rules_come_from_the_board() {
+    //X|O|-
+    //-|-|-
+    //-|-|-
+    let gameboard = BoardConfig::from_notation("xo.......").unwrap();
+
+    assert_eq!(gameboard.current_player(), 0);
+    assert_eq!(gameboard.legal_moves().len(), 7);
+    assert_eq!(gameboard.outcome(), None);
+  }
+
+  #[test]
+  fn applying_moves_takes_turns() {
+    let mut gameboard = BoardConfig::new();
+
+    gameboard.apply(&(1, 1));
+    gameboard.apply(&(0, 0));
+
+    assert_eq!(gameboard.to_notation(), "o...x....");
+    assert_eq!(gameboard.current_player(), 0);
+  }
+
+  #[test]
+  fn outcomes() {
+    //X|X|X
+    //O|O|-
+    //-|-|-
+    let gameboard = BoardConfig::from_notation("xxxoo....").unwrap();
+
+    assert_eq!(gameboard.outcome(), Some(Outcome::Won(0)));
+    assert!(gameboard.legal_moves().is_empty());
+
+    //X|O|X
+    //X|O|O
+    //O|X|X
+    let gameboard = BoardConfig::from_notation("xoxxoooxx").unwrap();
+
+    assert_eq!(gameboard.outcome(), Some(Outcome::Draw));
+  }
+}
+
+#[cfg(test)]
+mod bot {
+  use super::*;
+
+  #[test]
+  fn takes_the_win() {
+    //X|X|-
+    //O|O|-
+    //-|-|-
+    let gameboard = BoardConfig::from_notation("xx.oo....").unwrap();
+
+    assert_eq!(
+      best_move(&gameboard, MctsBudget::Iterations(2000), &mut rng()),
+      Some((0, 2))
+    );
+  }
+
+  #[test]
+  fn blocks_the_loss() {
+    //X|X|-
+    //-|O|-
+    //-|-|-
+    let gameboard = BoardConfig::from_notation("xx..o....").unwrap();
+
+    assert_eq!(
+      best_move(&gameboard, MctsBudget::Iterations(2000), &mut rng()),
+      Some((0, 2))
+    );
+  }
+
+  #[test]
+  fn nothing_to_search_once_the_game_is_over() {
+    let gameboard = BoardConfig::from_notation("xxxoo....").unwrap();
+
+    assert_eq!(
+      best_move(&gameboard, MctsBudget::Iterations(100), &mut rng()),
+      None
+    );
+  }
+
+  #[test]
+  fn time_budgets_end_the_search() {
+    let stats = search(
+      &BoardConfig::new(),
+      MctsBudget::Time(Duration::from_millis(50)),
+      DEFAULT_EXPLORATION,
+      &mut rng(),
+    );
+
+    assert_eq!(stats.len(), 9);
+    assert!(stats.iter().all(|move_stats| move_stats.visits > 0));
+  }
+
+  #[test]
+  fn iteration_budgets_are_counted() {
+    let stats = search(
+      &BoardConfig::new(),
+      MctsBudget::Iterations(500),
+      DEFAULT_EXPLORATION,
+      &mut rng(),
+    );
+
+    assert_eq!(
+      stats
+        .iter()
+        .map(|move_stats| move_stats.visits)
+        .sum::<u32>(),
+      500
+    );
+  }
+}
+
+#[cfg(test)]
+mod convergence {
+  use super::*;
+
+  #[test]
+  fn agrees_with_the_solver() {
+    let positions = [
+      // O has to take the middle.
+      "x........",
+      // O has to take an edge.
+      "x...o...x",
+      "xx.oo....",
+      "xo..x....",
+      "x.o.x.o..",
+      ".........",
+    ];
+
+    for notation in positions {
+      let gameboard = BoardConfig::from_notation(notation).unwrap();
+
+      let scores = solver::score_moves(&gameboard);
+      let best_score = scores.iter().map(|(_, score)| *score).max().unwrap();
+
+      let chosen = best_move(&gameboard, MctsBudget::Iterations(20_000), &mut rng()).unwrap();
+      let (_, chosen_score) = scores.iter().find(|(coords, _)| *coords == chosen).unwrap();
+
+      assert_eq!(
+        *chosen_score, best_score,
+        "{notation}: chose {chosen:?}, the solver scores {scores:?}"
+      );
+    }
+  }
+}
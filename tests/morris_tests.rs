@@ -0,0 +1,228 @@
+use std::io::Cursor;
+use tictactoe_with_ai::bot::Difficulty;
+use tictactoe_with_ai::gameboard::*;
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::morris::*;
+use tictactoe_with_ai::search::*;
+use tictactoe_with_ai::solver::opposing_symbol;
+
+fn play(morris_game: &mut MorrisGame, moves: &[&str]) {
+  for morris_move in moves {
+    let morris_move = parse_morris_move(morris_move).unwrap();
+
+    morris_game.play(&morris_move).unwrap();
+  }
+}
+
+/// X on a1 b1 c2, O on a2 a3 c3, nobody in a row yet.
+fn all_pieces_placed(orthogonal_only: bool) -> MorrisGame {
+  let mut morris_game = MorrisGame::new(orthogonal_only);
+
+  play(&mut morris_game, &["a1", "a2", "b1", "a3", "c2", "c3"]);
+
+  morris_game
+}
+
+#[cfg(test)]
+mod rules {
+  use super::*;
+
+  #[test]
+  fn pieces_slide_once_all_are_placed() {
+    let mut morris_game = all_pieces_placed(false);
+
+    assert!(!morris_game.is_placing());
+    assert_eq!(
+      morris_game
+        .play(&MorrisMove::Place((1, 1)))
+        .unwrap_err()
+        .to_string(),
+      "all your pieces are placed, slide one with e.g. 'a1 b2'"
+    );
+
+    play(&mut morris_game, &["b1 b2"]);
+
+    assert_eq!(
+      morris_game.gameboard.get_board_state(&(1, 0)),
+      &BoardStates::Empty
+    );
+    assert_eq!(
+      morris_game.gameboard.get_board_state(&(1, 1)),
+      &BoardStates::X
+    );
+    assert_eq!(morris_game.gameboard.tiles_covered, 6);
+    assert_eq!(morris_game.next_symbol, BoardStates::O);
+  }
+
+  #[test]
+  fn bad_slides_are_errors() {
+    let mut morris_game = all_pieces_placed(false);
+
+    let error = |morris_game: &mut MorrisGame, morris_move: &str| {
+      morris_game
+        .play(&parse_morris_move(morris_move).unwrap())
+        .unwrap_err()
+        .to_string()
+    };
+
+    assert_eq!(
+      error(&mut morris_game, "a2 b2"),
+      "a2 isn't one of your pieces"
+    );
+    assert_eq!(error(&mut morris_game, "a1 a2"), "a2 is already taken");
+    assert_eq!(error(&mut morris_game, "c2 a2"), "a2 is already taken");
+    assert_eq!(error(&mut morris_game, "a1 c1"), "c1 isn't next to a1");
+  }
+
+  #[test]
+  fn orthogonal_only_forbids_diagonals() {
+    let mut diagonal_game = all_pieces_placed(false);
+    let mut orthogonal_game = all_pieces_placed(true);
+
+    play(&mut diagonal_game, &["c2 b3"]);
+
+    assert_eq!(
+      orthogonal_game
+        .play(&MorrisMove::Slide((2, 1), (1, 2)))
+        .unwrap_err()
+        .to_string(),
+      "b3 isn't next to c2"
+    );
+    assert!(orthogonal_game
+      .legal_moves()
+      .iter()
+      .all(|morris_move| !matches!(morris_move, MorrisMove::Slide((2, 1), (1, 2)))));
+  }
+
+  #[test]
+  fn sliding_into_a_row_wins() {
+    let mut morris_game = all_pieces_placed(false);
+
+    play(&mut morris_game, &["c2 b2", "a3 b3"]);
+
+    assert_eq!(morris_game.result(), None);
+
+    play(&mut morris_game, &["b2 c1"]);
+
+    assert_eq!(morris_game.result(), Some(GameState::XWon));
+    assert!(morris_game.legal_moves().is_empty());
+  }
+
+  #[test]
+  fn threefold_repetition_draws() {
+    let mut morris_game = all_pieces_placed(false);
+
+    play(
+      &mut morris_game,
+      &[
+        "c2 b2", "c3 b3", "b2 c2", "b3 c3", "c2 b2", "c3 b3", "b2 c2",
+      ],
+    );
+
+    assert_eq!(morris_game.result(), None);
+
+    play(&mut morris_game, &["b3 c3"]);
+
+    assert!(morris_game.is_repeated());
+    assert_eq!(morris_game.result(), Some(GameState::Draw));
+    assert_eq!(
+      morris_game
+        .play(&MorrisMove::Slide((2, 1), (1, 1)))
+        .unwrap_err()
+        .to_string(),
+      "the game is over"
+    );
+  }
+}
+
+#[cfg(test)]
+mod bot {
+  use super::*;
+
+  #[test]
+  fn bot_takes_a_winning_slide() {
+    let mut morris_game = all_pieces_placed(false);
+
+    play(&mut morris_game, &["c2 b2", "a3 b3"]);
+
+    assert_eq!(
+      best_move(&morris_game, search_depth::<MorrisGame>(Difficulty::Easy)),
+      Some(MorrisMove::Slide((1, 1), (2, 0)))
+    );
+  }
+
+  #[test]
+  fn bot_blocks_a_row_while_placing() {
+    let mut morris_game = MorrisGame::new(false);
+
+    play(&mut morris_game, &["a1", "b2", "a2"]);
+
+    assert_eq!(
+      best_move(&morris_game, search_depth::<MorrisGame>(Difficulty::Medium)),
+      Some(MorrisMove::Place((0, 2)))
+    );
+  }
+
+  #[test]
+  fn hard_bot_never_loses_to_easy() {
+    for (hard_symbol, orthogonal_only) in [
+      (BoardStates::X, false),
+      (BoardStates::O, false),
+      (BoardStates::X, true),
+      (BoardStates::O, true),
+    ] {
+      let mut morris_game = MorrisGame::new(orthogonal_only);
+
+      while morris_game.result().is_none() {
+        let difficulty = if morris_game.next_symbol == hard_symbol {
+          Difficulty::Hard
+        } else {
+          Difficulty::Easy
+        };
+
+        let morris_move = best_move(&morris_game, search_depth::<MorrisGame>(difficulty)).unwrap();
+        morris_game.play(&morris_move).unwrap();
+      }
+
+      assert_ne!(
+        morris_game.winner(),
+        Some(opposing_symbol(hard_symbol)),
+        "{hard_symbol:?} lost, orthogonal only {orthogonal_only}"
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod interface {
+  use super::*;
+
+  #[test]
+  fn moves_are_parsed() {
+    assert_eq!(parse_morris_move("b2").unwrap(), MorrisMove::Place((1, 1)));
+    assert_eq!(
+      parse_morris_move("a1 5").unwrap(),
+      MorrisMove::Slide((0, 0), (1, 1))
+    );
+    assert!(parse_morris_move("a1 b2 c3").is_err());
+    assert!(parse_morris_move("").is_err());
+  }
+
+  #[test]
+  fn session_reports_bad_moves_and_the_bot_replies() {
+    let gameconfig = GameConfig {
+      player_symbol: BoardStates::X,
+      ..GameConfig::default()
+    };
+
+    let mut output = vec![];
+    let result = morris_session(gameconfig, false, Cursor::new("b2\nb2\n"), &mut output);
+
+    assert_eq!(result.unwrap_err().to_string(), INPUT_ENDED_ERROR);
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains(" -- bot played "));
+    assert!(output.contains("b2 is already taken"));
+  }
+}
@@ -0,0 +1,212 @@
+use std::io::Cursor;
+use tictactoe_with_ai::bot::Difficulty;
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::grid_board::parse_grid_tile;
+use tictactoe_with_ai::multiplayer::*;
+use tictactoe_with_ai::search::*;
+
+fn three_players() -> MultiplayerGame {
+  MultiplayerGame::new(3, 4, 4, 3).unwrap()
+}
+
+fn place_all(multiplayer_game: &mut MultiplayerGame, tiles: &[&str]) {
+  for tile in tiles {
+    let coords = parse_grid_tile(tile, &multiplayer_game.grid_board).unwrap();
+
+    multiplayer_game.place(&coords).unwrap();
+  }
+}
+
+fn every_tile() -> String {
+  ["a", "b", "c", "d"]
+    .into_iter()
+    .flat_map(|row| (1..=4).map(move |column| format!("{row}{column}\n")))
+    .collect()
+}
+
+#[cfg(test)]
+mod rules {
+  use super::*;
+
+  #[test]
+  fn turns_rotate_through_every_player() {
+    let mut multiplayer_game = three_players();
+
+    place_all(&mut multiplayer_game, &["a1", "a2", "a3"]);
+
+    assert_eq!(multiplayer_game.player_at(&(0, 0)), Some(0));
+    assert_eq!(multiplayer_game.player_at(&(0, 1)), Some(1));
+    assert_eq!(multiplayer_game.player_at(&(0, 2)), Some(2));
+    assert_eq!(multiplayer_game.next_player, 0);
+  }
+
+  #[test]
+  fn the_third_player_can_win() {
+    let mut multiplayer_game = three_players();
+
+    place_all(
+      &mut multiplayer_game,
+      &["a1", "d4", "b3", "c4", "d1", "c3", "a4", "b1"],
+    );
+
+    assert_eq!(multiplayer_game.result(), None);
+
+    place_all(&mut multiplayer_game, &["a3"]);
+
+    assert_eq!(
+      multiplayer_game.result(),
+      Some(MultiplayerResult::PlayerWon(2))
+    );
+    assert_eq!(
+      multiplayer_game.place(&(3, 3)).unwrap_err().to_string(),
+      "the game is over"
+    );
+  }
+
+  #[test]
+  fn bad_games_are_errors() {
+    assert_eq!(
+      MultiplayerGame::new(7, 4, 4, 3).unwrap_err().to_string(),
+      "there can be 2 to 6 players, got 7"
+    );
+    assert!(MultiplayerGame::new(3, 4, 4, 5).is_err());
+
+    let mut multiplayer_game = three_players();
+
+    place_all(&mut multiplayer_game, &["b2"]);
+
+    assert_eq!(
+      multiplayer_game.place(&(1, 1)).unwrap_err().to_string(),
+      "b2 is already taken"
+    );
+  }
+
+  #[test]
+  fn shared_lines_are_worth_nothing() {
+    let mut multiplayer_game = MultiplayerGame::new(3, 3, 3, 3).unwrap();
+
+    place_all(&mut multiplayer_game, &["a1", "a2"]);
+
+    // X's column and diagonal minus O's column, the top row being shared.
+    assert_eq!(multiplayer_game.evaluate(0), 4 + 4 - 4);
+    assert_eq!(multiplayer_game.evaluate(2), -(4 + 4 + 4));
+  }
+
+  #[test]
+  fn long_lines_dont_overflow() {
+    let mut multiplayer_game = MultiplayerGame::new(2, 20, 20, 20).unwrap();
+
+    for column in 0..19 {
+      multiplayer_game.place(&(0, column)).unwrap();
+      multiplayer_game.place(&(column + 1, column)).unwrap();
+    }
+
+    assert_eq!(multiplayer_game.evaluate(0), MAX_EVALUATION);
+  }
+}
+
+#[cfg(test)]
+mod bot {
+  use super::*;
+
+  #[test]
+  fn bot_takes_a_win() {
+    let mut multiplayer_game = three_players();
+
+    place_all(&mut multiplayer_game, &["b1", "a1", "a4", "b2", "a2", "d4"]);
+
+    assert_eq!(best_move(&multiplayer_game, 1), Some((1, 2)));
+  }
+
+  #[test]
+  fn bot_blocks_whoever_is_about_to_win() {
+    let mut multiplayer_game = three_players();
+
+    // △ has c1 and c2, O places next and has to stop c3 before △ gets there.
+    place_all(
+      &mut multiplayer_game,
+      &["a4", "d4", "c1", "a1", "b3", "c2", "d1"],
+    );
+
+    assert_eq!(multiplayer_game.next_player, 1);
+    assert_eq!(
+      best_move(
+        &multiplayer_game,
+        search_depth::<MultiplayerGame>(Difficulty::Medium)
+      ),
+      Some((2, 2))
+    );
+  }
+}
+
+#[cfg(test)]
+mod interface {
+  use super::*;
+
+  #[test]
+  fn every_symbol_is_rendered() {
+    let mut multiplayer_game = three_players();
+
+    place_all(&mut multiplayer_game, &["a2", "b2", "b3"]);
+
+    assert_eq!(
+      render_multiplayer(&multiplayer_game),
+      "    1  2  3  4\n\
+      \x20a  .  X  .  .\n\
+      \x20b  .  O [△] .\n\
+      \x20c  .  .  .  .\n\
+      \x20d  .  .  .  .\n"
+    );
+  }
+
+  #[test]
+  fn session_plays_against_two_bots() {
+    let mut gameconfig = GameConfig::default();
+    gameconfig.bot.difficulty = Difficulty::Easy;
+
+    let mut output = vec![];
+    let multiplayer_result = multiplayer_session(
+      gameconfig,
+      three_players(),
+      1,
+      Cursor::new(every_tile()),
+      &mut output,
+    )
+    .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.starts_with("You X | Bot O | Bot △ | 3 in a row wins\n"));
+    assert!(output.contains(" -- bot △ placed on "));
+
+    match multiplayer_result {
+      MultiplayerResult::PlayerWon(0) => assert!(output.ends_with("You X won\n")),
+      MultiplayerResult::PlayerWon(winner) => {
+        assert!(output.ends_with(&format!("Bot {} won\n", player_symbol(winner))))
+      }
+      MultiplayerResult::Draw => assert!(output.ends_with("Draw\n")),
+    }
+  }
+
+  #[test]
+  fn people_can_share_the_keyboard() {
+    let script = "a1\na1\nb1\nc4\na2\nb2\nd4\na3\n";
+
+    let mut output = vec![];
+    let multiplayer_result = multiplayer_session(
+      GameConfig::default(),
+      three_players(),
+      3,
+      Cursor::new(script),
+      &mut output,
+    )
+    .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(multiplayer_result, MultiplayerResult::PlayerWon(0));
+    assert!(output.contains("Player 2 O, select a tile"));
+    assert!(output.contains("a1 is already taken"));
+    assert!(output.ends_with("Player 1 X won\n"));
+  }
+}
@@ -0,0 +1,108 @@
+use std::io::Cursor;
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use tictactoe_with_ai::gameboard::*;
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::network_play::*;
+
+/// A gameconfig where the host places X.
+fn host_gameconfig() -> GameConfig {
+  let mut gameconfig = GameConfig::new();
+
+  gameconfig.player_symbol = BoardStates::X;
+  gameconfig.bot.bot_symbol = BoardStates::O;
+
+  gameconfig
+}
+
+/// Runs a host and a joined player on localhost, each reading their scripted input.
+fn play_on_localhost(
+  host_input: &'static str,
+  join_input: &'static str,
+) -> (GameState, GameState, String) {
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let address = listener.local_addr().unwrap();
+
+  let joined_player = thread::spawn(move || {
+    let mut output = Vec::new();
+    let stream = TcpStream::connect(address).unwrap();
+    let end_gamestate = join_session(
+      stream,
+      GameConfig::new(),
+      Cursor::new(join_input),
+      &mut output,
+    )
+    .unwrap();
+
+    (end_gamestate, String::from_utf8(output).unwrap())
+  });
+
+  let (stream, _) = listener.accept().unwrap();
+  let host_end_gamestate = host_session(
+    stream,
+    host_gameconfig(),
+    Cursor::new(host_input),
+    Vec::new(),
+  )
+  .unwrap();
+
+  let (join_end_gamestate, join_output) = joined_player.join().unwrap();
+
+  (host_end_gamestate, join_end_gamestate, join_output)
+}
+
+#[test]
+fn host_wins_over_network() {
+  //X|X|X
+  //O|O|-
+  //-|-|-
+  let (host_end_gamestate, join_end_gamestate, join_output) =
+    play_on_localhost("a1\na2\na3\n", "b1\nb2\n");
+
+  assert_eq!(host_end_gamestate, GameState::XWon);
+  assert_eq!(join_end_gamestate, GameState::XWon);
+  assert!(join_output.contains("You are O"));
+  assert!(join_output.ends_with(
+    " a  *X*|*X*|*X*
+    ---+---+---
+ b   O | O |
+    ---+---+---
+ c     |   |
+XWon
+"
+  ));
+}
+
+#[test]
+fn illegal_moves_are_rejected_by_host() {
+  //X|O|X
+  //X|O|O
+  //O|X|X
+  let (host_end_gamestate, join_end_gamestate, join_output) =
+    play_on_localhost("a1\na3\nb1\nc2\nc3\n", "a1\nzz\na2\nb2\nb3\nc1\n");
+
+  assert_eq!(host_end_gamestate, GameState::Draw);
+  assert_eq!(join_end_gamestate, GameState::Draw);
+  assert!(join_output.contains("a1 is already taken"));
+  assert!(join_output.contains("incorrect input"));
+}
+
+#[test]
+fn host_reports_disconnect() {
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let address = listener.local_addr().unwrap();
+
+  let joined_player = thread::spawn(move || {
+    let stream = TcpStream::connect(address).unwrap();
+
+    // the joined player can't select anything, so leaves on their first turn
+    join_session(stream, GameConfig::new(), Cursor::new(""), Vec::new())
+      .is_err_and(|error| is_input_ended(error.as_ref()))
+  });
+
+  let (stream, _) = listener.accept().unwrap();
+  let host_result = host_session(stream, host_gameconfig(), Cursor::new("a1\n"), Vec::new());
+
+  assert!(joined_player.join().unwrap());
+  assert_eq!(host_result.unwrap_err().to_string(), DISCONNECTED_ERROR);
+}
@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+use tictactoe_with_ai::bot::Difficulty;
+use tictactoe_with_ai::gameboard::{BoardConfig, BoardStates};
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::notakto::*;
+
+fn play(notakto_game: &mut NotaktoGame, moves: &[&str]) {
+  for notakto_move in moves {
+    let notakto_move = parse_notakto_move(notakto_move, notakto_game).unwrap();
+
+    notakto_game.place(&notakto_move).unwrap();
+  }
+}
+
+fn value(value: &str) -> QuotientValue {
+  QuotientValue::parse(value).unwrap()
+}
+
+/// Searches every move, true if whoever places next loses.
+fn brute_force_is_lost(notakto_game: &NotaktoGame, known: &mut HashMap<Vec<String>, bool>) -> bool {
+  let mut key: Vec<String> = notakto_game
+    .boards
+    .iter()
+    .map(|gameboard| gameboard.to_notation())
+    .collect();
+  key.sort();
+
+  if let Some(is_lost) = known.get(&key) {
+    return *is_lost;
+  }
+
+  let is_lost = notakto_game.legal_moves().iter().all(|notakto_move| {
+    let mut next_game = notakto_game.clone();
+    next_game.place(notakto_move).unwrap();
+
+    next_game.is_over() || !brute_force_is_lost(&next_game, known)
+  });
+
+  known.insert(key, is_lost);
+
+  is_lost
+}
+
+#[cfg(test)]
+mod quotient {
+  use super::*;
+
+  #[test]
+  fn relations_hold() {
+    let relations = [
+      ("a2", "1"),
+      ("b3", "b"),
+      ("b2c", "c"),
+      ("c3", "ac2"),
+      ("b2d", "d"),
+      ("cd", "ad"),
+      ("d2", "c2"),
+    ];
+
+    for (left, right) in relations {
+      assert_eq!(value(left), value(right), "{left} = {right}");
+    }
+  }
+
+  #[test]
+  fn quotient_has_eighteen_elements() {
+    let generators = ["a", "b", "c", "d"].map(value);
+    let mut elements = vec![QuotientValue::IDENTITY];
+    let mut index = 0;
+
+    while index < elements.len() {
+      for generator in generators {
+        let product = elements[index].multiply(generator);
+
+        if !elements.contains(&product) {
+          elements.push(product);
+        }
+      }
+
+      index += 1;
+    }
+
+    assert_eq!(elements.len(), 18);
+    assert_eq!(
+      elements
+        .iter()
+        .filter(|element| element.is_losing_to_move())
+        .count(),
+      4
+    );
+  }
+
+  #[test]
+  fn values_are_displayed_like_they_are_parsed() {
+    for written in ["1", "a", "ab2", "c2", "ad", "bc"] {
+      assert_eq!(value(written).to_string(), written);
+    }
+  }
+
+  #[test]
+  fn known_board_values() {
+    let mut notakto_game = NotaktoGame::new(1);
+
+    assert_eq!(notakto_game.value(), value("c"));
+
+    play(&mut notakto_game, &["b2"]);
+
+    assert_eq!(notakto_game.value(), value("c2"));
+    assert!(notakto_game.value().is_losing_to_move());
+  }
+
+  #[test]
+  fn quotient_agrees_with_searching_every_move() {
+    let mut known = HashMap::new();
+    let mut positions = vec![NotaktoGame::new(2)];
+    let mut checked = 0;
+
+    // Every position up to three X in, then the search handles the rest.
+    for _ in 0..3 {
+      let mut next_positions = vec![];
+
+      for notakto_game in &positions {
+        assert_eq!(
+          notakto_game.value().is_losing_to_move(),
+          brute_force_is_lost(notakto_game, &mut known),
+          "{:?}",
+          notakto_game
+            .boards
+            .iter()
+            .map(|gameboard| gameboard.to_notation())
+            .collect::<Vec<String>>()
+        );
+        checked += 1;
+
+        for notakto_move in notakto_game.legal_moves() {
+          let mut next_game = notakto_game.clone();
+          next_game.place(&notakto_move).unwrap();
+
+          if !next_game.is_over() {
+            next_positions.push(next_game);
+          }
+        }
+      }
+
+      positions = next_positions;
+    }
+
+    assert!(checked > 100);
+  }
+
+  #[test]
+  fn quotient_agrees_with_searching_every_one_and_two_board_position() {
+    // Built straight from the tiles, since notation only takes boards reachable by taking turns.
+    let live_boards: Vec<BoardConfig> = (0..1 << 9)
+      .map(|tiles: u32| {
+        let mut gameboard = BoardConfig::new();
+
+        for tile in (0..9).filter(|tile| tiles >> tile & 1 == 1) {
+          gameboard.place_tile(&(tile / 3, tile % 3), BoardStates::X);
+          gameboard.tiles_covered += 1;
+        }
+
+        gameboard
+      })
+      .filter(|gameboard| gameboard.winning_row().is_none())
+      .collect();
+
+    let mut known = HashMap::new();
+    let mut positions: Vec<Vec<BoardConfig>> = live_boards
+      .iter()
+      .map(|gameboard| vec![gameboard.clone()])
+      .collect();
+
+    for (index, first) in live_boards.iter().enumerate() {
+      for second in &live_boards[index..] {
+        positions.push(vec![first.clone(), second.clone()]);
+      }
+    }
+
+    for boards in positions {
+      let notakto_game = NotaktoGame {
+        boards,
+        last_move: None,
+      };
+
+      assert_eq!(
+        notakto_game.value().is_losing_to_move(),
+        brute_force_is_lost(&notakto_game, &mut known),
+        "{:?}",
+        notakto_game
+          .boards
+          .iter()
+          .map(|gameboard| gameboard.to_notation())
+          .collect::<Vec<String>>()
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod bot {
+  use super::*;
+
+  #[test]
+  fn one_board_is_won_by_the_center() {
+    let notakto_game = NotaktoGame::new(1);
+
+    assert_eq!(winning_move(&notakto_game), Some((0, (1, 1))));
+  }
+
+  #[test]
+  fn hard_bot_never_loses_when_it_places_first() {
+    for board_count in 1..=3 {
+      let mut notakto_game = NotaktoGame::new(board_count);
+      let mut bot_to_move = true;
+
+      if notakto_game.value().is_losing_to_move() {
+        continue;
+      }
+
+      while !notakto_game.is_over() {
+        let difficulty = if bot_to_move {
+          Difficulty::Hard
+        } else {
+          Difficulty::Easy
+        };
+
+        let notakto_move = bot_move(&notakto_game, difficulty).unwrap();
+        notakto_game.place(&notakto_move).unwrap();
+
+        bot_to_move = !bot_to_move;
+      }
+
+      assert!(
+        bot_to_move,
+        "the bot killed the last of {board_count} boards"
+      );
+    }
+  }
+
+  #[test]
+  fn easy_bot_avoids_killing_the_last_board() {
+    let mut notakto_game = NotaktoGame::new(1);
+
+    play(&mut notakto_game, &["a1", "a2"]);
+
+    for _ in 0..20 {
+      assert_ne!(bot_move(&notakto_game, Difficulty::Easy), Some((0, (0, 2))));
+    }
+  }
+}
+
+#[cfg(test)]
+mod interface {
+  use super::*;
+
+  #[test]
+  fn moves_are_parsed() {
+    let mut notakto_game = NotaktoGame::new(3);
+
+    assert_eq!(
+      parse_notakto_move("2 b2", &notakto_game).unwrap(),
+      (1, (1, 1))
+    );
+    assert_eq!(
+      parse_notakto_move("3 1", &notakto_game).unwrap(),
+      (2, (0, 0))
+    );
+    assert!(parse_notakto_move("0 b2", &notakto_game).is_err());
+    assert!(parse_notakto_move("b2", &notakto_game).is_err());
+
+    play(
+      &mut notakto_game,
+      &["1 a1", "1 a2", "1 a3", "2 a1", "2 a2", "2 a3"],
+    );
+
+    assert_eq!(
+      parse_notakto_move("c3", &notakto_game).unwrap(),
+      (2, (2, 2))
+    );
+    assert_eq!(
+      notakto_game.place(&(0, (2, 2))).unwrap_err().to_string(),
+      "board 1 is dead"
+    );
+    assert_eq!(
+      notakto_game.place(&(3, (2, 2))).unwrap_err().to_string(),
+      "there are only 3 boards"
+    );
+  }
+
+  #[test]
+  fn boards_are_rendered_side_by_side() {
+    let mut notakto_game = NotaktoGame::new(2);
+
+    play(&mut notakto_game, &["1 a1", "1 a2", "1 a3", "2 b2"]);
+
+    assert_eq!(
+      render_notakto(&notakto_game),
+      " board 1 dead   board 2\n\
+      \x20   1  2  3        1  2  3\n\
+      \x20a  X  X  X     a  .  .  .\n\
+      \x20b  .  .  .     b  . [X] .\n\
+      \x20c  .  .  .     c  .  .  .\n"
+    );
+  }
+
+  #[test]
+  fn bot_replies_to_the_player() {
+    let gameconfig = GameConfig {
+      player_turn: true,
+      ..GameConfig::default()
+    };
+
+    let mut output = vec![];
+    let result = notakto_session(gameconfig, 1, Cursor::new("b2\n"), &mut output);
+
+    // The script runs out once the bot has replied.
+    assert_eq!(result.unwrap_err().to_string(), INPUT_ENDED_ERROR);
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains(" -- bot placed 1 "));
+  }
+
+  #[test]
+  fn bot_wins_when_it_starts() {
+    let gameconfig = GameConfig {
+      player_turn: false,
+      ..GameConfig::default()
+    };
+
+    let moves = ["a1", "a2", "a3", "b1", "b3", "c1", "c2", "c3"];
+    let script: String = moves.iter().map(|tile| format!("{tile}\n")).collect();
+
+    let mut output = vec![];
+    let player_won = notakto_session(gameconfig, 1, Cursor::new(script), &mut output).unwrap();
+
+    assert!(!player_won);
+    assert!(String::from_utf8(output)
+      .unwrap()
+      .ends_with("The bot won\n"));
+  }
+}
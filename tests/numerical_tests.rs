@@ -0,0 +1,193 @@
+use std::io::Cursor;
+use tictactoe_with_ai::bot::Difficulty;
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::numerical::*;
+use tictactoe_with_ai::search::search_depth;
+
+fn play(numerical_game: &mut NumericalGame, moves: &[&str]) {
+  for numerical_move in moves {
+    let numerical_move = parse_numerical_move(numerical_move).unwrap();
+
+    numerical_game.play(&numerical_move).unwrap();
+  }
+}
+
+fn error(numerical_game: &mut NumericalGame, numerical_move: &str) -> String {
+  numerical_game
+    .play(&parse_numerical_move(numerical_move).unwrap())
+    .unwrap_err()
+    .to_string()
+}
+
+#[cfg(test)]
+mod rules {
+  use super::*;
+
+  #[test]
+  fn a_full_row_adding_to_fifteen_wins() {
+    let mut numerical_game = NumericalGame::new();
+
+    play(&mut numerical_game, &["1 a1", "8 b1", "5 a2", "2 c3"]);
+
+    assert_eq!(numerical_game.result(), None);
+    assert_eq!(numerical_game.winning_move(), Some((9, (0, 2))));
+
+    play(&mut numerical_game, &["9 a3"]);
+
+    assert_eq!(numerical_game.result(), Some(GameState::XWon));
+    assert_eq!(error(&mut numerical_game, "4 b2"), "the game is over");
+  }
+
+  #[test]
+  fn full_rows_not_adding_to_fifteen_dont_win() {
+    let mut numerical_game = NumericalGame::new();
+
+    play(&mut numerical_game, &["1 a1", "2 a2", "3 a3"]);
+
+    assert_eq!(numerical_game.winning_row(), None);
+    assert_eq!(numerical_game.result(), None);
+  }
+
+  #[test]
+  fn numbers_belong_to_one_side_and_are_used_once() {
+    let mut numerical_game = NumericalGame::new();
+
+    assert_eq!(
+      error(&mut numerical_game, "4 a1"),
+      "4 isn't yours, you place the odd numbers"
+    );
+    assert_eq!(
+      error(&mut numerical_game, "11 a1"),
+      "numbers go from 1 to 9"
+    );
+
+    play(&mut numerical_game, &["7 b2", "4 a1"]);
+
+    assert_eq!(
+      error(&mut numerical_game, "7 c3"),
+      "7 has already been placed"
+    );
+    assert_eq!(error(&mut numerical_game, "3 b2"), "b2 is already taken");
+    assert_eq!(numerical_game.remaining_numbers(true), vec![1, 3, 5, 9]);
+    assert_eq!(numerical_game.remaining_numbers(false), vec![2, 6, 8]);
+  }
+
+  #[test]
+  fn moves_are_parsed() {
+    assert_eq!(parse_numerical_move("7 b2").unwrap(), (7, (1, 1)));
+    assert_eq!(parse_numerical_move("7 at 9\n").unwrap(), (7, (2, 2)));
+    assert!(parse_numerical_move("b2 7").is_err());
+    assert!(parse_numerical_move("7").is_err());
+  }
+}
+
+#[cfg(test)]
+mod bot {
+  use super::*;
+
+  #[test]
+  fn bot_fills_a_row() {
+    let mut numerical_game = NumericalGame::new();
+
+    play(&mut numerical_game, &["1 a1", "8 b1", "5 a2", "2 c3"]);
+
+    assert_eq!(
+      best_move(
+        &numerical_game,
+        search_depth::<NumericalGame>(Difficulty::Easy)
+      ),
+      Some((9, (0, 2)))
+    );
+  }
+
+  #[test]
+  fn bot_blocks_without_opening_another_row() {
+    let mut numerical_game = NumericalGame::new();
+
+    // Odd needs 9 on a3, and an even number there can't leave odd
+    // another row to fill.
+    play(&mut numerical_game, &["1 a1", "2 c3", "5 a2"]);
+
+    let numerical_move = best_move(
+      &numerical_game,
+      search_depth::<NumericalGame>(Difficulty::Medium),
+    )
+    .unwrap();
+    assert_eq!(numerical_move.1, (0, 2));
+
+    numerical_game.play(&numerical_move).unwrap();
+    assert_eq!(numerical_game.winning_move(), None);
+  }
+
+  #[test]
+  fn hard_bot_never_loses_to_medium() {
+    for hard_is_odd in [true, false] {
+      let mut numerical_game = NumericalGame::new();
+
+      // Skipping the slowest search, the opening, which matters least here.
+      play(&mut numerical_game, &["5 b2"]);
+
+      while numerical_game.result().is_none() {
+        let difficulty = if numerical_game.odd_to_move == hard_is_odd {
+          Difficulty::Hard
+        } else {
+          Difficulty::Medium
+        };
+
+        let numerical_move =
+          best_move(&numerical_game, search_depth::<NumericalGame>(difficulty)).unwrap();
+        numerical_game.play(&numerical_move).unwrap();
+      }
+
+      let lost = if hard_is_odd {
+        GameState::OWon
+      } else {
+        GameState::XWon
+      };
+
+      assert_ne!(numerical_game.result(), Some(lost));
+    }
+  }
+}
+
+#[cfg(test)]
+mod interface {
+  use super::*;
+
+  #[test]
+  fn board_shows_the_numbers_left() {
+    let mut numerical_game = NumericalGame::new();
+
+    play(&mut numerical_game, &["7 a1", "4 b2"]);
+
+    assert_eq!(
+      render_numerical(&numerical_game),
+      "     1   2   3\n\
+      \x20a   7 | . | .\n\
+      \x20   ---+---+---\n\
+      \x20b   . | 4 | .\n\
+      \x20   ---+---+---\n\
+      \x20c   . | . | .\n\
+      \x20Odd left: 1 3 5 9 | Even left: 2 6 8\n"
+    );
+  }
+
+  #[test]
+  fn session_reports_bad_moves_and_the_bot_replies() {
+    let gameconfig = GameConfig {
+      player_turn: true,
+      ..GameConfig::default()
+    };
+
+    let mut output = vec![];
+    let result = numerical_session(gameconfig, Cursor::new("8 b2\n9 b2\n"), &mut output);
+
+    assert_eq!(result.unwrap_err().to_string(), INPUT_ENDED_ERROR);
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.starts_with("You odd | Bot even | A row adding up to 15 wins\n"));
+    assert!(output.contains("8 isn't yours, you place the odd numbers"));
+    assert!(output.contains(" -- bot placed "));
+  }
+}
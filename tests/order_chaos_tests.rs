@@ -0,0 +1,210 @@
+use std::io::Cursor;
+use tictactoe_with_ai::bot::Difficulty;
+use tictactoe_with_ai::gameboard::BoardStates;
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::order_chaos::*;
+use tictactoe_with_ai::search::*;
+
+/// Places each "o c4" style move in turn, whoever's turn it is.
+fn place_all(order_chaos_game: &mut OrderChaosGame, moves: &[&str]) {
+  for order_chaos_move in moves {
+    let order_chaos_move =
+      parse_order_chaos_move(order_chaos_move, &order_chaos_game.grid_board).unwrap();
+
+    order_chaos_game.place(&order_chaos_move).unwrap();
+  }
+}
+
+/// Order with four X across the top, chaos having placed in the bottom corners.
+fn four_across() -> OrderChaosGame {
+  let mut order_chaos_game = OrderChaosGame::new();
+
+  place_all(
+    &mut order_chaos_game,
+    &["x a1", "o f1", "x a2", "o f6", "x a3", "x f3", "x a4"],
+  );
+
+  order_chaos_game
+}
+
+#[cfg(test)]
+mod rules {
+  use super::*;
+
+  #[test]
+  fn order_wins_with_five_of_either_symbol() {
+    let mut order_chaos_game = OrderChaosGame::new();
+
+    place_all(
+      &mut order_chaos_game,
+      &["o b2", "o c2", "o d2", "x e2", "o f2", "o a2"],
+    );
+
+    assert_eq!(order_chaos_game.result(), None);
+
+    let mut order_chaos_game = four_across();
+
+    place_all(&mut order_chaos_game, &["x e6", "x a5"]);
+
+    assert_eq!(order_chaos_game.result(), Some(OrderChaosResult::OrderWon));
+    assert_eq!(
+      order_chaos_game
+        .place(&(BoardStates::O, (5, 3)))
+        .unwrap_err()
+        .to_string(),
+      "the game is over"
+    );
+  }
+
+  #[test]
+  fn chaos_wins_by_filling_the_board() {
+    let rows = ["xxooxx", "ooxxoo", "xxooxx", "ooxxoo", "xxooxx", "ooxxoo"];
+    let mut order_chaos_game = OrderChaosGame::new();
+
+    for (row, symbols) in rows.iter().enumerate() {
+      for (column, symbol) in symbols.chars().enumerate() {
+        assert_eq!(order_chaos_game.result(), None);
+
+        let symbol = if symbol == 'x' {
+          BoardStates::X
+        } else {
+          BoardStates::O
+        };
+
+        order_chaos_game.place(&(symbol, (row, column))).unwrap();
+      }
+    }
+
+    assert_eq!(order_chaos_game.result(), Some(OrderChaosResult::ChaosWon));
+  }
+
+  #[test]
+  fn taken_tiles_are_refused() {
+    let mut order_chaos_game = four_across();
+
+    assert_eq!(
+      order_chaos_game
+        .place(&(BoardStates::O, (0, 0)))
+        .unwrap_err()
+        .to_string(),
+      "a1 is already taken"
+    );
+    assert_eq!(order_chaos_game.next_role, Role::Chaos);
+  }
+
+  #[test]
+  fn moves_are_parsed_either_way_round() {
+    let grid_board = OrderChaosGame::new().grid_board;
+
+    assert_eq!(
+      parse_order_chaos_move("o c4", &grid_board).unwrap(),
+      (BoardStates::O, (2, 3))
+    );
+    assert_eq!(
+      parse_order_chaos_move("F6 X\n", &grid_board).unwrap(),
+      (BoardStates::X, (5, 5))
+    );
+    assert_eq!(
+      parse_order_chaos_move("c4", &grid_board)
+        .unwrap_err()
+        .to_string(),
+      "give the symbol to place as well as the tile, e.g. 'o c4'"
+    );
+    assert!(parse_order_chaos_move("x g1", &grid_board).is_err());
+  }
+}
+
+#[cfg(test)]
+mod bot {
+  use super::*;
+
+  #[test]
+  fn each_side_judges_mixed_lines_its_own_way() {
+    let mut same_symbols = OrderChaosGame::new();
+    let mut mixed_symbols = OrderChaosGame::new();
+
+    place_all(&mut same_symbols, &["x a1", "x a2"]);
+    place_all(&mut mixed_symbols, &["x a1", "o a2"]);
+
+    assert!(same_symbols.evaluate_order() > mixed_symbols.evaluate_order());
+    assert!(same_symbols.evaluate_chaos() < mixed_symbols.evaluate_chaos());
+    // Only a1 to a5 has both in it.
+    assert_eq!(mixed_symbols.evaluate_chaos(), DEAD_LINE_SCORE);
+  }
+
+  #[test]
+  fn order_finishes_the_line() {
+    let mut order_chaos_game = four_across();
+
+    place_all(&mut order_chaos_game, &["o e6"]);
+
+    assert_eq!(
+      best_move(&order_chaos_game, 1),
+      Some((BoardStates::X, (0, 4)))
+    );
+  }
+
+  #[test]
+  fn chaos_ruins_the_line() {
+    let order_chaos_game = four_across();
+
+    assert_eq!(order_chaos_game.next_role, Role::Chaos);
+    assert_eq!(
+      best_move(
+        &order_chaos_game,
+        search_depth::<OrderChaosGame>(Difficulty::Medium)
+      ),
+      Some((BoardStates::O, (0, 4)))
+    );
+  }
+}
+
+#[cfg(test)]
+mod interface {
+  use super::*;
+
+  #[test]
+  fn session_plays_chaos_against_the_bot() {
+    let mut gameconfig = GameConfig::default();
+    gameconfig.bot.difficulty = Difficulty::Easy;
+
+    let script: String = ["a", "b", "c", "d", "e", "f"]
+      .into_iter()
+      .flat_map(|row| (1..=6).map(move |column| format!("o {row}{column}\n")))
+      .collect();
+
+    let mut output = vec![];
+    let order_chaos_result = order_chaos_session(
+      gameconfig,
+      OrderChaosGame::new(),
+      Role::Chaos,
+      Cursor::new(script),
+      &mut output,
+    )
+    .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.starts_with("You Chaos | Bot Order | "));
+    assert!(output.contains(" -- bot placed "));
+    assert!(output.ends_with(&format!("{order_chaos_result:?}\n")));
+  }
+
+  #[test]
+  fn session_reports_bad_moves() {
+    let mut output = vec![];
+    let result = order_chaos_session(
+      GameConfig::default(),
+      OrderChaosGame::new(),
+      Role::Order,
+      Cursor::new("c4\nz c4\n"),
+      &mut output,
+    );
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(result.unwrap_err().to_string(), INPUT_ENDED_ERROR);
+    assert!(output.contains("give the symbol to place as well as the tile"));
+    assert!(output.contains("pick a symbol to place, x or o"));
+  }
+}
@@ -0,0 +1,293 @@
+use std::io::Cursor;
+use tictactoe_with_ai::bot::Difficulty;
+use tictactoe_with_ai::gameboard::BoardStates;
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::quantum::*;
+
+fn play_all(quantum_game: &mut QuantumGame, moves: &[&str]) {
+  for quantum_move in moves {
+    let quantum_move = parse_quantum_move(quantum_move, quantum_game).unwrap();
+
+    quantum_game.play(&quantum_move).unwrap();
+  }
+}
+
+#[cfg(test)]
+mod rules {
+  use super::*;
+
+  #[test]
+  fn spooky_marks_sit_in_two_tiles() {
+    let mut quantum_game = QuantumGame::new();
+
+    play_all(&mut quantum_game, &["a1 b2", "b2 c3"]);
+
+    assert_eq!(quantum_game.spooky_marks.len(), 2);
+    assert_eq!(quantum_game.free_tiles().len(), 9);
+    assert_eq!(quantum_game.next_symbol, BoardStates::X);
+    assert!(quantum_game.is_linked(&(0, 0), &(2, 2)));
+    assert!(!quantum_game.is_linked(&(0, 0), &(1, 2)));
+    assert_eq!(quantum_game.pending_collapse, None);
+  }
+
+  #[test]
+  fn closing_a_cycle_waits_on_the_other_side() {
+    let mut quantum_game = QuantumGame::new();
+
+    play_all(&mut quantum_game, &["a1 b2", "b2 c3", "c3 a1"]);
+
+    let pending = quantum_game.pending_collapse.unwrap();
+
+    assert_eq!(pending.move_number, 3);
+    assert_eq!(quantum_game.next_symbol, BoardStates::O);
+    assert_eq!(
+      quantum_game.legal_moves(),
+      vec![QuantumMove::Collapse((2, 2)), QuantumMove::Collapse((0, 0))]
+    );
+    assert_eq!(
+      quantum_game
+        .play(&QuantumMove::Spooky((0, 1), (0, 2)))
+        .unwrap_err()
+        .to_string(),
+      "x₃ closed a cycle, pick which of c3 or a1 it collapses into first"
+    );
+    assert_eq!(
+      quantum_game
+        .play(&QuantumMove::Collapse((1, 1)))
+        .unwrap_err()
+        .to_string(),
+      "x₃ can only collapse into c3 or a1"
+    );
+  }
+
+  #[test]
+  fn collapsing_forces_the_whole_cycle() {
+    let mut quantum_game = QuantumGame::new();
+
+    play_all(&mut quantum_game, &["a1 b2", "b2 c3", "c3 a1", "a1"]);
+
+    assert_eq!(
+      quantum_game.classical_at(&(0, 0)),
+      Some((BoardStates::X, 3))
+    );
+    assert_eq!(
+      quantum_game.classical_at(&(2, 2)),
+      Some((BoardStates::O, 2))
+    );
+    assert_eq!(
+      quantum_game.classical_at(&(1, 1)),
+      Some((BoardStates::X, 1))
+    );
+    assert!(quantum_game.spooky_marks.is_empty());
+    assert_eq!(quantum_game.pending_collapse, None);
+
+    // The collapse doesn't use up O's turn.
+    assert_eq!(quantum_game.next_symbol, BoardStates::O);
+    assert_eq!(
+      quantum_game
+        .play(&QuantumMove::Spooky((0, 0), (0, 1)))
+        .unwrap_err()
+        .to_string(),
+      "a1 is already classical"
+    );
+  }
+
+  #[test]
+  fn marks_hanging_off_the_cycle_collapse_too() {
+    let mut quantum_game = QuantumGame::new();
+
+    play_all(&mut quantum_game, &["a1 a2", "a2 a3", "a1 a2", "a2"]);
+
+    // x₃ took a2, pushing x₁ back into a1 and o₂ on to a3.
+    assert_eq!(
+      quantum_game.classical_at(&(0, 0)),
+      Some((BoardStates::X, 1))
+    );
+    assert_eq!(
+      quantum_game.classical_at(&(0, 1)),
+      Some((BoardStates::X, 3))
+    );
+    assert_eq!(
+      quantum_game.classical_at(&(0, 2)),
+      Some((BoardStates::O, 2))
+    );
+  }
+
+  #[test]
+  fn the_row_finished_first_wins_a_tie() {
+    let mut quantum_game = QuantumGame::new();
+
+    for (column, numbers) in [(0, [1, 2]), (1, [3, 4]), (2, [7, 6])] {
+      quantum_game.classical_marks[0][column] = Some((BoardStates::X, numbers[0]));
+      quantum_game.classical_marks[2][column] = Some((BoardStates::O, numbers[1]));
+    }
+
+    assert_eq!(quantum_game.result(), Some(GameState::OWon));
+
+    quantum_game.classical_marks[2][2] = Some((BoardStates::O, 8));
+
+    assert_eq!(quantum_game.result(), Some(GameState::XWon));
+    assert!(quantum_game.legal_moves().is_empty());
+  }
+
+  #[test]
+  fn the_last_tile_gets_a_classical_mark() {
+    let mut quantum_game = QuantumGame::new();
+
+    for (index, symbol) in [
+      BoardStates::X,
+      BoardStates::O,
+      BoardStates::X,
+      BoardStates::X,
+      BoardStates::O,
+      BoardStates::O,
+      BoardStates::O,
+      BoardStates::X,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+      quantum_game.classical_marks[index / 3][index % 3] = Some((symbol, index as u8 + 1));
+    }
+
+    quantum_game.move_number = 8;
+
+    assert_eq!(
+      quantum_game.legal_moves(),
+      vec![QuantumMove::Classical((2, 2))]
+    );
+    assert!(parse_quantum_move("a1 c3", &quantum_game)
+      .and_then(|quantum_move| quantum_game.play(&quantum_move))
+      .is_err());
+
+    play_all(&mut quantum_game, &["c3"]);
+
+    assert_eq!(
+      quantum_game.classical_at(&(2, 2)),
+      Some((BoardStates::X, 9))
+    );
+    assert_eq!(quantum_game.result(), Some(GameState::Draw));
+  }
+}
+
+#[cfg(test)]
+mod bot {
+  use super::*;
+
+  #[test]
+  fn bot_picks_the_collapse_that_wins() {
+    let mut quantum_game = QuantumGame::new();
+
+    quantum_game.classical_marks[0][0] = Some((BoardStates::X, 1));
+    quantum_game.classical_marks[1][0] = Some((BoardStates::O, 2));
+    quantum_game.classical_marks[0][1] = Some((BoardStates::X, 3));
+    quantum_game.classical_marks[1][1] = Some((BoardStates::O, 4));
+    quantum_game.move_number = 4;
+
+    play_all(&mut quantum_game, &["a3 c3", "a3 c3"]);
+
+    // o₆ into c3 pushes x₅ into a3, finishing X's top row.
+    assert_eq!(
+      best_move(&quantum_game, Difficulty::Medium),
+      Some(QuantumMove::Collapse((2, 2)))
+    );
+  }
+
+  #[test]
+  fn bot_finishes_against_a_random_bot() {
+    let mut quantum_game = QuantumGame::new();
+
+    while quantum_game.result().is_none() {
+      let difficulty = match quantum_game.next_symbol {
+        BoardStates::X => Difficulty::Medium,
+        _ => Difficulty::Easy,
+      };
+
+      let quantum_move = best_move(&quantum_game, difficulty).unwrap();
+      quantum_game.play(&quantum_move).unwrap();
+    }
+
+    assert!(quantum_game.legal_moves().is_empty());
+    assert_eq!(quantum_game.pending_collapse, None);
+  }
+}
+
+#[cfg(test)]
+mod interface {
+  use super::*;
+
+  #[test]
+  fn moves_are_parsed_by_what_the_game_waits_on() {
+    let mut quantum_game = QuantumGame::new();
+
+    assert_eq!(
+      parse_quantum_move("a1 5\n", &quantum_game).unwrap(),
+      QuantumMove::Spooky((0, 0), (1, 1))
+    );
+    assert!(parse_quantum_move("a1", &quantum_game).is_err());
+    assert!(parse_quantum_move("a1 b2 c3", &quantum_game).is_err());
+
+    play_all(&mut quantum_game, &["a1 b2", "a1 b2"]);
+
+    assert_eq!(
+      parse_quantum_move("b2", &quantum_game).unwrap(),
+      QuantumMove::Collapse((1, 1))
+    );
+  }
+
+  #[test]
+  fn tiles_show_every_mark_in_them() {
+    let mut quantum_game = QuantumGame::new();
+
+    play_all(&mut quantum_game, &["a1 b2", "a1 c3", "b2 c3"]);
+
+    let rendered = render_quantum(&quantum_game);
+
+    assert!(rendered.contains(" a  x₁ o₂ "));
+    assert!(rendered.contains(" b  "));
+    assert!(rendered.contains("x₁ x₃"));
+    assert!(rendered.contains("o₂ x₃"));
+    assert!(rendered.contains("x₃ closed a cycle, O picks whether it collapses into b2 or c3"));
+
+    play_all(&mut quantum_game, &["b2"]);
+
+    let rendered = render_quantum(&quantum_game);
+
+    assert!(rendered.contains("X₃"));
+    assert!(rendered.contains("O₂"));
+    assert!(rendered.contains("X₁"));
+  }
+
+  #[test]
+  fn session_plays_until_the_game_ends() {
+    let mut gameconfig = GameConfig {
+      player_symbol: BoardStates::X,
+      ..GameConfig::default()
+    };
+
+    gameconfig.bot.bot_symbol = BoardStates::O;
+    gameconfig.bot.difficulty = Difficulty::Easy;
+
+    // Every pair then every single tile, the ones that don't fit being refused.
+    let tiles: Vec<String> = (1..=9).map(|tile| tile.to_string()).collect();
+    let mut script = String::new();
+
+    for (index, first) in tiles.iter().enumerate() {
+      for second in &tiles[index + 1..] {
+        script += &format!("{first} {second}\n");
+      }
+    }
+
+    script += &tiles.join("\n");
+    script += "\n";
+
+    let mut output = vec![];
+    let end_gamestate =
+      quantum_session(gameconfig, Cursor::new(script.repeat(20)), &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains(" -- bot "));
+    assert!(output.ends_with(&format!("{end_gamestate:?}\n")));
+  }
+}
@@ -0,0 +1,71 @@
+use std::time::Duration;
+use tictactoe_with_ai::bot::{Difficulty, SearchBudget};
+use tictactoe_with_ai::gravity::*;
+use tictactoe_with_ai::mcts::Game;
+use tictactoe_with_ai::search::*;
+
+fn drop_all(gravity_game: &mut GravityGame, columns: &[usize]) {
+  for column in columns {
+    gravity_game.drop_piece(column - 1).unwrap();
+  }
+}
+
+#[cfg(test)]
+mod evaluation {
+  use super::*;
+
+  #[test]
+  fn lines_are_worth_more_the_fuller_they_are() {
+    assert_eq!(line_score(0), 1);
+    assert_eq!(line_score(3), 64);
+    assert_eq!(line_score(40), i64::MAX);
+  }
+
+  #[test]
+  fn totals_stay_within_the_limit() {
+    assert_eq!(total_evaluation([16, -4].into_iter()), 12);
+    assert_eq!(
+      total_evaluation([i64::MAX, i64::MAX].into_iter()),
+      MAX_EVALUATION
+    );
+    assert_eq!(total_evaluation([i64::MIN].into_iter()), -MAX_EVALUATION);
+  }
+}
+
+#[cfg(test)]
+mod alpha_beta {
+  use super::*;
+
+  #[test]
+  fn depths_come_from_the_game() {
+    assert_eq!(search_depth::<GravityGame>(Difficulty::Easy), 1);
+    assert_eq!(search_depth::<GravityGame>(Difficulty::Hard), 6);
+  }
+
+  #[test]
+  fn takes_a_win_over_blocking() {
+    let mut gravity_game = GravityGame::new(7, 6, 4).unwrap();
+
+    // Both sides have three along the bottom, X to move.
+    drop_all(&mut gravity_game, &[1, 1, 2, 2, 3, 3]);
+
+    assert_eq!(best_move(&gravity_game, 3), Some(3));
+  }
+
+  #[test]
+  fn budget_still_finishes_the_first_search() {
+    let mut gravity_game = GravityGame::new(7, 6, 4).unwrap();
+
+    drop_all(&mut gravity_game, &[1, 1, 2, 2, 3, 3]);
+
+    let budget = SearchBudget {
+      max_depth: 8,
+      time_limit: Duration::ZERO,
+    };
+
+    assert_eq!(
+      best_move_within(&gravity_game, &gravity_game.legal_moves(), budget),
+      Some(3)
+    );
+  }
+}
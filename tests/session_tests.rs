@@ -0,0 +1,20 @@
+use tictactoe_with_ai::session::*;
+
+#[test]
+fn two_player_seats_put_the_person_where_they_play() {
+  let seats = two_player_seats("Ada", 1);
+
+  assert_eq!(
+    seats,
+    vec![
+      Seat {
+        name: "Bot".to_string(),
+        is_person: false
+      },
+      Seat {
+        name: "Ada".to_string(),
+        is_person: true
+      },
+    ]
+  );
+}
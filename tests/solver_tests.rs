@@ -0,0 +1,113 @@
+use tictactoe_with_ai::gameboard::*;
+use tictactoe_with_ai::solver::*;
+
+#[test]
+fn empty_board_is_a_draw() {
+  let gameboard = BoardConfig::new();
+
+  let scores = score_moves(&gameboard);
+
+  assert_eq!(scores.len(), 9);
+  assert!(scores.iter().all(|(_, score)| *score == DRAW_SCORE));
+}
+
+#[test]
+fn winning_tile_is_best() {
+  let expected_move = Some((0, 2));
+
+  //X|X|-
+  //O|O|-
+  //-|-|-
+  let gameboard = BoardConfig::from_notation("xx.oo....").unwrap();
+
+  assert_eq!(best_move(&gameboard), expected_move);
+}
+
+#[test]
+fn missing_the_block_loses() {
+  //X|-|-
+  //-|O|-
+  //-|-|X
+  let gameboard = BoardConfig::from_notation("x...o...x").unwrap();
+
+  let scores = score_moves(&gameboard);
+
+  // only the edges hold the draw for O
+  for (coords, score) in scores {
+    let expected_score = if gameboard.get_board_position(&coords) == &BoardPositions::Edge {
+      DRAW_SCORE
+    } else {
+      LOSS_SCORE
+    };
+
+    assert_eq!(score, expected_score, "{coords:?}");
+  }
+}
+
+#[test]
+fn finished_game_has_no_moves() {
+  let gameboard = BoardConfig::from_notation("xxxoo....").unwrap();
+
+  assert_eq!(score_moves(&gameboard), vec![]);
+}
+
+#[cfg(test)]
+mod misere {
+  use super::*;
+
+  #[test]
+  fn center_is_the_only_safe_opening() {
+    let gameboard = BoardConfig::new();
+
+    let scores = misere_score_moves(&gameboard);
+
+    assert_eq!(misere_best_move(&gameboard), Some((1, 1)));
+    assert!(scores
+      .iter()
+      .all(|(coords, score)| (*score == DRAW_SCORE) == (*coords == (1, 1))));
+  }
+
+  #[test]
+  fn completing_a_row_loses() {
+    //X|X|-
+    //O|O|-
+    //-|-|-
+    let gameboard = BoardConfig::from_notation("xx.oo....").unwrap();
+
+    let scores = misere_score_moves(&gameboard);
+
+    assert!(scores.contains(&((0, 2), LOSS_SCORE)));
+    assert_ne!(misere_best_move(&gameboard), Some((0, 2)));
+  }
+}
+
+#[cfg(test)]
+mod wild {
+  use super::*;
+
+  #[test]
+  fn first_to_place_wins_from_the_center() {
+    let gameboard = BoardConfig::new();
+
+    let scores = wild_score_moves(&gameboard);
+
+    assert_eq!(scores.len(), 18);
+    assert!(scores.contains(&((BoardStates::X, (1, 1)), WIN_SCORE)));
+    assert!(scores.contains(&((BoardStates::O, (1, 1)), WIN_SCORE)));
+  }
+
+  #[test]
+  fn any_completed_row_wins() {
+    //X|X|-
+    //O|-|-
+    //O|-|-
+    let gameboard = BoardConfig::from_notation("xx.o..o..").unwrap();
+
+    let scores = wild_score_moves(&gameboard);
+
+    // An O on b2 leaves a row of O for the other side to finish.
+    assert!(scores.contains(&((BoardStates::X, (0, 2)), WIN_SCORE)));
+    assert!(scores.contains(&((BoardStates::O, (1, 1)), LOSS_SCORE)));
+    assert_eq!(wild_best_move(&gameboard), Some((BoardStates::X, (0, 2))));
+  }
+}
@@ -0,0 +1,93 @@
+use std::io::Cursor;
+use tictactoe_with_ai::bot::Difficulty;
+use tictactoe_with_ai::gameboard::BoardStates;
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::grid_board::parse_grid_tile;
+use tictactoe_with_ai::search::*;
+use tictactoe_with_ai::torus::*;
+
+fn place_all(torus_game: &mut TorusGame, tiles: &[&str]) {
+  for tile in tiles {
+    let coords = parse_grid_tile(tile, &torus_game.grid_board).unwrap();
+
+    torus_game.place(&coords).unwrap();
+  }
+}
+
+#[cfg(test)]
+mod bot {
+  use super::*;
+
+  #[test]
+  fn bot_wins_across_the_edge() {
+    let mut torus_game = TorusGame::new(4, 4).unwrap();
+
+    place_all(&mut torus_game, &["b4", "c3", "b1", "d1", "b2", "a2"]);
+
+    assert_eq!(best_move(&torus_game, 1), Some((1, 2)));
+  }
+
+  #[test]
+  fn bot_blocks_a_broken_diagonal() {
+    let mut torus_game = TorusGame::new(5, 4).unwrap();
+
+    // X has b4, c5 and d1 on a diagonal wrapping past the right edge, O has a3 behind it.
+    place_all(&mut torus_game, &["b4", "a3", "c5", "e3", "d1"]);
+
+    assert_eq!(
+      best_move(&torus_game, search_depth::<TorusGame>(Difficulty::Medium)),
+      Some((4, 1))
+    );
+  }
+
+  #[test]
+  fn candidates_are_next_to_pieces() {
+    let mut torus_game = TorusGame::new(5, 4).unwrap();
+
+    assert_eq!(torus_game.candidate_tiles().len(), 25);
+
+    place_all(&mut torus_game, &["a1"]);
+
+    let candidates = torus_game.candidate_tiles();
+
+    assert_eq!(candidates.len(), 8);
+    assert!(candidates.contains(&(4, 4)));
+  }
+}
+
+#[cfg(test)]
+mod interface {
+  use super::*;
+
+  #[test]
+  fn session_plays_until_the_game_ends() {
+    let mut gameconfig = GameConfig {
+      player_symbol: BoardStates::X,
+      ..GameConfig::default()
+    };
+
+    gameconfig.bot.difficulty = Difficulty::Easy;
+
+    let tiles: Vec<String> = ["a", "b", "c", "d"]
+      .into_iter()
+      .flat_map(|row| (1..=4).map(move |column| format!("{row}{column}\n")))
+      .collect();
+
+    let script = format!("e1\n{}", tiles.concat());
+
+    let mut output = vec![];
+    let end_gamestate = torus_session(
+      gameconfig,
+      TorusGame::new(4, 4).unwrap(),
+      Cursor::new(script),
+      &mut output,
+    )
+    .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("e1 is off the 4x4 board"));
+    assert!(output.contains(" -- bot placed on "));
+    assert!(output.ends_with(&format!("{end_gamestate:?}\n")));
+  }
+}
@@ -0,0 +1,128 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tictactoe_with_ai::board_render::Theme;
+use tictactoe_with_ai::gameboard::*;
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::tui::*;
+
+fn tui_state_with_player_symbol(player_symbol: BoardStates) -> TuiState {
+  let mut gameconfig = GameConfig::new();
+
+  gameconfig.player_symbol = player_symbol;
+  gameconfig.bot.bot_symbol = if player_symbol == BoardStates::X {
+    BoardStates::O
+  } else {
+    BoardStates::X
+  };
+
+  TuiState::new(gameconfig)
+}
+
+#[test]
+fn cursor_stays_on_the_board() {
+  let mut tui_state = tui_state_with_player_symbol(BoardStates::X);
+
+  for key in [TuiKey::Up, TuiKey::Up, TuiKey::Left, TuiKey::Left] {
+    tui_state.handle_key(key);
+  }
+
+  assert_eq!(tui_state.cursor, (0, 0));
+
+  for key in [TuiKey::Down, TuiKey::Down, TuiKey::Down, TuiKey::Right] {
+    tui_state.handle_key(key);
+  }
+
+  assert_eq!(tui_state.cursor, (2, 1));
+}
+
+#[test]
+fn selecting_places_tile_and_bot_replies() {
+  let mut tui_state = tui_state_with_player_symbol(BoardStates::X);
+
+  tui_state.handle_key(TuiKey::Select);
+
+  assert_eq!(
+    tui_state.gameconfig.gameboard.get_board_state(&(1, 1)),
+    &BoardStates::X
+  );
+  assert_eq!(tui_state.move_history.len(), 2);
+  assert_eq!(tui_state.move_history[0], (BoardStates::X, (1, 1)));
+
+  tui_state.handle_key(TuiKey::Select);
+
+  assert_eq!(tui_state.message, "b2 is taken");
+  assert_eq!(tui_state.move_history.len(), 2);
+}
+
+#[test]
+fn bot_opens_when_it_is_x() {
+  let tui_state = tui_state_with_player_symbol(BoardStates::O);
+
+  assert_eq!(tui_state.move_history.len(), 1);
+  assert_eq!(tui_state.move_history[0].0, BoardStates::X);
+}
+
+#[test]
+fn finished_games_are_scored() {
+  let mut tui_state = tui_state_with_player_symbol(BoardStates::X);
+
+  while !tui_state.is_game_over() {
+    let empty_tile = (0..9)
+      .map(|index| (index / 3, index % 3))
+      .find(|coords| tui_state.gameconfig.gameboard.get_board_state(coords) == &BoardStates::Empty)
+      .unwrap();
+
+    tui_state.cursor = empty_tile;
+    tui_state.handle_key(TuiKey::Select);
+  }
+
+  let scoreboard = tui_state.scoreboard.clone();
+
+  assert_eq!(scoreboard.wins, 0);
+  assert_eq!(scoreboard.losses + scoreboard.draws, 1);
+
+  tui_state.handle_key(TuiKey::NewGame);
+
+  assert_eq!(tui_state.scoreboard, scoreboard);
+  assert!(tui_state.move_history.len() <= 1);
+}
+
+#[test]
+fn screen_shows_board_history_and_status() {
+  let mut tui_state = tui_state_with_player_symbol(BoardStates::X);
+
+  tui_state.handle_key(TuiKey::Up);
+  tui_state.handle_key(TuiKey::Left);
+  tui_state.handle_key(TuiKey::Select);
+
+  let screen_lines = tui_state.screen_lines();
+
+  assert_eq!(screen_lines[0], "     1   2   3      Moves");
+  assert_eq!(screen_lines[1], " a  [X]|   |        1. X a1");
+  assert!(screen_lines[2].ends_with("2. O b2"));
+  assert!(screen_lines[7].starts_with(" You X | Bot O | Your turn | Wins 0"));
+}
+
+#[test]
+fn screen_uses_the_chosen_theme() {
+  let mut tui_state = tui_state_with_player_symbol(BoardStates::X);
+
+  tui_state.gameconfig.theme = Theme::Compact;
+  tui_state.handle_key(TuiKey::Up);
+  tui_state.handle_key(TuiKey::Left);
+
+  assert_eq!(tui_state.screen_lines()[0], "[.]../.../...       Moves");
+}
+
+#[test]
+fn keys_are_mapped() {
+  let key = |code| key_from_event(KeyEvent::new(code, KeyModifiers::NONE));
+
+  assert_eq!(key(KeyCode::Up), Some(TuiKey::Up));
+  assert_eq!(key(KeyCode::Enter), Some(TuiKey::Select));
+  assert_eq!(key(KeyCode::Char('q')), Some(TuiKey::Quit));
+  assert_eq!(key(KeyCode::Char('z')), None);
+  assert_eq!(
+    key_from_event(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+    Some(TuiKey::Quit)
+  );
+}
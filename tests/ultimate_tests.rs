@@ -0,0 +1,210 @@
+use tictactoe_with_ai::bot::Difficulty;
+use tictactoe_with_ai::gameboard::*;
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::search::best_move;
+use tictactoe_with_ai::ultimate::*;
+
+fn play(ultimate_board: &mut UltimateBoard, moves: &[&str]) {
+  for ultimate_move in moves {
+    let ultimate_move = parse_ultimate_move(ultimate_move, None).unwrap();
+
+    ultimate_board.place(&ultimate_move).unwrap();
+  }
+}
+
+/// X wins sub-board a1 along its top row, O sending X back there each time.
+fn x_wins_top_left_sub_board() -> UltimateBoard {
+  let mut ultimate_board = UltimateBoard::new();
+
+  play(
+    &mut ultimate_board,
+    &["a1 a2", "a2 a1", "a1 a3", "a3 a1", "a1 a1"],
+  );
+
+  ultimate_board
+}
+
+#[test]
+fn cell_picks_the_next_sub_board() {
+  let mut ultimate_board = UltimateBoard::new();
+
+  play(&mut ultimate_board, &["b2 c3"]);
+
+  assert_eq!(ultimate_board.next_sub_board, Some((2, 2)));
+  assert_eq!(ultimate_board.next_symbol, BoardStates::O);
+  assert_eq!(ultimate_board.legal_moves().len(), 9);
+  assert!(ultimate_board
+    .legal_moves()
+    .iter()
+    .all(|(sub_board, _)| sub_board == &(2, 2)));
+}
+
+#[test]
+fn illegal_moves_are_refused() {
+  let mut ultimate_board = UltimateBoard::new();
+
+  play(&mut ultimate_board, &["b2 b2", "b2 a1"]);
+
+  let wrong_sub_board = ultimate_board.place(&((1, 1), (0, 0)));
+  let taken_cell = ultimate_board.place(&((0, 0), (0, 0)));
+
+  assert_eq!(
+    wrong_sub_board.unwrap_err().to_string(),
+    "this move has to be in sub-board a1"
+  );
+  assert!(taken_cell.is_ok());
+
+  play(&mut ultimate_board, &["a1 b2"]);
+
+  assert_eq!(
+    ultimate_board
+      .place(&((1, 1), (1, 1)))
+      .unwrap_err()
+      .to_string(),
+    "b2 is already taken"
+  );
+}
+
+#[test]
+fn won_sub_board_marks_the_meta_board() {
+  let mut ultimate_board = x_wins_top_left_sub_board();
+
+  assert_eq!(
+    ultimate_board.meta_board.get_board_state(&(0, 0)),
+    &BoardStates::X
+  );
+  assert!(!ultimate_board.is_sub_board_open(&(0, 0)));
+
+  // O sends X to a1, which is already won, so X can place anywhere but a1.
+  play(&mut ultimate_board, &["b1 a1"]);
+
+  assert_eq!(ultimate_board.next_sub_board, None);
+  assert!(ultimate_board
+    .legal_moves()
+    .iter()
+    .all(|(sub_board, _)| sub_board != &(0, 0)));
+  assert_eq!(
+    ultimate_board
+      .place(&((0, 0), (2, 2)))
+      .unwrap_err()
+      .to_string(),
+    "sub-board a1 is already finished"
+  );
+}
+
+#[test]
+fn three_won_sub_boards_win_the_game() {
+  let mut ultimate_board = UltimateBoard::new();
+
+  for meta_column in 0..3 {
+    ultimate_board.sub_boards[0][meta_column] = BoardConfig::from_notation("xx.oo....").unwrap();
+  }
+
+  ultimate_board
+    .meta_board
+    .place_tile(&(0, 0), BoardStates::X);
+  ultimate_board
+    .meta_board
+    .place_tile(&(0, 1), BoardStates::X);
+  ultimate_board.sub_boards[0][0] = BoardConfig::from_notation("xxxoo....").unwrap();
+  ultimate_board.sub_boards[0][1] = BoardConfig::from_notation("xxxoo....").unwrap();
+
+  assert_eq!(best_move(&ultimate_board, 1), Some(((0, 2), (0, 2))));
+
+  ultimate_board.place(&((0, 2), (0, 2))).unwrap();
+
+  assert_eq!(ultimate_board.result(), Some(GameState::XWon));
+  assert!(ultimate_board.legal_moves().is_empty());
+}
+
+#[test]
+fn search_blocks_a_sub_board_win() {
+  let mut ultimate_board = UltimateBoard::new();
+
+  // O has two in a row in b2 and X is sent there.
+  play(
+    &mut ultimate_board,
+    &[
+      "a1 b2", "b2 a1", "a1 a1", "a1 b1", "b1 b2", "b2 a2", "a2 b2",
+    ],
+  );
+
+  assert_eq!(ultimate_board.next_sub_board, Some((1, 1)));
+  assert_eq!(best_move(&ultimate_board, 2), Some(((1, 1), (0, 2))));
+}
+
+#[test]
+fn moves_are_parsed() {
+  assert_eq!(
+    parse_ultimate_move("b2 a1", None).unwrap(),
+    ((1, 1), (0, 0))
+  );
+  assert_eq!(
+    parse_ultimate_move("5 9\n", None).unwrap(),
+    ((1, 1), (2, 2))
+  );
+  assert_eq!(
+    parse_ultimate_move("c3", Some((0, 1))).unwrap(),
+    ((0, 1), (2, 2))
+  );
+  assert!(parse_ultimate_move("c3", None).is_err());
+  assert!(parse_ultimate_move("a1 b2 c3", None).is_err());
+}
+
+#[test]
+fn board_is_rendered() {
+  let expected_render = "         1       2       3
+     +-------+-------+-------+
+     |       | O . . | O . . |
+  a  |   X   | . . . | . . . |
+     |       | . . . | . . . |
+     +-------+-------+-------+
+     | . . . | . . . | . . . |
+  b  | . . . | . . . | . . . |
+     | . . . | . . . | . . . |
+     +-------+-------+-------+
+     | . . . | . . . | . . . |
+  c  | . . . | . . . | . . . |
+     | . . . | . . . | . . . |
+     +-------+-------+-------+
+O places anywhere
+";
+
+  assert_eq!(
+    render_ultimate(&x_wins_top_left_sub_board()),
+    expected_render
+  );
+}
+
+#[test]
+fn next_sub_board_and_last_move_are_marked() {
+  let mut ultimate_board = UltimateBoard::new();
+
+  play(&mut ultimate_board, &["b2 b3"]);
+
+  let render = render_ultimate(&ultimate_board);
+  let lines: Vec<&str> = render.lines().collect();
+
+  assert_eq!(lines[5], "     +-------+-------+#######+");
+  assert_eq!(lines[7], "  b  | . . . | . .[X]# . . . #");
+  assert_eq!(lines[9], "     +-------+-------+#######+");
+  assert_eq!(lines[14], "O places in b3");
+}
+
+#[test]
+fn scripted_game_against_the_search() {
+  let mut gameconfig = GameConfig::new();
+  gameconfig.player_symbol = BoardStates::X;
+  gameconfig.bot.bot_symbol = BoardStates::O;
+  gameconfig.bot.difficulty = Difficulty::Easy;
+
+  let mut transcript = Vec::new();
+  let script = "b2 b2\nz9\n";
+
+  let end_gamestate = ultimate_session(gameconfig, script.as_bytes(), &mut transcript);
+  let transcript = String::from_utf8(transcript).unwrap();
+
+  assert_eq!(end_gamestate.unwrap_err().to_string(), INPUT_ENDED_ERROR);
+  assert!(transcript.contains(" -- bot placed b2 "));
+  assert!(transcript.contains("incorrect input"));
+}
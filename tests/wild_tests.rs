@@ -0,0 +1,83 @@
+use std::io::Cursor;
+use tictactoe_with_ai::bot::Difficulty;
+use tictactoe_with_ai::gameboard::*;
+use tictactoe_with_ai::gameplay::*;
+use tictactoe_with_ai::wild::*;
+
+fn gameconfig(player_turn: bool, difficulty: Difficulty) -> GameConfig {
+  let mut gameconfig = GameConfig {
+    player_turn,
+    ..GameConfig::default()
+  };
+  gameconfig.bot.difficulty = difficulty;
+
+  gameconfig
+}
+
+#[test]
+fn moves_are_parsed_either_way_round() {
+  assert_eq!(parse_wild_move("o b2").unwrap(), (BoardStates::O, (1, 1)));
+  assert_eq!(parse_wild_move("3 X\n").unwrap(), (BoardStates::X, (0, 2)));
+  assert_eq!(
+    parse_wild_move("b2").unwrap_err().to_string(),
+    "give the symbol to place as well as the tile, e.g. 'o b2'"
+  );
+  assert!(parse_wild_move("z b2").is_err());
+}
+
+#[test]
+fn taken_tiles_are_refused() {
+  let mut gameboard = BoardConfig::new();
+
+  place_wild_move(&mut gameboard, &(BoardStates::O, (0, 0))).unwrap();
+
+  assert_eq!(
+    place_wild_move(&mut gameboard, &(BoardStates::X, (0, 0)))
+      .unwrap_err()
+      .to_string(),
+    "a1 is already taken"
+  );
+  assert_eq!(gameboard.tiles_covered, 1);
+}
+
+#[test]
+fn player_wins_by_completing_a_row() {
+  //X|O|-
+  //O|X|-
+  //X|X|O
+  let mut gameconfig = gameconfig(true, Difficulty::Easy);
+  gameconfig.gameboard = BoardConfig::from_notation("xo.ox.xxo").unwrap();
+
+  let mut output = vec![];
+  let wild_result = wild_session(gameconfig, Cursor::new("o a1\nx a3\n"), &mut output).unwrap();
+
+  let output = String::from_utf8(output).unwrap();
+
+  // The player placed first, so they count as X.
+  assert_eq!(wild_result, GameState::XWon);
+  assert!(output.contains("a1 is already taken"));
+  assert!(output.ends_with("XWon\n"));
+}
+
+#[test]
+fn hard_bot_wins_when_it_places_first() {
+  let script = [
+    "x a1", "o a2", "x a3", "o b1", "x b3", "o c1", "x c2", "o c3",
+  ]
+  .map(|wild_move| format!("{wild_move}\n"))
+  .concat();
+
+  let mut output = vec![];
+  let wild_result = wild_session(
+    gameconfig(false, Difficulty::Hard),
+    Cursor::new(script),
+    &mut output,
+  )
+  .unwrap();
+
+  // The bot placed first, so it counts as X.
+  assert_eq!(wild_result, GameState::XWon);
+  assert!(String::from_utf8(output)
+    .unwrap()
+    .contains(" -- bot placed "));
+}